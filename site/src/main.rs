@@ -16,8 +16,8 @@ use sherwood::{
     Asset, Breadcrumb, BuildError, NavItem, Page, PageContext, ParserRegistry, run_cli,
 };
 
-/// Bundled stylesheet, embedded at compile time and written to the output as
-/// `style.css` after the build.
+/// Bundled stylesheet, embedded at compile time and written to the output
+/// after the build (as `style.css` unless `--stylesheet` names another file).
 const STYLE: &str = include_str!("../assets/style.css");
 
 #[derive(TemplateSimple)]
@@ -30,6 +30,8 @@ struct PageTemplate<'a> {
     prev: Option<&'a NavItem>,
     next: Option<&'a NavItem>,
     base_path: &'a str,
+    stylesheet: &'a str,
+    lang: &'a str,
 }
 
 /// The render closure handed to `build_site` via `run_cli`. Maps each page +
@@ -43,6 +45,8 @@ fn render(page: &Page, ctx: &PageContext) -> Result<String, BuildError> {
         prev: ctx.prev.as_ref(),
         next: ctx.next.as_ref(),
         base_path: &ctx.base_path,
+        stylesheet: &ctx.stylesheet,
+        lang: &ctx.lang,
     }
     .render_once()
     .map_err(|e| BuildError::Render(e.to_string()))
@@ -0,0 +1,104 @@
+//! Cleanup for the fingerprinted stylesheet (`--fingerprint-stylesheet`):
+//! the names themselves come from `core::fingerprint`.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::fingerprint::HASH_LEN;
+pub(super) use crate::core::fingerprint::{fingerprinted_name, fnv1a};
+
+/// Delete fingerprinted copies of the stylesheet left by earlier builds:
+/// siblings of `current` (relative to `output_dir`) named
+/// `<stem>.<hash>.<ext>`, where `<stem>.<ext>` is `current` with any
+/// fingerprint removed. `current` itself is kept. Returns the removed paths.
+pub(super) fn remove_stale(output_dir: &Path, current: &str) -> std::io::Result<Vec<PathBuf>> {
+    let current = output_dir.join(current);
+    let (Some(dir), Some(file)) = (
+        current.parent(),
+        current.file_name().and_then(|f| f.to_str()),
+    ) else {
+        return Ok(Vec::new());
+    };
+    let (stem, ext) = base_name(file);
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut removed = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path == current || !path.is_file() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        if let Some((s, e)) = split_fingerprint(name)
+            && s == stem
+            && e == ext
+        {
+            std::fs::remove_file(&path)?;
+            removed.push(path);
+        }
+    }
+    Ok(removed)
+}
+
+/// `(stem, ext)` of a file name with any fingerprint removed:
+/// `style.0123456789abcdef.css` and `style.css` both give `("style", "css")`.
+fn base_name(file: &str) -> (&str, &str) {
+    if let Some(parts) = split_fingerprint(file) {
+        return parts;
+    }
+    match file.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, ext),
+        _ => (file, ""),
+    }
+}
+
+/// Split `<stem>.<hash>.<ext>` into `(stem, ext)`, or `None` when the middle
+/// segment isn't a fingerprint.
+fn split_fingerprint(file: &str) -> Option<(&str, &str)> {
+    let (rest, ext) = file.rsplit_once('.')?;
+    let (stem, hash) = rest.rsplit_once('.')?;
+    let is_hash = hash.len() == HASH_LEN
+        && hash
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    (is_hash && !stem.is_empty()).then_some((stem, ext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_stale_keeps_current_and_unrelated_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let old = "style.0123456789abcdef.css";
+        let current = fingerprinted_name("style.css", b"new");
+        for f in [
+            old,
+            current.as_str(),
+            "style.css",
+            "other.0123456789abcdef.css",
+        ] {
+            std::fs::write(tmp.path().join(f), "x").unwrap();
+        }
+
+        let removed = remove_stale(tmp.path(), &current).unwrap();
+
+        assert_eq!(removed, vec![tmp.path().join(old)]);
+        assert!(tmp.path().join(&current).exists());
+        assert!(tmp.path().join("style.css").exists());
+        assert!(tmp.path().join("other.0123456789abcdef.css").exists());
+    }
+
+    #[test]
+    fn remove_stale_without_fingerprinting_clears_old_copies() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("style.0123456789abcdef.css"), "x").unwrap();
+        remove_stale(tmp.path(), "style.css").unwrap();
+        assert!(!tmp.path().join("style.0123456789abcdef.css").exists());
+    }
+}
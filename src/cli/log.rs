@@ -0,0 +1,227 @@
+//! CLI output levels (`--quiet`, `--verbose`) and formats (`--log-format`).
+//!
+//! The library never prints — it reports through callbacks and return
+//! values — so everything the binary writes goes through a [`Log`]. Text
+//! output keeps the familiar shape: progress on stdout, `warning:` lines on
+//! stderr. JSON writes one object per line to stdout, for CI to parse.
+//! Warnings are also counted, for `--fail-on-warning`.
+
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
+
+use crate::core::date::format_system_time;
+
+/// How much the CLI prints. Warnings and errors show at every level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Level {
+    /// Warnings and errors only.
+    Quiet,
+    /// Plus progress and summaries ("Build complete.").
+    Normal,
+    /// Plus per-file detail (each page built).
+    Verbose,
+}
+
+/// How the CLI prints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line: `{"time", "level", "event", "message", …}`.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format `{other}` (expected text or json)"
+            )),
+        }
+    }
+}
+
+/// A field value in a JSON event. Numbers stay numbers so consumers can sum
+/// timings without parsing strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Value {
+    Str(String),
+    Int(u128),
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::Str(s)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Self::Int(n as u128)
+    }
+}
+
+impl From<u128> for Value {
+    fn from(n: u128) -> Self {
+        Self::Int(n)
+    }
+}
+
+/// Where CLI output goes. Cheap to clone into closures and threads; clones
+/// share one warning count.
+#[derive(Debug, Clone)]
+pub(super) struct Log {
+    level: Level,
+    format: LogFormat,
+    warnings: Arc<AtomicUsize>,
+}
+
+impl Log {
+    pub(super) fn new(level: Level, format: LogFormat) -> Self {
+        Self {
+            level,
+            format,
+            warnings: Arc::default(),
+        }
+    }
+
+    /// Warnings emitted so far, through this log or any clone of it.
+    pub(super) fn warnings(&self) -> usize {
+        self.warnings.load(Ordering::Relaxed)
+    }
+
+    /// Per-file detail, shown with `--verbose`.
+    pub(super) fn verbose(
+        &self,
+        event: &str,
+        message: impl fmt::Display,
+        fields: &[(&str, Value)],
+    ) {
+        if self.level >= Level::Verbose {
+            self.emit("verbose", event, &message.to_string(), fields);
+        }
+    }
+
+    /// Progress and summaries, hidden by `--quiet`.
+    pub(super) fn info(&self, event: &str, message: impl fmt::Display, fields: &[(&str, Value)]) {
+        if self.level >= Level::Normal {
+            self.emit("info", event, &message.to_string(), fields);
+        }
+    }
+
+    /// A problem that doesn't stop the run. Text goes to stderr as
+    /// `warning: <message>`.
+    pub(super) fn warn(&self, event: &str, message: impl fmt::Display, fields: &[(&str, Value)]) {
+        self.warnings.fetch_add(1, Ordering::Relaxed);
+        self.emit("warning", event, &message.to_string(), fields);
+    }
+
+    /// A failure: one the run continues past (a watch-mode rebuild), or the
+    /// one that ends it. Text goes to stderr as-is.
+    pub(super) fn error(&self, event: &str, message: impl fmt::Display) {
+        self.emit("error", event, &message.to_string(), &[]);
+    }
+
+    fn emit(&self, level: &str, event: &str, message: &str, fields: &[(&str, Value)]) {
+        let time = format_system_time(SystemTime::now());
+        match (self.format, level) {
+            (LogFormat::Json, _) => println!("{}", json_line(&time, level, event, message, fields)),
+            (LogFormat::Text, "warning") => eprintln!("warning: {message}"),
+            (LogFormat::Text, "error") => eprintln!("{message}"),
+            (LogFormat::Text, _) => println!("{message}"),
+        }
+    }
+}
+
+/// One JSON event: fixed keys first, then `fields` in order.
+fn json_line(
+    time: &str,
+    level: &str,
+    event: &str,
+    message: &str,
+    fields: &[(&str, Value)],
+) -> String {
+    let mut out = String::from("{");
+    let fixed = [
+        ("time", time),
+        ("level", level),
+        ("event", event),
+        ("message", message),
+    ];
+    for (i, (key, value)) in fixed.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "{}:{}", json_string(key), json_string(value));
+    }
+    for (key, value) in fields {
+        let _ = match value {
+            Value::Str(s) => write!(out, ",{}:{}", json_string(key), json_string(s)),
+            Value::Int(n) => write!(out, ",{}:{n}", json_string(key)),
+        };
+    }
+    out.push('}');
+    out
+}
+
+/// `s` as a quoted JSON string.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_line_escapes_and_keeps_numbers() {
+        let line = json_line(
+            "2026-01-02T03:04:05Z",
+            "info",
+            "build",
+            "Built \"docs\"\n",
+            &[("pages", Value::from(3usize)), ("dir", Value::from("a\\b"))],
+        );
+        assert_eq!(
+            line,
+            r#"{"time":"2026-01-02T03:04:05Z","level":"info","event":"build","message":"Built \"docs\"\n","pages":3,"dir":"a\\b"}"#
+        );
+    }
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert_eq!("text".parse::<LogFormat>(), Ok(LogFormat::Text));
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+}
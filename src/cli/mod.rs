@@ -1,13 +1,30 @@
 use std::borrow::Cow;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
-use clap::{Parser, Subcommand};
+use clap::builder::NonEmptyStringValueParser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use log::{Level, Log, LogFormat, Value};
 
+mod fingerprint;
+mod log;
 mod serve;
+mod watch;
 
-use crate::{BuildError, Page, PageContext, ParserRegistry, SiteConfig, build_site};
+use crate::{
+    BuildError, BuildReport, CompressConfig, CompressFormat, ConfigError,
+    DEFAULT_COMPRESS_MIN_SIZE, DEFAULT_DATE_FORMAT, DEFAULT_LANGUAGE, DEFAULT_RELATED_LIMIT,
+    DEFAULT_STYLESHEET, FeedConfig, FeedFormat, HeadTag, HtmlParser, ImageFingerprints, MenuItem,
+    Page, PageContext, ParserRegistry, Redirect, SiteConfig, TrailingSlash, UtcOffset,
+    ValidationRule, build_feeds, build_single_file, build_site_with_report, check_accessibility,
+};
+
+/// Output file name of the `--print-stylesheet` file.
+const PRINT_STYLESHEET: &str = "print.css";
 
 /// A static asset written to the output directory after the site build.
 ///
@@ -36,46 +53,676 @@ impl Asset {
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Print only warnings and errors.
+    #[arg(long, short, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Also print each page as it's written.
+    #[arg(long, short, global = true)]
+    verbose: bool,
+    /// Output format: `text`, or `json` for one machine-readable event per
+    /// line on stdout.
+    #[arg(long, global = true, default_value = "text")]
+    log_format: LogFormat,
+    /// Exit with an error after the run if it printed any warning, e.g. to
+    /// keep CI builds clean. `serve` and `build --watch` run until stopped,
+    /// so it only affects one-off runs.
+    #[arg(long, global = true)]
+    fail_on_warning: bool,
+}
+
+impl Cli {
+    fn log(&self) -> Log {
+        let level = if self.quiet {
+            Level::Quiet
+        } else if self.verbose {
+            Level::Verbose
+        } else {
+            Level::Normal
+        };
+        Log::new(level, self.log_format)
+    }
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Build the site from content/ to _site/
     Build {
-        #[arg(long, default_value = "content")]
-        content_dir: PathBuf,
-        #[arg(long, default_value = "_site")]
-        output_dir: PathBuf,
-        /// URL prefix for serving from a subdirectory, e.g. `/sherwood`.
-        /// Affects generated URLs only, not output paths.
-        #[arg(long, default_value = "")]
-        base_path: String,
-        /// Override a bundled asset with a file from disk. Format: `name=path`,
-        /// where `name` matches an Asset's `dest`. May be repeated.
-        #[arg(long, value_parser = parse_asset_override)]
-        asset: Vec<(PathBuf, PathBuf)>,
+        #[command(flatten)]
+        site: SiteArgs,
+        /// Keep running and rebuild whenever the content (or an `--asset`
+        /// source) changes, without starting a server. Stop with Ctrl-C.
+        #[arg(long)]
+        watch: bool,
+        /// Only regenerate the `--feed` files: pages are parsed for their
+        /// metadata, but no HTML is written and nothing is copied.
+        #[arg(long, conflicts_with = "watch")]
+        feeds_only: bool,
+        /// Serve the output once it's built, exactly as built: no drafts,
+        /// no watching, no live reload. For checking a production build.
+        #[arg(long, conflicts_with_all = ["watch", "feeds_only"])]
+        serve: bool,
+        /// Run the whole build but write nothing: print each file that would
+        /// be written, with its size. Catches errors and previews the
+        /// output's layout.
+        #[arg(long, conflicts_with_all = ["watch", "feeds_only", "serve"])]
+        dry_run: bool,
+        /// Port for `--serve`. `0` lets the OS pick a free one.
+        #[arg(long, default_value_t = 4000, requires = "serve")]
+        port: u16,
+        /// Address for `--serve`; `0.0.0.0` (or `::`) exposes it to your
+        /// network.
+        #[arg(long, default_value = "127.0.0.1", requires = "serve")]
+        host: std::net::IpAddr,
     },
     /// Build then serve, with file watching and browser live reload.
     Serve {
-        #[arg(long, default_value = "content")]
-        content_dir: PathBuf,
-        #[arg(long, default_value = "_site")]
-        output_dir: PathBuf,
+        #[command(flatten)]
+        site: SiteArgs,
+        /// Port to listen on. `0` lets the OS pick a free one; the bound
+        /// address is printed either way.
         #[arg(long, default_value_t = 4000)]
         port: u16,
-        /// URL prefix for serving from a subdirectory, e.g. `/sherwood`. The
-        /// dev server mounts the site under this path to match production.
-        #[arg(long, default_value = "")]
-        base_path: String,
-        /// Override a bundled asset with a file from disk. Format: `name=path`.
-        /// May be repeated. Re-applied on every rebuild.
-        #[arg(long, value_parser = parse_asset_override)]
-        asset: Vec<(PathBuf, PathBuf)>,
+        /// Address to listen on. The default only accepts connections from
+        /// this machine; `0.0.0.0` (or `::`) exposes the preview to your
+        /// network, e.g. to check it on a phone.
+        #[arg(long, default_value = "127.0.0.1")]
+        host: std::net::IpAddr,
         /// Disable file watching and live reload. The dev server becomes a
         /// plain static-file server.
         #[arg(long)]
         no_watch: bool,
     },
+    /// Export every page into one self-contained HTML file, with the
+    /// stylesheet inlined and a table of contents.
+    Export {
+        #[command(flatten)]
+        site: SiteArgs,
+        /// Where to write the document.
+        #[arg(long, default_value = "manual.html")]
+        file: PathBuf,
+    },
+    /// Check page content for common accessibility problems: images without
+    /// alt text, vague link text, and skipped heading levels. Nothing is
+    /// written.
+    Check {
+        #[command(flatten)]
+        site: SiteArgs,
+        /// Exit with an error when any problem is found, e.g. to fail CI.
+        /// Without it, problems are only printed.
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Copy a bundled file, such as the default stylesheet, into the project
+    /// to customize it, then build with `--asset <name>=<copy>`. Without a
+    /// name, list the files that can be ejected.
+    Eject {
+        /// The file to copy, named as in the output, e.g. `style.css`.
+        name: Option<PathBuf>,
+        /// Where to write the copy. Defaults to the file's name in the
+        /// current directory.
+        #[arg(long, requires = "name")]
+        to: Option<PathBuf>,
+        /// Replace the target if it already exists.
+        #[arg(long, requires = "name")]
+        force: bool,
+    },
+}
+
+impl Commands {
+    /// The site options, for every command that builds the site.
+    fn site_mut(&mut self) -> Option<&mut SiteArgs> {
+        match self {
+            Commands::Build { site, .. }
+            | Commands::Serve { site, .. }
+            | Commands::Export { site, .. }
+            | Commands::Check { site, .. } => Some(site),
+            Commands::Eject { .. } => None,
+        }
+    }
+}
+
+/// Site options shared by `build`, `serve`, `export`, and `check`.
+#[derive(Args)]
+struct SiteArgs {
+    /// Read settings from this config file, e.g. `Sherwood.prod.toml`.
+    /// Options given on the command line override its values. The file
+    /// must exist.
+    #[arg(long = "config", value_name = "PATH")]
+    config_file: Option<PathBuf>,
+    /// Also take `${VAR}` values in the `--config` file from this `.env`
+    /// file (`KEY=value` lines). Variables already set in the environment
+    /// win.
+    #[arg(long, value_name = "PATH", requires = "config_file")]
+    env_file: Option<PathBuf>,
+    /// The `--config` file's settings, loaded once at startup.
+    #[arg(skip)]
+    file_config: Option<SiteConfig>,
+    /// Ids of the options given on the command line, which win over the
+    /// file's values.
+    #[arg(skip)]
+    from_command_line: HashSet<String>,
+    #[arg(long, default_value = "content")]
+    content_dir: PathBuf,
+    /// Another content directory merged into the site, mapped onto the
+    /// output root like `--content-dir`. May be repeated; two files writing
+    /// the same output path fail the build.
+    #[arg(long)]
+    extra_content_dir: Vec<PathBuf>,
+    #[arg(long, default_value = "_site")]
+    output_dir: PathBuf,
+    /// URL prefix for serving from a subdirectory, e.g. `/sherwood`.
+    /// Affects generated URLs only, not output paths; `serve` mounts the site
+    /// under this path to match production. `--base-url` is accepted as an
+    /// alias.
+    #[arg(long, visible_alias = "base-url", default_value = "")]
+    base_path: String,
+    /// Whether generated page links end with `/`: `always` (`/about/`),
+    /// `never` (`/about`), or `auto` to keep them as generated.
+    #[arg(long, default_value = "auto")]
+    trailing_slash: TrailingSlash,
+    /// Override a bundled asset with a file from disk. Format: `name=path`,
+    /// where `name` matches an Asset's `dest`. May be repeated. Re-applied on
+    /// every `serve` rebuild.
+    #[arg(long, value_parser = parse_asset_override)]
+    asset: Vec<(PathBuf, PathBuf)>,
+    /// Take each page's last-modified time from git history (slow on large
+    /// sites: one git process per file).
+    #[arg(long)]
+    git_last_modified: bool,
+    /// Absolute origin the site is published at, e.g.
+    /// `https://example.com`. Used for feed links and ids.
+    #[arg(long, default_value = "")]
+    site_url: String,
+    /// Feed formats to write: `rss`, `atom`, `json`, or a comma-separated
+    /// list such as `rss,atom`. May be repeated. Omit for no feeds.
+    #[arg(long, value_delimiter = ',')]
+    feed: Vec<FeedFormat>,
+    /// Embed each page's full body (with absolute links) in feed items
+    /// instead of just its excerpt.
+    #[arg(long)]
+    feed_full_content: bool,
+    /// Most items per feed: only the newest pages are included. Omit for
+    /// every dated page.
+    #[arg(long, value_parser = parse_feed_limit)]
+    feed_limit: Option<usize>,
+    /// Output file name for the stylesheet, relative to the output
+    /// directory (e.g. `styles.css` or `css/main.css`). The bundled
+    /// `style.css` asset is written there and templates link to it.
+    #[arg(long, default_value = DEFAULT_STYLESHEET)]
+    stylesheet: String,
+    /// Add a content hash to the stylesheet's file name
+    /// (`style.<hash>.css`) for cache busting. Fingerprinted copies from
+    /// earlier builds are removed.
+    #[arg(long)]
+    fingerprint_stylesheet: bool,
+    /// A stylesheet for printing, read from this file, written as
+    /// `print.css` and linked with `media="print"`. Without it no print
+    /// stylesheet is linked.
+    #[arg(long)]
+    print_stylesheet: Option<PathBuf>,
+    /// Add a site menu entry, `name=url` (e.g. `Blog=/blog/`). May be
+    /// repeated; entries appear in the order given and replace the
+    /// automatic nav in the default template.
+    #[arg(long)]
+    menu: Vec<MenuItem>,
+    /// How page dates are displayed: a strftime-style pattern such as
+    /// `%d %b %Y`, or `relative` for "3 days ago".
+    #[arg(long, default_value = DEFAULT_DATE_FORMAT)]
+    date_format: String,
+    /// Fail when a page's `date` isn't a valid date (`YYYY-MM-DD`,
+    /// optionally with a time and UTC offset), instead of warning and
+    /// showing it as written.
+    #[arg(long)]
+    strict_dates: bool,
+    /// UTC offset for dates that don't give one, e.g. `+02:00` — used for
+    /// feed timestamps.
+    #[arg(long, default_value = "Z")]
+    timezone: UtcOffset,
+    /// The site's language, emitted as `<html lang>` (e.g. `de`). A page's
+    /// `lang` frontmatter field or language directory overrides it.
+    #[arg(long, default_value = DEFAULT_LANGUAGE)]
+    language: String,
+    /// Language codes whose top-level content directories hold language
+    /// variants, e.g. `en,fr`. Pages sharing a `translation_key` are linked
+    /// with `hreflang` alternates.
+    #[arg(long, value_delimiter = ',')]
+    languages: Vec<String>,
+    /// Add a `<link>`, `<meta>` or `<script src>` tag to every page's `<head>`, e.g.
+    /// `--head 'link rel=icon href=/favicon.ico'`. Quote values containing
+    /// spaces. May be repeated; malformed tags are skipped with a warning.
+    #[arg(long)]
+    head: Vec<String>,
+    /// Shallowest heading level in `toc: true` tables of contents (1–6).
+    #[arg(long, default_value_t = 2)]
+    toc_min_level: u8,
+    /// Deepest heading level in `toc: true` tables of contents (1–6).
+    #[arg(long, default_value_t = 3)]
+    toc_max_level: u8,
+    /// Write precompressed siblings of output files for static hosts:
+    /// `gzip` (`.gz`), `brotli` (`.br`), or `gzip,brotli`. Applies to
+    /// `build` only; `serve` never compresses.
+    #[arg(long, value_delimiter = ',')]
+    compress: Vec<CompressFormat>,
+    /// Smallest file, in bytes, worth compressing.
+    #[arg(long, default_value_t = DEFAULT_COMPRESS_MIN_SIZE)]
+    compress_min_size: u64,
+    /// File extensions to compress, e.g. `html,css`. Defaults to
+    /// `html,css,js,json,xml,svg`.
+    #[arg(long, value_delimiter = ',')]
+    compress_extensions: Vec<String>,
+    /// Add `loading="lazy"` and `decoding="async"` to content images, and
+    /// `width` / `height` for local PNG, GIF, JPEG, and WebP files.
+    #[arg(long)]
+    lazy_images: bool,
+    /// Point content images at content-hashed copies (`cat.<hash>.png`)
+    /// for immutable caching: `relative` for page-relative sources only,
+    /// `local` for root-absolute ones too, or `off`.
+    #[arg(long, default_value = "off")]
+    fingerprint_images: ImageFingerprints,
+    /// Wrap each content table in `<div class="table-wrapper">` so wide
+    /// tables scroll sideways on narrow screens instead of overflowing.
+    #[arg(long)]
+    wrap_tables: bool,
+    /// Cut page excerpts longer than this many characters at a word
+    /// boundary, ending them with `…`. `0` keeps them whole.
+    #[arg(long, default_value_t = 0)]
+    excerpt_max_chars: usize,
+    /// Marker that ends a page's excerpt in markdown and HTML content, e.g.
+    /// `<!--more-->` (default `<!-- more -->`).
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    excerpt_separator: Option<String>,
+    /// Render `.html` content files that start with frontmatter as pages in
+    /// the site template. Other `.html` files are still copied verbatim.
+    #[arg(long)]
+    wrap_html: bool,
+    /// Include pages under `_drafts` directories. `serve` always does.
+    #[arg(long)]
+    drafts: bool,
+    /// Include pages whose frontmatter sets `published: false`, which are
+    /// otherwise left out of `build` and `serve` alike.
+    #[arg(long)]
+    include_unpublished: bool,
+    /// Most pages in each page's "related" list (pages sharing `tags`);
+    /// `0` turns it off.
+    #[arg(long, default_value_t = DEFAULT_RELATED_LIMIT)]
+    related_limit: usize,
+    /// Most threads loading and parsing content at once; `0` means one per
+    /// CPU. Lower it on slow disks or network filesystems, or in small CI
+    /// containers.
+    #[arg(long, short, default_value_t = 0)]
+    jobs: usize,
+    /// Redirect an old path: `--redirect '/old/ /new/'`, with an optional
+    /// status (`301` default, `302`, `307`, `308`) as a third word. May be
+    /// repeated. `build` writes a stub page at the old path; `serve`
+    /// answers it with an HTTP redirect.
+    #[arg(long)]
+    redirect: Vec<Redirect>,
+    /// Require frontmatter fields of pages matching a glob, e.g.
+    /// `--require 'blog/**=date,author'`. May be repeated; a page missing a
+    /// field fails the run with an error naming the file and field.
+    #[arg(long)]
+    require: Vec<ValidationRule>,
+}
+
+impl SiteArgs {
+    /// Load the `--config` file, if any, and note which options `matches`
+    /// (this subcommand's) got from the command line.
+    fn read_config_file(&mut self, matches: &ArgMatches) -> Result<(), CliError> {
+        let Some(path) = &self.config_file else {
+            return Ok(());
+        };
+        self.file_config = Some(match &self.env_file {
+            Some(env_file) => SiteConfig::load_with_env_file(path, env_file)?,
+            None => SiteConfig::load(path)?,
+        });
+        self.from_command_line = matches
+            .ids()
+            .filter(|id| matches.value_source(id.as_str()) == Some(ValueSource::CommandLine))
+            .map(|id| id.to_string())
+            .collect();
+        Ok(())
+    }
+
+    /// Whether option `id` applies: always without a config file, and only
+    /// when given on the command line with one.
+    fn given(&self, id: &str) -> bool {
+        self.file_config.is_none() || self.from_command_line.contains(id)
+    }
+
+    fn config(&self) -> SiteConfig {
+        let mut config = self.file_config.clone().unwrap_or_default();
+        if self.given("content_dir") {
+            config = config.with_content_dir(&self.content_dir);
+        }
+        if self.given("extra_content_dir") {
+            config = config.with_extra_content_dirs(&self.extra_content_dir);
+        }
+        if self.given("output_dir") {
+            config = config.with_output_dir(&self.output_dir);
+        }
+        if self.given("base_path") {
+            config = config.with_base_path(&self.base_path);
+        }
+        if self.given("trailing_slash") {
+            config = config.with_trailing_slash(self.trailing_slash);
+        }
+        if self.given("git_last_modified") {
+            config = config.with_git_last_modified(self.git_last_modified);
+        }
+        if self.given("site_url") {
+            config = config.with_site_url(&self.site_url);
+        }
+        let feed = self.feed_config(config.feed.clone());
+        config = config.with_feed(feed);
+        if self.given("stylesheet") {
+            config = config.with_stylesheet(&self.stylesheet);
+        }
+        if self.given("menu") {
+            config = config.with_menu(self.menu.iter().cloned());
+        }
+        if self.given("date_format") {
+            config = config.with_date_format(&self.date_format);
+        }
+        if self.given("strict_dates") {
+            config = config.with_strict_dates(self.strict_dates);
+        }
+        if self.given("timezone") {
+            config = config.with_timezone(self.timezone);
+        }
+        if self.given("language") {
+            config = config.with_language(&self.language);
+        }
+        if self.given("languages") {
+            config = config.with_languages(self.languages.iter().cloned());
+        }
+        if self.given("head") {
+            config = config.with_head(head_tags(&self.head));
+        }
+        let (min, max) = self.toc_levels();
+        config = config.with_toc_levels(min, max);
+        let compress = self.compress_config(config.compress.clone());
+        config = config.with_compress(compress);
+        if self.given("lazy_images") {
+            config = config.with_lazy_images(self.lazy_images);
+        }
+        if self.given("fingerprint_images") {
+            config = config.with_fingerprint_images(self.fingerprint_images);
+        }
+        if self.given("wrap_tables") {
+            config = config.with_wrap_tables(self.wrap_tables);
+        }
+        if self.given("excerpt_max_chars") {
+            config = config.with_excerpt_max_chars(self.excerpt_max_chars);
+        }
+        if let Some(separator) = &self.excerpt_separator {
+            config = config.with_excerpt_separator(separator);
+        }
+        if self.given("drafts") {
+            config = config.with_drafts(self.drafts);
+        }
+        if self.given("include_unpublished") {
+            config = config.with_unpublished(self.include_unpublished);
+        }
+        if self.given("related_limit") {
+            config = config.with_related_limit(self.related_limit);
+        }
+        if self.given("jobs") {
+            config = config.with_max_concurrency(self.jobs);
+        }
+        if self.given("redirect") {
+            config = config.with_redirects(self.redirect.iter().cloned());
+        }
+        if self.given("require") {
+            config = config.with_validation(self.require.iter().cloned());
+        }
+        match self.print_stylesheet {
+            Some(_) => config.with_print_stylesheet(PRINT_STYLESHEET),
+            None => config,
+        }
+    }
+
+    /// The TOC levels asked for: the command line's, falling back to the
+    /// config file's for a level not given.
+    fn toc_levels(&self) -> (u8, u8) {
+        let default = SiteConfig::default();
+        let base = self.file_config.as_ref().unwrap_or(&default);
+        let min = if self.given("toc_min_level") {
+            self.toc_min_level
+        } else {
+            base.toc_min_level
+        };
+        let max = if self.given("toc_max_level") {
+            self.toc_max_level
+        } else {
+            base.toc_max_level
+        };
+        (min, max)
+    }
+
+    /// `--asset` overrides, plus the print stylesheet as one more asset.
+    fn asset_overrides(&self) -> Vec<(PathBuf, PathBuf)> {
+        let mut overrides = self.asset.clone();
+        if let Some(path) = &self.print_stylesheet {
+            overrides.push((PathBuf::from(PRINT_STYLESHEET), path.clone()));
+        }
+        overrides
+    }
+
+    fn feed_config(&self, mut config: FeedConfig) -> FeedConfig {
+        if self.given("feed") {
+            config = config.with_formats(self.feed.iter().copied());
+        }
+        if self.given("feed_full_content") {
+            config = config.with_full_content(self.feed_full_content);
+        }
+        match self.feed_limit {
+            Some(limit) => config.with_limit(limit),
+            None => config,
+        }
+    }
+
+    fn compress_config(&self, mut config: CompressConfig) -> CompressConfig {
+        if self.given("compress") {
+            config = config.with_formats(self.compress.iter().copied());
+        }
+        if self.given("compress_min_size") {
+            config = config.with_min_size(self.compress_min_size);
+        }
+        if self.compress_extensions.is_empty() {
+            config
+        } else {
+            config.with_extensions(&self.compress_extensions)
+        }
+    }
+
+    /// Warn about option values the config builders silently adjust or
+    /// drop.
+    fn warn_adjusted(&self, config: &SiteConfig, log: &Log) {
+        let (min, max) = self.toc_levels();
+        if (config.toc_min_level, config.toc_max_level) != (min, max) {
+            log.warn(
+                "option",
+                format!(
+                    "TOC levels {min}–{max} are out of range or inverted; using {}–{}",
+                    config.toc_min_level, config.toc_max_level
+                ),
+                &[],
+            );
+        }
+        for value in &self.head {
+            if let Err(e) = parse_head(value) {
+                log.warn("option", format!("skipping --head `{value}`: {e}"), &[]);
+            }
+        }
+        // Atom ids and links must be absolute IRIs; without a site URL they
+        // fall back to root-relative paths that feed readers can't resolve.
+        if config.feed.formats.contains(&FeedFormat::Atom) && config.site_url.is_empty() {
+            log.warn(
+                "option",
+                "the Atom feed needs --site-url (or `site_url`); its ids and links will be relative",
+                &[],
+            );
+        }
+    }
+}
+
+/// Log a page's non-fatal load warnings.
+fn report_warnings(page: &Page, log: &Log) {
+    for warning in &page.warnings {
+        log.warn(
+            "page_warning",
+            format!("{}: {warning}", page.source_path.display()),
+            &[("source", page.source_path.display().to_string().into())],
+        );
+    }
+}
+
+/// What `build` prints when it finishes: the library's [`BuildReport`] plus
+/// the assets the CLI writes itself.
+struct BuildSummary {
+    /// Nothing was written; the counts are what a build would write.
+    dry_run: bool,
+    report: BuildReport,
+    /// Pages, copied files, feeds and CLI assets.
+    bytes_written: u64,
+    stylesheet_bytes: u64,
+    assets_time: Duration,
+    elapsed: Duration,
+}
+
+impl BuildSummary {
+    fn log(&self, log: &Log) {
+        let ms = |d: Duration| d.as_millis();
+        let report = &self.report;
+        let (event, done, written) = if self.dry_run {
+            ("dry_run", "Dry run complete (nothing written)", "to write")
+        } else {
+            ("build", "Build complete", "written")
+        };
+        log.info(
+            event,
+            format!(
+                "{done}: {} pages ({} list pages), {} files copied, {} {written} \
+                 (stylesheet {}) in {}ms — parse {}ms, render {}ms, assets {}ms.",
+                report.pages,
+                report.section_pages,
+                report.assets,
+                human_bytes(self.bytes_written),
+                human_bytes(self.stylesheet_bytes),
+                ms(self.elapsed),
+                ms(report.parse_time),
+                ms(report.render_time),
+                ms(self.assets_time),
+            ),
+            &[
+                ("pages", report.pages.into()),
+                ("section_pages", report.section_pages.into()),
+                ("assets", report.assets.into()),
+                ("bytes", u128::from(self.bytes_written).into()),
+                ("stylesheet_bytes", u128::from(self.stylesheet_bytes).into()),
+                ("ms", ms(self.elapsed).into()),
+                ("parse_ms", ms(report.parse_time).into()),
+                ("render_ms", ms(report.render_time).into()),
+                ("assets_ms", ms(self.assets_time).into()),
+            ],
+        );
+    }
+}
+
+/// `512 B`, `4.1 KB`, `1.2 MB` (powers of 1024).
+fn human_bytes(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Precompress the output after a `build`, when `--compress` asked for it.
+/// `serve` skips this: the dev server doesn't serve the siblings, and
+/// rebuilds should stay fast.
+fn compress(config: &SiteConfig, log: &Log) -> Result<(), CliError> {
+    #[cfg(feature = "compress")]
+    {
+        let written = crate::compress_output(config)?;
+        if written > 0 {
+            log.info(
+                "compressed",
+                format!("Compressed {written} files."),
+                &[("files", written.into())],
+            );
+        }
+    }
+    #[cfg(not(feature = "compress"))]
+    if !config.compress.formats.is_empty() {
+        log.warn(
+            "option",
+            "ignoring --compress: built without the `compress` feature",
+            &[],
+        );
+    }
+    Ok(())
+}
+
+/// Parse `--head` values, dropping malformed ones rather than failing the
+/// build over a favicon ([`SiteArgs::warn_adjusted`] reports them).
+fn head_tags(raw: &[String]) -> Vec<HeadTag> {
+    raw.iter()
+        .filter_map(|value| parse_head(value).ok())
+        .collect()
+}
+
+fn parse_head(value: &str) -> Result<HeadTag, String> {
+    value
+        .parse::<HeadTag>()
+        .and_then(|tag| tag.validate().map(|()| tag))
+}
+
+/// Resolve the stylesheet name for this build: with `fingerprint`, hash the
+/// stylesheet asset's bytes into [`SiteConfig::stylesheet`]. Runs before
+/// `build_site` so every page links the fingerprinted file.
+fn with_fingerprint(mut config: SiteConfig, assets: &[Asset], fingerprint: bool) -> SiteConfig {
+    if fingerprint
+        && let Some(style) = assets
+            .iter()
+            .find(|a| a.dest == Path::new(DEFAULT_STYLESHEET))
+    {
+        config.stylesheet = fingerprint::fingerprinted_name(&config.stylesheet, &style.bytes);
+    }
+    config
+}
+
+/// Parse the command line like clap's `parse_from` (printing and exiting on
+/// invalid arguments), then load the `--config` file it names.
+fn parse_cli<I, T>(args: I) -> Result<Cli, CliError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let (mut cli, matches) = parse_args(args);
+    read_config_file(&mut cli, &matches)?;
+    Ok(cli)
+}
+
+/// The command line alone, before any `--config` file is read, so the log
+/// settings are known even when loading the file fails.
+fn parse_args<I, T>(args: I) -> (Cli, ArgMatches)
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let matches = Cli::command().get_matches_from(args);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    (cli, matches)
+}
+
+/// Load the `--config` file named on the command line into `cli`'s site
+/// options.
+fn read_config_file(cli: &mut Cli, matches: &ArgMatches) -> Result<(), CliError> {
+    if let (Some((_, sub)), Some(site)) = (matches.subcommand(), cli.command.site_mut()) {
+        site.read_config_file(sub)?;
+    }
+    Ok(())
 }
 
 fn parse_asset_override(raw: &str) -> Result<(PathBuf, PathBuf), String> {
@@ -88,17 +735,29 @@ fn parse_asset_override(raw: &str) -> Result<(PathBuf, PathBuf), String> {
     Ok((PathBuf::from(name), PathBuf::from(path)))
 }
 
+fn parse_feed_limit(raw: &str) -> Result<usize, String> {
+    match raw.parse::<usize>() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => Err(format!("expected a positive number of items, got `{raw}`")),
+    }
+}
+
 /// Run the standard Sherwood CLI (build + serve subcommands). Exits the
-/// process with code 0 on success, 1 on failure. Use [`try_run_cli`] if you
-/// want to handle errors yourself.
+/// process with code 0 on success, 1 on failure, reporting the error in the
+/// chosen `--log-format`. Use [`try_run_cli`] if you want to handle errors
+/// yourself.
 pub fn run_cli<F>(registry: ParserRegistry, renderer: F, assets: Vec<Asset>) -> ExitCode
 where
     F: FnMut(&Page, &PageContext) -> Result<String, BuildError> + Send + 'static,
 {
-    match try_run_cli(registry, renderer, assets) {
+    let (mut cli, matches) = parse_args(std::env::args_os());
+    let log = cli.log();
+    let outcome = read_config_file(&mut cli, &matches)
+        .and_then(|()| run_parsed(cli, registry, renderer, assets));
+    match outcome {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            eprintln!("{e}");
+            log.error("failed", e);
             ExitCode::FAILURE
         }
     }
@@ -133,43 +792,181 @@ where
     T: Into<std::ffi::OsString> + Clone,
     F: FnMut(&Page, &PageContext) -> Result<String, BuildError> + Send + 'static,
 {
-    let cli = Cli::parse_from(args);
-    match cli.command {
+    run_parsed(parse_cli(args)?, registry, renderer, assets)
+}
+
+/// Run an already-parsed command line, its `--config` file loaded.
+fn run_parsed<F>(
+    mut cli: Cli,
+    mut registry: ParserRegistry,
+    renderer: F,
+    assets: Vec<Asset>,
+) -> Result<(), CliError>
+where
+    F: FnMut(&Page, &PageContext) -> Result<String, BuildError> + Send + 'static,
+{
+    if cli.command.site_mut().is_some_and(|site| site.wrap_html) {
+        registry.register(Arc::new(HtmlParser::new()));
+    }
+    let log = cli.log();
+    let fail_on_warning = cli.fail_on_warning;
+    let outcome: Result<(), CliError> = match cli.command {
         Commands::Build {
-            content_dir,
-            output_dir,
-            base_path,
-            asset,
+            site,
+            feeds_only: true,
+            ..
         } => {
-            let assets = apply_overrides(assets, asset)?;
-            let config = SiteConfig::new()
-                .with_content_dir(content_dir)
-                .with_output_dir(output_dir)
-                .with_base_path(base_path);
-            build_site(&config, &registry, renderer, |page| {
-                println!(
-                    "{} -> {}",
-                    page.source_path.display(),
-                    page.output_path.display()
+            let config = site.config();
+            site.warn_adjusted(&config, &log);
+            let started = Instant::now();
+            let written = build_feeds(&config, &registry)?;
+            if written.is_empty() {
+                log.warn(
+                    "option",
+                    "--feeds-only without --feed formats: nothing written",
+                    &[],
                 );
-            })?;
-            write_assets(&assets, &config)?;
-            println!("Build complete.");
+            } else {
+                let names: Vec<String> = written
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                let ms = started.elapsed().as_millis();
+                log.info(
+                    "build",
+                    format!("Feeds written: {} in {ms}ms", names.join(", ")),
+                    &[("files", names.len().into()), ("ms", ms.into())],
+                );
+            }
+            Ok(())
+        }
+        Commands::Build {
+            site,
+            watch,
+            serve,
+            dry_run,
+            port,
+            host,
+            ..
+        } => {
+            let overrides = site.asset_overrides();
+            report_asset_sources(&assets, &overrides, &log);
+            let mut renderer = renderer;
+            let mut build = || -> Result<BuildSummary, CliError> {
+                let started = Instant::now();
+                // Overrides are re-read on every rebuild, as in `serve`.
+                let assets = apply_overrides(assets.clone(), overrides.clone())?;
+                let config = with_fingerprint(
+                    site.config().with_dry_run(dry_run),
+                    &assets,
+                    site.fingerprint_stylesheet,
+                );
+                let report = build_site_with_report(&config, &registry, &mut renderer, |page| {
+                    log.verbose(
+                        "page",
+                        format!(
+                            "{} -> {}",
+                            page.source_path.display(),
+                            page.output_path.display()
+                        ),
+                        &[
+                            ("source", page.source_path.display().to_string().into()),
+                            ("output", page.output_path.display().to_string().into()),
+                            ("url", page.url.as_str().into()),
+                        ],
+                    );
+                    report_warnings(page, &log);
+                })?;
+                let assets_started = Instant::now();
+                let asset_bytes = if dry_run {
+                    let outputs = asset_outputs(&assets, &config);
+                    for (path, bytes) in report.outputs.iter().chain(&outputs) {
+                        log.info(
+                            "would_write",
+                            format!("{} ({})", path.display(), human_bytes(*bytes)),
+                            &[
+                                ("output", path.display().to_string().into()),
+                                ("bytes", u128::from(*bytes).into()),
+                            ],
+                        );
+                    }
+                    outputs.iter().map(|(_, bytes)| bytes).sum()
+                } else {
+                    let written = write_assets(&assets, &config)?;
+                    compress(&config, &log)?;
+                    written
+                };
+                let assets_time = assets_started.elapsed();
+                Ok(BuildSummary {
+                    dry_run,
+                    stylesheet_bytes: assets
+                        .iter()
+                        .find(|a| a.dest == Path::new(DEFAULT_STYLESHEET))
+                        .map_or(0, |a| a.bytes.len() as u64),
+                    bytes_written: report.bytes_written + asset_bytes,
+                    report,
+                    assets_time,
+                    elapsed: started.elapsed(),
+                })
+            };
+            let config = site.config();
+            site.warn_adjusted(&config, &log);
+            let summary = build()?;
+            summary.log(&log);
+            if serve {
+                // Already built: the server's "rebuild" has nothing to do.
+                let redirects = Arc::new(RwLock::new(summary.report.redirect_rules));
+                let runtime = tokio::runtime::Runtime::new().map_err(CliError::Runtime)?;
+                runtime.block_on(serve::serve_with_watch(
+                    &config,
+                    std::net::SocketAddr::new(host, port),
+                    || Ok(()),
+                    false,
+                    Vec::new(),
+                    redirects,
+                    log.clone(),
+                ))?;
+            }
+            if watch {
+                let content_dirs: Vec<PathBuf> =
+                    config.content_roots().map(Path::to_path_buf).collect();
+                let watched = content_dirs
+                    .iter()
+                    .map(|dir| dir.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                log.info(
+                    "watch",
+                    format!("Watching {watched} for changes (Ctrl-C to stop)"),
+                    &[("dir", watched.clone().into())],
+                );
+                let watch_paths: Vec<PathBuf> =
+                    overrides.iter().map(|(_, path)| path.clone()).collect();
+                watch::watch_loop(
+                    content_dirs,
+                    &watch_paths,
+                    log.clone(),
+                    || {
+                        build()
+                            .map(drop)
+                            .map_err(|e| BuildError::Render(e.to_string()))
+                    },
+                    |_| {},
+                );
+            }
             Ok(())
         }
         Commands::Serve {
-            content_dir,
-            output_dir,
+            site,
             port,
-            base_path,
-            asset,
+            host,
             no_watch,
         } => {
-            let config = SiteConfig::new()
-                .with_content_dir(content_dir.clone())
-                .with_output_dir(output_dir.clone())
-                .with_base_path(base_path);
-            let base_path = config.base_path.clone();
+            let config = site.config().with_drafts(true);
+            site.warn_adjusted(&config, &log);
+            let asset = site.asset_overrides();
+            report_asset_sources(&assets, &asset, &log);
+            let fingerprint = site.fingerprint_stylesheet;
             // Watch the `--asset` override sources too, so editing e.g. a
             // custom stylesheet triggers a rebuild like content edits do.
             let watch_paths: Vec<PathBuf> = asset.iter().map(|(_, path)| path.clone()).collect();
@@ -180,45 +977,198 @@ where
             let config_for_rebuild = config.clone();
             let renderer_for_rebuild = renderer.clone();
             let registry_for_rebuild = registry.clone();
+            // Each build's redirect rules (config plus page aliases), answered
+            // by the server before it looks for a file.
+            let redirects = serve::SharedRedirects::default();
+            let redirects_for_rebuild = redirects.clone();
+            let log_for_rebuild = log.clone();
 
             let rebuild = move || -> Result<(), BuildError> {
                 let mut guard = renderer_for_rebuild
                     .lock()
                     .map_err(|_| BuildError::Render("renderer mutex poisoned".to_string()))?;
                 let renderer_ref: &mut F = &mut guard;
-                build_site(
-                    &config_for_rebuild,
-                    &registry_for_rebuild,
-                    |p, c| renderer_ref(p, c),
-                    |_| {},
-                )?;
                 // Overrides are re-read from disk on every rebuild so edits to
                 // `--asset` source files land, not the bytes from startup.
+                // They're applied before the build so a fingerprinted
+                // stylesheet name reflects the current bytes.
                 let assets = apply_overrides(assets.clone(), asset.clone())
                     .map_err(|e| BuildError::Render(e.to_string()))?;
-                write_assets(&assets, &config_for_rebuild)
-                    .map_err(|e| BuildError::Render(e.to_string()))?;
+                let config = with_fingerprint(config_for_rebuild.clone(), &assets, fingerprint);
+                let report = build_site_with_report(
+                    &config,
+                    &registry_for_rebuild,
+                    |p, c| renderer_ref(p, c),
+                    |p| report_warnings(p, &log_for_rebuild),
+                )?;
+                if let Ok(mut rules) = redirects_for_rebuild.write() {
+                    *rules = report.redirect_rules;
+                }
+                write_assets(&assets, &config).map_err(|e| BuildError::Render(e.to_string()))?;
                 Ok(())
             };
 
             let runtime = tokio::runtime::Runtime::new().map_err(CliError::Runtime)?;
             runtime.block_on(serve::serve_with_watch(
-                content_dir,
-                output_dir,
-                base_path,
-                port,
+                &config,
+                std::net::SocketAddr::new(host, port),
                 rebuild,
                 !no_watch,
                 watch_paths,
+                redirects,
+                log.clone(),
             ))?;
             Ok(())
         }
+        Commands::Export { site, file } => {
+            let assets = apply_overrides(assets, site.asset_overrides())?;
+            let config = site.config();
+            site.warn_adjusted(&config, &log);
+            let stylesheet = assets
+                .iter()
+                .find(|a| a.dest == Path::new(DEFAULT_STYLESHEET))
+                .map(|a| String::from_utf8_lossy(&a.bytes).into_owned())
+                .unwrap_or_default();
+            let html = build_single_file(&config, &registry, &stylesheet)?;
+            if let Some(parent) = file.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| CliError::AssetWrite {
+                    path: file.clone(),
+                    source: e,
+                })?;
+            }
+            std::fs::write(&file, html).map_err(|e| CliError::AssetWrite {
+                path: file.clone(),
+                source: e,
+            })?;
+            log.info(
+                "export",
+                format!("Exported {}.", file.display()),
+                &[("file", file.display().to_string().into())],
+            );
+            Ok(())
+        }
+        Commands::Check { site, strict } => {
+            let config = site.config();
+            site.warn_adjusted(&config, &log);
+            let issues = check_accessibility(&config, &registry)?;
+            for issue in &issues {
+                let mut fields: Vec<(&str, Value)> =
+                    vec![("source", issue.path.display().to_string().into())];
+                if let Some(line) = issue.line {
+                    fields.push(("line", line.into()));
+                }
+                log.warn("a11y", issue, &fields);
+            }
+            match issues.len() {
+                0 => log.info(
+                    "check",
+                    "No accessibility problems found.",
+                    &[("issues", 0usize.into())],
+                ),
+                n if strict => return Err(CliError::Check(n)),
+                n => log.info(
+                    "check",
+                    format!("Found {n} accessibility problem(s)."),
+                    &[("issues", n.into())],
+                ),
+            }
+            Ok(())
+        }
+        Commands::Eject { name, to, force } => eject(&assets, name, to, force, &log),
+    };
+    outcome?;
+    match log.warnings() {
+        n if fail_on_warning && n > 0 => Err(CliError::Warnings(n)),
+        _ => Ok(()),
+    }
+}
+
+/// Copy the bundled asset `name` to `to` (default: its name, relative to the
+/// working directory), or list the bundled assets when no name is given.
+fn eject(
+    assets: &[Asset],
+    name: Option<PathBuf>,
+    to: Option<PathBuf>,
+    force: bool,
+    log: &Log,
+) -> Result<(), CliError> {
+    let Some(name) = name else {
+        for a in assets {
+            log.info("eject", a.dest.display(), &[]);
+        }
+        return Ok(());
+    };
+    let Some(asset) = assets.iter().find(|a| a.dest == name) else {
+        let available: Vec<String> = assets
+            .iter()
+            .map(|a| a.dest.display().to_string())
+            .collect();
+        return Err(CliError::UnknownAsset {
+            name,
+            available: available.join(", "),
+        });
+    };
+    let target = to.unwrap_or_else(|| asset.dest.clone());
+    if target.exists() && !force {
+        return Err(CliError::EjectExists(target));
+    }
+    if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| CliError::AssetWrite {
+            path: target.clone(),
+            source: e,
+        })?;
+    }
+    std::fs::write(&target, &asset.bytes).map_err(|e| CliError::AssetWrite {
+        path: target.clone(),
+        source: e,
+    })?;
+    log.info(
+        "eject",
+        format!(
+            "Ejected {} to {}; build with --asset {}={} to use it.",
+            name.display(),
+            target.display(),
+            name.display(),
+            target.display()
+        ),
+        &[("file", target.display().to_string().into())],
+    );
+    Ok(())
+}
+
+/// Where each of `assets` is written under the output directory (see
+/// [`write_assets`]), with its size.
+fn asset_outputs(assets: &[Asset], config: &SiteConfig) -> Vec<(PathBuf, u64)> {
+    assets
+        .iter()
+        .map(|a| (asset_dest(a, config), a.bytes.len() as u64))
+        .collect()
+}
+
+fn asset_dest(asset: &Asset, config: &SiteConfig) -> PathBuf {
+    if asset.dest == Path::new(DEFAULT_STYLESHEET) {
+        config.output_dir.join(&config.stylesheet)
+    } else {
+        config.output_dir.join(&asset.dest)
     }
 }
 
-fn write_assets(assets: &[Asset], config: &SiteConfig) -> Result<(), CliError> {
+/// Write `assets` under the output directory, returning the bytes written.
+/// The stylesheet asset (dest `style.css`) lands at
+/// [`SiteConfig::stylesheet`] so it matches the href templates link to, and
+/// fingerprinted copies from earlier builds are removed.
+fn write_assets(assets: &[Asset], config: &SiteConfig) -> Result<u64, CliError> {
+    let mut written = 0;
     for a in assets {
-        let dest = config.output_dir.join(&a.dest);
+        let dest = asset_dest(a, config);
+        if a.dest == Path::new(DEFAULT_STYLESHEET) {
+            fingerprint::remove_stale(&config.output_dir, &config.stylesheet).map_err(|e| {
+                CliError::AssetWrite {
+                    path: dest.clone(),
+                    source: e,
+                }
+            })?;
+        }
         if let Some(parent) = dest.parent() {
             std::fs::create_dir_all(parent).map_err(|e| CliError::AssetWrite {
                 path: dest.clone(),
@@ -229,8 +1179,52 @@ fn write_assets(assets: &[Asset], config: &SiteConfig) -> Result<(), CliError> {
             path: dest,
             source: e,
         })?;
+        written += a.bytes.len() as u64;
     }
-    Ok(())
+    Ok(written)
+}
+
+/// Log, at verbose level, where each asset comes from: the bundled default
+/// or an `--asset` file. The first thing to check when a custom stylesheet
+/// doesn't seem to apply.
+fn report_asset_sources(assets: &[Asset], overrides: &[(PathBuf, PathBuf)], log: &Log) {
+    for line in asset_sources(assets, overrides) {
+        log.verbose("asset", line, &[]);
+    }
+}
+
+/// One line per asset for [`report_asset_sources`], in the order
+/// [`apply_overrides`] resolves them: a later `--asset` for the same name
+/// wins.
+fn asset_sources(assets: &[Asset], overrides: &[(PathBuf, PathBuf)]) -> Vec<String> {
+    let from_file = |name: &Path| {
+        overrides
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, path)| path)
+    };
+    let mut lines: Vec<String> = assets
+        .iter()
+        .map(|a| match from_file(&a.dest) {
+            Some(path) => format!("{}: from {}", a.dest.display(), path.display()),
+            None => format!(
+                "{}: bundled default (no --asset {}=<file> given)",
+                a.dest.display(),
+                a.dest.display()
+            ),
+        })
+        .collect();
+    let mut added: Vec<&Path> = Vec::new();
+    for (name, _) in overrides {
+        if !assets.iter().any(|a| a.dest == *name) && !added.contains(&name.as_path()) {
+            added.push(name);
+            if let Some(path) = from_file(name) {
+                lines.push(format!("{}: added from {}", name.display(), path.display()));
+            }
+        }
+    }
+    lines
 }
 
 fn apply_overrides(
@@ -271,6 +1265,16 @@ pub enum CliError {
         path: PathBuf,
         source: std::io::Error,
     },
+    #[error("Nothing to eject named {}; bundled files: {available}", name.display())]
+    UnknownAsset { name: PathBuf, available: String },
+    #[error("Not ejecting over {}: it already exists (pass --force to replace it)", .0.display())]
+    EjectExists(PathBuf),
+    #[error("Check failed: {0} accessibility problem(s) found")]
+    Check(usize),
+    #[error("Failed: {0} warning(s) emitted with --fail-on-warning")]
+    Warnings(usize),
+    #[error("Failed to load config: {0}")]
+    Config(#[from] ConfigError),
 }
 
 #[cfg(test)]
@@ -319,6 +1323,27 @@ mod tests {
         assert_eq!(out[0].dest, PathBuf::from("extra.txt"));
     }
 
+    #[test]
+    fn asset_sources_name_bundled_and_overridden_assets() {
+        let assets = vec![
+            Asset::new("style.css", &b"body{}"[..]),
+            Asset::new("print.css", &b""[..]),
+        ];
+        let overrides = vec![
+            (PathBuf::from("style.css"), PathBuf::from("old.css")),
+            (PathBuf::from("style.css"), PathBuf::from("my.css")),
+            (PathBuf::from("robots.txt"), PathBuf::from("robots.txt")),
+        ];
+        assert_eq!(
+            asset_sources(&assets, &overrides),
+            [
+                "style.css: from my.css",
+                "print.css: bundled default (no --asset print.css=<file> given)",
+                "robots.txt: added from robots.txt",
+            ]
+        );
+    }
+
     #[test]
     fn apply_overrides_missing_file_errors() {
         let assets = vec![];
@@ -360,6 +1385,786 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eject_copies_a_bundled_asset_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("theme/my.css");
+        let eject = |extra: &[&str]| {
+            let args = [
+                "sherwood",
+                "eject",
+                "style.css",
+                "--to",
+                target.to_str().unwrap(),
+            ];
+            try_run_cli_from(
+                args.iter().chain(extra).copied(),
+                ParserRegistry::default(),
+                |_, _| Ok(String::new()),
+                vec![Asset::new("style.css", &b"body{}"[..])],
+            )
+        };
+        eject(&[]).unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "body{}");
+
+        std::fs::write(&target, "edited").unwrap();
+        assert!(matches!(eject(&[]), Err(CliError::EjectExists(path)) if path == target));
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "edited");
+        eject(&["--force"]).unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "body{}");
+    }
+
+    #[test]
+    fn eject_rejects_unknown_names() {
+        let err = try_run_cli_from(
+            ["sherwood", "eject", "main.css"],
+            ParserRegistry::default(),
+            |_, _| Ok(String::new()),
+            vec![Asset::new("style.css", &b"body{}"[..])],
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, CliError::UnknownAsset { available, .. } if available == "style.css"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn try_run_cli_from_dry_run_writes_nothing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+        std::fs::write(content.join("logo.svg"), "<svg/>").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--dry-run",
+            ],
+            ParserRegistry::default(),
+            |page, _ctx| Ok(page.frontmatter.title.clone()),
+            vec![Asset::new("style.css", &b"body{}"[..])],
+        )
+        .unwrap();
+
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn try_run_cli_from_wraps_html_pages_with_frontmatter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("landing.html"),
+            "---\ntitle: Landing\n---\n<section>Hi</section>\n",
+        )
+        .unwrap();
+        std::fs::write(content.join("bespoke.html"), "<html>Mine</html>").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--wrap-html",
+            ],
+            ParserRegistry::default(),
+            |page, _ctx| Ok(format!("<main>{}</main>", page.content_html)),
+            Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(output.join("landing/index.html")).unwrap(),
+            "<main><section>Hi</section></main>"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.join("bespoke.html")).unwrap(),
+            "<html>Mine</html>"
+        );
+    }
+
+    #[test]
+    fn try_run_cli_from_applies_config_file_markdown_features() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("Sherwood.toml");
+        std::fs::write(
+            &file,
+            "content_dir = \"content\"\noutput_dir = \"out\"\n[markdown]\ntables = false\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("content")).unwrap();
+        std::fs::write(
+            tmp.path().join("content/index.md"),
+            "---\ntitle: Home\n---\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+        )
+        .unwrap();
+
+        try_run_cli_from(
+            ["sherwood", "build", "--config", file.to_str().unwrap()],
+            ParserRegistry::default(),
+            |page, _ctx| Ok(page.content_html.clone()),
+            Vec::new(),
+        )
+        .unwrap();
+
+        let html = std::fs::read_to_string(tmp.path().join("out/index.html")).unwrap();
+        assert!(!html.contains("<table>"), "{html}");
+        assert!(html.contains("| a | b |"), "{html}");
+    }
+
+    #[test]
+    fn try_run_cli_from_keeps_registered_markdown_parser_with_config_file() {
+        use crate::{MarkdownParser, ShortcodeCall, ShortcodeRegistry};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("Sherwood.toml");
+        std::fs::write(
+            &file,
+            "content_dir = \"content\"\noutput_dir = \"out\"\n[markdown]\ntables = false\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("content")).unwrap();
+        std::fs::write(
+            tmp.path().join("content/index.md"),
+            "---\ntitle: Home\n---\n{{< greet Ada >}}\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+        )
+        .unwrap();
+        let mut shortcodes = ShortcodeRegistry::new();
+        shortcodes.register("greet", |call: &ShortcodeCall| {
+            Ok(format!("<b>Hi {}</b>", call.arg(0).unwrap_or_default()))
+        });
+        let mut registry = ParserRegistry::default();
+        registry.register(Arc::new(MarkdownParser::new().with_shortcodes(shortcodes)));
+
+        try_run_cli_from(
+            ["sherwood", "build", "--config", file.to_str().unwrap()],
+            registry,
+            |page, _ctx| Ok(page.content_html.clone()),
+            Vec::new(),
+        )
+        .unwrap();
+
+        // The shortcode still expands, and the file's `[markdown]` applies.
+        let html = std::fs::read_to_string(tmp.path().join("out/index.html")).unwrap();
+        assert!(html.contains("<b>Hi Ada</b>"), "{html}");
+        assert!(!html.contains("<table>"), "{html}");
+    }
+
+    #[test]
+    fn try_run_cli_from_renders_mermaid_when_config_enables_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("Sherwood.toml");
+        std::fs::write(
+            &file,
+            "content_dir = \"content\"\noutput_dir = \"out\"\n[markdown]\nmermaid = true\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("content")).unwrap();
+        std::fs::write(
+            tmp.path().join("content/index.md"),
+            "---\ntitle: Home\n---\n```mermaid\ngraph TD\n```\n",
+        )
+        .unwrap();
+
+        try_run_cli_from(
+            ["sherwood", "build", "--config", file.to_str().unwrap()],
+            ParserRegistry::default(),
+            |page, _ctx| Ok(page.content_html.clone()),
+            Vec::new(),
+        )
+        .unwrap();
+
+        let html = std::fs::read_to_string(tmp.path().join("out/index.html")).unwrap();
+        assert!(html.contains("<pre class=\"mermaid\">graph TD"), "{html}");
+    }
+
+    #[test]
+    fn try_run_cli_from_uses_excerpt_separator_flag() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("post.md"),
+            "---\ntitle: Post\n---\nIntro.\n\n<!--cut-->\n\nRest.\n",
+        )
+        .unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--excerpt-separator",
+                "<!--cut-->",
+            ],
+            ParserRegistry::default(),
+            |page, _ctx| Ok(page.excerpt_html.clone().unwrap_or_default()),
+            Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(output.join("post/index.html")).unwrap(),
+            "<p>Intro.</p>\n"
+        );
+    }
+
+    #[test]
+    fn try_run_cli_from_writes_stylesheet_under_configured_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--base-path",
+                "/docs",
+                "--stylesheet",
+                "css/main.css",
+            ],
+            ParserRegistry::default(),
+            |_page, ctx| Ok(ctx.stylesheet.clone()),
+            vec![Asset::new("style.css", &b"body{}"[..])],
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(output.join("index.html")).unwrap(),
+            "/docs/css/main.css"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.join("css/main.css")).unwrap(),
+            "body{}"
+        );
+        assert!(!output.join("style.css").exists());
+    }
+
+    #[test]
+    fn try_run_cli_from_fingerprints_stylesheet() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::create_dir_all(&output).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+        std::fs::write(output.join("style.0123456789abcdef.css"), "stale").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--fingerprint-stylesheet",
+            ],
+            ParserRegistry::default(),
+            |_page, ctx| Ok(ctx.stylesheet.clone()),
+            vec![Asset::new("style.css", &b"body{}"[..])],
+        )
+        .unwrap();
+
+        let href = std::fs::read_to_string(output.join("index.html")).unwrap();
+        let name = href.trim_start_matches('/');
+        assert_eq!(
+            name,
+            fingerprint::fingerprinted_name("style.css", b"body{}")
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.join(name)).unwrap(),
+            "body{}"
+        );
+        assert!(!output.join("style.0123456789abcdef.css").exists());
+        assert!(!output.join("style.css").exists());
+    }
+
+    #[test]
+    fn base_url_alias_sets_normalized_base_path() {
+        let cli = Cli::parse_from(["sherwood", "build", "--base-url", "docs/"]);
+        let Commands::Build { site, watch, .. } = cli.command else {
+            panic!("expected build");
+        };
+        assert_eq!(site.config().base_path, "/docs");
+        assert!(!watch);
+    }
+
+    #[test]
+    fn extra_content_dir_may_be_repeated() {
+        let cli = Cli::parse_from([
+            "sherwood",
+            "build",
+            "--extra-content-dir",
+            "blog",
+            "--extra-content-dir",
+            "docs",
+        ]);
+        let Commands::Build { site, .. } = cli.command else {
+            panic!("expected build");
+        };
+        assert_eq!(
+            site.config().extra_content_dirs,
+            [PathBuf::from("blog"), PathBuf::from("docs")]
+        );
+    }
+
+    #[test]
+    fn config_file_supplies_settings_and_flags_override_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("Sherwood.staging.toml");
+        std::fs::write(
+            &file,
+            concat!(
+                "content_dir = \"src\"\n",
+                "base_path = \"/docs\"\n",
+                "site_url = \"https://staging.example.com\"\n",
+                "related_limit = 3\n",
+                "[toc]\n",
+                "max_level = 4\n",
+            ),
+        )
+        .unwrap();
+        let cli = parse_cli([
+            "sherwood",
+            "build",
+            "--config",
+            file.to_str().unwrap(),
+            "--site-url",
+            "https://example.com",
+            "--toc-min-level",
+            "3",
+        ])
+        .unwrap();
+        let Commands::Build { site, .. } = cli.command else {
+            panic!("expected build");
+        };
+        let config = site.config();
+        assert_eq!(config.content_dir, tmp.path().join("src"));
+        assert_eq!(config.base_path, "/docs");
+        assert_eq!(config.site_url, "https://example.com");
+        assert_eq!(config.related_limit, 3);
+        assert_eq!((config.toc_min_level, config.toc_max_level), (3, 4));
+    }
+
+    #[test]
+    fn env_file_fills_config_variables_and_needs_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("Sherwood.toml");
+        let env_file = tmp.path().join(".env");
+        std::fs::write(&file, "base_path = \"${SHERWOOD_TEST_CLI_BASE}\"\n").unwrap();
+        std::fs::write(&env_file, "SHERWOOD_TEST_CLI_BASE=/preview\n").unwrap();
+        let cli = parse_cli([
+            "sherwood",
+            "build",
+            "--config",
+            file.to_str().unwrap(),
+            "--env-file",
+            env_file.to_str().unwrap(),
+        ])
+        .unwrap();
+        let Commands::Build { site, .. } = cli.command else {
+            panic!("expected build");
+        };
+        assert_eq!(site.config().base_path, "/preview");
+        assert!(Cli::try_parse_from(["sherwood", "build", "--env-file", ".env"]).is_err());
+    }
+
+    #[test]
+    fn missing_config_file_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("Sherwood.prod.toml");
+        assert!(matches!(
+            parse_cli(["sherwood", "build", "--config", missing.to_str().unwrap()]),
+            Err(CliError::Config(ConfigError::Read { .. }))
+        ));
+    }
+
+    #[test]
+    fn log_flags_set_level_and_format() {
+        let cli = Cli::parse_from(["sherwood", "build", "-v", "--log-format", "json"]);
+        assert!(cli.verbose && !cli.quiet);
+        assert_eq!(cli.log_format, LogFormat::Json);
+        let cli = Cli::parse_from(["sherwood", "--quiet", "check"]);
+        assert!(cli.quiet);
+        assert_eq!(cli.log_format, LogFormat::Text);
+        assert!(Cli::try_parse_from(["sherwood", "build", "-q", "-v"]).is_err());
+    }
+
+    #[test]
+    fn human_bytes_picks_a_unit() {
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(4198), "4.1 KB");
+        assert_eq!(human_bytes(1_258_291), "1.2 MB");
+    }
+
+    #[test]
+    fn build_accepts_watch_flag() {
+        let cli = Cli::parse_from(["sherwood", "build", "--watch"]);
+        assert!(matches!(cli.command, Commands::Build { watch: true, .. }));
+    }
+
+    #[test]
+    fn build_serve_takes_port_and_excludes_watch() {
+        let cli = Cli::parse_from(["sherwood", "build", "--serve", "--port", "0"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Build {
+                serve: true,
+                port: 0,
+                ..
+            }
+        ));
+        for args in [
+            &["sherwood", "build", "--port", "8080"][..],
+            &["sherwood", "build", "--serve", "--watch"][..],
+            &["sherwood", "build", "--serve", "--feeds-only"][..],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn head_tags_skip_malformed_entries() {
+        let tags = head_tags(&[
+            "link rel=icon href=/favicon.ico".to_string(),
+            "link rel=icon".to_string(),
+            "meta name=\"unterminated".to_string(),
+        ]);
+        assert_eq!(
+            tags,
+            vec![HeadTag::link([("rel", "icon"), ("href", "/favicon.ico")])]
+        );
+    }
+
+    #[test]
+    fn try_run_cli_from_writes_requested_feeds() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("post.md"),
+            "---\ntitle: Post\ndate: 2026-05-31\n---\n",
+        )
+        .unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--site-url",
+                "https://example.com",
+                "--feed",
+                "atom,rss",
+            ],
+            ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            vec![],
+        )
+        .unwrap();
+
+        let atom = std::fs::read_to_string(output.join("atom.xml")).unwrap();
+        assert!(atom.contains("https://example.com/post/"), "{atom}");
+        assert!(output.join("rss.xml").exists());
+    }
+
+    #[test]
+    fn try_run_cli_from_feeds_only_skips_pages_and_assets() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("post.md"),
+            "---\ntitle: Post\ndate: 2026-05-31\n---\n",
+        )
+        .unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--feeds-only",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--feed",
+                "rss",
+            ],
+            ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            vec![Asset::new("style.css", &b"body{}"[..])],
+        )
+        .unwrap();
+
+        assert!(output.join("rss.xml").exists());
+        assert!(!output.join("post/index.html").exists());
+        assert!(!output.join("style.css").exists());
+    }
+
+    #[test]
+    fn try_run_cli_from_exports_single_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let file = tmp.path().join("dist/manual.html");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n\n# Hi\n").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "export",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--file",
+                file.to_str().unwrap(),
+            ],
+            ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            vec![Asset::new("style.css", &b"body{}"[..])],
+        )
+        .unwrap();
+
+        let html = std::fs::read_to_string(&file).unwrap();
+        assert!(html.contains("<style>\nbody{}\n</style>"), "{html}");
+        assert!(html.contains("<h1 id=\"index--hi\">Hi</h1>"), "{html}");
+    }
+
+    #[test]
+    fn try_run_cli_from_check_fails_only_when_strict() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("index.md"),
+            "---\ntitle: Home\n---\n\n![](a.png)\n",
+        )
+        .unwrap();
+        let check = |strict: bool| {
+            let mut args = vec![
+                "sherwood",
+                "check",
+                "--content-dir",
+                content.to_str().unwrap(),
+            ];
+            if strict {
+                args.push("--strict");
+            }
+            try_run_cli_from(
+                args,
+                ParserRegistry::default(),
+                |_p, _c| Ok(String::new()),
+                vec![],
+            )
+        };
+
+        check(false).unwrap();
+        assert!(matches!(check(true), Err(CliError::Check(1))));
+    }
+
+    #[test]
+    fn try_run_cli_from_fails_on_warning_only_when_asked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+        let output = tmp.path().join("out");
+        let build = |fail_on_warning: bool| {
+            // Inverted TOC levels are adjusted with a warning.
+            let mut args = vec![
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--toc-min-level",
+                "4",
+                "--toc-max-level",
+                "2",
+                "--quiet",
+            ];
+            if fail_on_warning {
+                args.push("--fail-on-warning");
+            }
+            try_run_cli_from(
+                args,
+                ParserRegistry::default(),
+                |_p, _c| Ok(String::new()),
+                vec![],
+            )
+        };
+
+        build(false).unwrap();
+        assert!(matches!(build(true), Err(CliError::Warnings(1))));
+    }
+
+    #[test]
+    fn try_run_cli_from_warns_about_atom_feed_without_site_url() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+        let output = tmp.path().join("out");
+        let build = |site_url: Option<&str>| {
+            let mut args = vec![
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--feed",
+                "atom",
+                "--fail-on-warning",
+                "--quiet",
+            ];
+            if let Some(url) = site_url {
+                args.extend(["--site-url", url]);
+            }
+            try_run_cli_from(
+                args,
+                ParserRegistry::default(),
+                |_p, _c| Ok(String::new()),
+                vec![],
+            )
+        };
+
+        assert!(matches!(build(None), Err(CliError::Warnings(1))));
+        build(Some("https://example.com")).unwrap();
+    }
+
+    #[test]
+    fn try_run_cli_from_links_print_stylesheet() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        let print = tmp.path().join("my-print.css");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+        std::fs::write(&print, "nav { display: none }").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--print-stylesheet",
+                print.to_str().unwrap(),
+            ],
+            ParserRegistry::default(),
+            |_page, ctx| Ok(ctx.print_stylesheet.clone().unwrap_or_default()),
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output.join("index.html")).unwrap(),
+            "/print.css"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.join("print.css")).unwrap(),
+            "nav { display: none }"
+        );
+    }
+
+    #[test]
+    fn try_run_cli_from_require_fails_on_missing_fields() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        std::fs::create_dir_all(content.join("blog")).unwrap();
+        std::fs::write(content.join("blog/post.md"), "---\ntitle: Post\n---\n").unwrap();
+
+        let err = try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                tmp.path().join("out").to_str().unwrap(),
+                "--require",
+                "blog/**=date",
+            ],
+            ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            vec![],
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("missing required frontmatter `date`"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn try_run_cli_from_compresses_build_output() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = tmp.path().join("content");
+        let output = tmp.path().join("out");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("index.md"), "---\ntitle: Home\n---\n").unwrap();
+
+        try_run_cli_from(
+            [
+                "sherwood",
+                "build",
+                "--content-dir",
+                content.to_str().unwrap(),
+                "--output-dir",
+                output.to_str().unwrap(),
+                "--compress",
+                "gzip,brotli",
+                "--compress-min-size",
+                "0",
+                "--compress-extensions",
+                "html",
+            ],
+            ParserRegistry::default(),
+            |_p, _c| Ok("<p>home</p>".repeat(100)),
+            vec![Asset::new("style.css", "body{}".repeat(100).into_bytes())],
+        )
+        .unwrap();
+
+        assert!(output.join("index.html.gz").exists());
+        assert!(output.join("index.html.br").exists());
+        assert!(!output.join("style.css.gz").exists());
+    }
+
     #[test]
     fn try_run_cli_from_missing_content_dir_returns_build_error() {
         let tmp = tempfile::tempdir().unwrap();
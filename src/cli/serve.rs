@@ -1,15 +1,15 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
 
 use axum::{
     Router,
-    body::{Body, to_bytes},
+    body::{Body, HttpBody, to_bytes},
     extract::{
         State, WebSocketUpgrade,
         ws::{Message, WebSocket},
     },
-    http::{Request, Response, header},
+    http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode, header},
     middleware::{self, Next},
     response::{IntoResponse, Redirect},
     routing::get,
@@ -18,7 +18,15 @@ use thiserror::Error;
 use tokio::sync::broadcast;
 use tower_http::services::ServeDir;
 
+use super::fingerprint::fnv1a;
+use super::log::Log;
+use super::watch::watch_loop;
 use crate::core::build::BuildError;
+use crate::core::config::SiteConfig;
+use crate::core::content::page::PageError;
+use crate::core::content::parser::escape_html;
+use crate::core::content::section::SectionError;
+use crate::core::redirect::{self, Redirect as RedirectRule};
 
 #[derive(Debug, Error)]
 pub enum ServeError {
@@ -30,27 +38,118 @@ pub enum ServeError {
     Watcher(String),
 }
 
+/// The redirect rules `serve` answers, replaced after each successful
+/// build (configured rules and page aliases both change with the content).
+pub(super) type SharedRedirects = Arc<RwLock<Vec<RedirectRule>>>;
+
+/// The last rebuild's failure while watching, shown over every served page
+/// until a rebuild succeeds. `None` after a good build.
+pub(super) type SharedFailure = Arc<RwLock<Option<BuildFailure>>>;
+
+/// A failed rebuild as the dev server's error overlay shows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct BuildFailure {
+    /// The source file the error points at, when it names one.
+    file: Option<PathBuf>,
+    message: String,
+}
+
+impl BuildFailure {
+    fn new(error: &BuildError) -> Self {
+        let file = match error {
+            BuildError::Page(
+                PageError::Read { path, .. }
+                | PageError::Parse { path, .. }
+                | PageError::MissingFields { path, .. }
+                | PageError::InvalidDate { path, .. },
+            ) => Some(path.clone()),
+            BuildError::Read { path, .. } => Some(path.clone()),
+            BuildError::DuplicateOutput { second, .. } => Some(second.clone()),
+            BuildError::Section(
+                SectionError::Read { path, .. } | SectionError::Parse { path, .. },
+            ) => Some(path.clone()),
+            _ => None,
+        };
+        Self {
+            file,
+            message: error.to_string(),
+        }
+    }
+
+    /// A fixed panel over the page naming the file and the error. The page
+    /// underneath is whatever the last good build wrote.
+    fn to_html(&self) -> String {
+        let file = self
+            .file
+            .as_ref()
+            .map(|f| {
+                format!(
+                    "<p style=\"margin:0 0 .75em;font-weight:bold\">{}</p>",
+                    escape_html(&f.display().to_string())
+                )
+            })
+            .unwrap_or_default();
+        format!(
+            "\n<div id=\"{ERROR_OVERLAY_ID}\" role=\"alert\" style=\"position:fixed;inset:0;z-index:2147483647;overflow:auto;padding:2em;background:rgba(20,0,0,.92);color:#fff;font:14px/1.5 ui-monospace,monospace\">\
+             <p style=\"margin:0 0 .75em;font-size:1.25em;color:#ff8080\">Build failed</p>{file}\
+             <pre style=\"margin:0;white-space:pre-wrap\">{}</pre>\
+             <p style=\"margin:1em 0 0;opacity:.7\">Showing the last successful build. This clears when the next build succeeds.</p></div>\n",
+            escape_html(&self.message)
+        )
+    }
+}
+
 const LIVE_RELOAD_PATH: &str = "/_sherwood/reload";
 
+/// `id` of the build-error overlay injected while the last rebuild failed.
+const ERROR_OVERLAY_ID: &str = "sherwood-build-error";
+
 const LIVE_RELOAD_SNIPPET: &str = "\n<script>\n(function(){function c(){var p=location.protocol==='https:'?'wss':'ws';var w=new WebSocket(p+'://'+location.host+'/_sherwood/reload');w.onmessage=function(){location.reload();};w.onclose=function(){setTimeout(c,1000);};}c();})();\n</script>\n";
 
+/// `Cache-Control` while watching: browsers may keep files but must
+/// revalidate on every load, so a rebuild is never masked by the cache.
+const DEV_CACHE_CONTROL: &str = "no-cache";
+
+/// `Cache-Control` for `--no-watch` serving, which previews a finished build:
+/// files are cached for a day, as a production host might.
+const PREVIEW_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Largest response body read into memory, to inject the reload script or
+/// hash an `ETag`. Bigger ones are streamed as they are.
+const MAX_BUFFERED_BODY: usize = 8 * 1024 * 1024;
+
 /// Build a router for static-only serving (no live reload).
 pub fn router(output_dir: &Path, base_path: &str) -> Router {
-    mount(Router::new(), output_dir, base_path)
+    mount(Router::new(), output_dir, base_path).layer(middleware::from_fn_with_state(
+        HeaderValue::from_static(PREVIEW_CACHE_CONTROL),
+        cache_headers,
+    ))
 }
 
 /// Build a router with live-reload wiring: a `/_sherwood/reload` websocket
 /// endpoint that pushes `reload` messages on the broadcast channel, plus a
 /// middleware that injects a tiny script into every served HTML response so
-/// the browser connects to that socket.
+/// the browser connects to that socket. While `failure` holds a failed
+/// rebuild, the same middleware injects its error overlay too.
 pub fn router_with_reload(
     output_dir: &Path,
     reload_tx: broadcast::Sender<()>,
+    failure: SharedFailure,
     base_path: &str,
 ) -> Router {
     let state = Arc::new(reload_tx);
     let router = Router::new().route(LIVE_RELOAD_PATH, get(ws_handler).with_state(state));
-    mount(router, output_dir, base_path).layer(middleware::from_fn(inject_reload_script))
+    // The cache layer wraps the injector so the ETag covers the body the
+    // browser actually receives.
+    mount(router, output_dir, base_path)
+        .layer(middleware::from_fn_with_state(
+            failure,
+            inject_reload_script,
+        ))
+        .layer(middleware::from_fn_with_state(
+            HeaderValue::from_static(DEV_CACHE_CONTROL),
+            cache_headers,
+        ))
 }
 
 /// Attach the static-file service to `router`. With an empty `base_path` the
@@ -59,7 +158,7 @@ pub fn router_with_reload(
 /// subpath. The live-reload websocket route stays at the root either way.
 fn mount(router: Router, output_dir: &Path, base_path: &str) -> Router {
     let serve = ServeDir::new(output_dir);
-    if base_path.is_empty() {
+    let router = if base_path.is_empty() {
         router.fallback_service(serve)
     } else {
         let target = format!("{base_path}/");
@@ -72,20 +171,65 @@ fn mount(router: Router, output_dir: &Path, base_path: &str) -> Router {
                 }),
             )
             .nest_service(base_path, serve)
+    };
+    router.layer(middleware::from_fn(byte_ranges))
+}
+
+/// Let browsers seek media: `ServeDir` answers a single `Range: bytes=…`
+/// with `206 Partial Content`. A range header we can't honour (malformed,
+/// another unit, several ranges) is dropped so the whole file comes back
+/// with `200`, as RFC 9110 allows, instead of a `416`.
+async fn byte_ranges(mut req: Request<Body>, next: Next) -> Response<Body> {
+    let unusable = req
+        .headers()
+        .get(header::RANGE)
+        .is_some_and(|v| !v.to_str().is_ok_and(is_single_byte_range));
+    if unusable {
+        req.headers_mut().remove(header::RANGE);
+    }
+    let mut resp = next.run(req).await;
+    if matches!(resp.status(), StatusCode::OK | StatusCode::PARTIAL_CONTENT) {
+        resp.headers_mut()
+            .entry(header::ACCEPT_RANGES)
+            .or_insert(HeaderValue::from_static("bytes"));
+    }
+    resp
+}
+
+/// Whether a `Range` value is one well-formed byte range: `bytes=0-499`,
+/// `bytes=500-` or `bytes=-500`.
+fn is_single_byte_range(value: &str) -> bool {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return false;
+    };
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return false;
+    };
+    let number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match (start.is_empty(), end.is_empty()) {
+        (false, true) => number(start),
+        (true, false) => number(end),
+        (false, false) => {
+            number(start) && number(end) && start.parse::<u64>().ok() <= end.parse::<u64>().ok()
+        }
+        (true, true) => false,
     }
 }
 
-/// Start the dev server. If `watch` is true, also watches `content_dir` (and
-/// any extra `watch_paths`, e.g. `--asset` override source files), reruns
-/// `rebuild` on changes, and pushes live-reload notifications.
-pub async fn serve_with_watch<F>(
-    content_dir: PathBuf,
-    output_dir: PathBuf,
-    base_path: String,
-    port: u16,
+/// Start the dev server on `addr`. If `watch` is true, also watches every
+/// content directory (and any extra `watch_paths`, e.g. `--asset` override source
+/// files), reruns `rebuild` on changes, and pushes live-reload notifications.
+/// A failed rebuild also pushes one, so open pages reload into an overlay
+/// showing the error; the next good build clears it.
+/// `redirects` (kept current by `rebuild`) are answered before any file.
+pub(super) async fn serve_with_watch<F>(
+    config: &SiteConfig,
+    addr: SocketAddr,
     mut rebuild: F,
     watch: bool,
     watch_paths: Vec<PathBuf>,
+    redirects: SharedRedirects,
+    log: Log,
 ) -> Result<(), ServeError>
 where
     F: FnMut() -> Result<(), BuildError> + Send + 'static,
@@ -94,126 +238,135 @@ where
     // — the user's first request would 404 otherwise.
     rebuild().map_err(ServeError::Build)?;
 
+    let output_dir = &config.output_dir;
+    let content_dirs: Vec<PathBuf> = config.content_roots().map(Path::to_path_buf).collect();
+    let watched = content_dirs
+        .iter()
+        .map(|dir| dir.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let base_path = config.base_path.as_str();
     let app = if watch {
         let (tx, _rx) = broadcast::channel::<()>(16);
+        let failure = SharedFailure::default();
         let tx_for_watcher = tx.clone();
-        let content_dir_for_watcher = content_dir.clone();
+        let failure_for_watcher = failure.clone();
+        let log_for_watcher = log.clone();
         tokio::task::spawn_blocking(move || {
-            watch_loop(content_dir_for_watcher, watch_paths, tx_for_watcher, rebuild);
+            watch_loop(
+                content_dirs,
+                &watch_paths,
+                log_for_watcher,
+                rebuild,
+                |outcome| {
+                    if let Ok(mut failure) = failure_for_watcher.write() {
+                        *failure = outcome.err().map(BuildFailure::new);
+                    }
+                    let _ = tx_for_watcher.send(());
+                },
+            );
         });
-        router_with_reload(&output_dir, tx, &base_path)
+        router_with_reload(output_dir, tx, failure, base_path)
     } else {
-        router(&output_dir, &base_path)
+        router(output_dir, base_path)
     };
-
-    let addr = format!("127.0.0.1:{}", port);
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    let url = format!("http://{addr}{base_path}/");
-    if watch {
-        println!(
+    let app = with_redirects(app, redirects, base_path);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    // Read the address back rather than echoing `addr`: with port 0 the OS
+    // picks a free one, and this is the only place to learn which.
+    let addr = listener.local_addr()?;
+    let url = server_url(addr, base_path);
+    let message = if watch {
+        format!(
             "Serving {} at {} (watching {} for changes)",
             output_dir.display(),
             url,
-            content_dir.display()
-        );
+            watched
+        )
     } else {
-        println!("Serving {} at {}", output_dir.display(), url);
+        format!("Serving {} at {}", output_dir.display(), url)
+    };
+    log.info("serve", message, &[("url", url.as_str().into())]);
+    if let Some(network) = network_url(addr, base_path) {
+        log.info(
+            "serve_network",
+            format!("On your network: {network}"),
+            &[("url", network.as_str().into())],
+        );
     }
     axum::serve(listener, app).await?;
     Ok(())
 }
 
-fn watch_loop<F>(
-    content_dir: PathBuf,
-    watch_paths: Vec<PathBuf>,
-    reload_tx: broadcast::Sender<()>,
-    mut rebuild: F,
-) where
-    F: FnMut() -> Result<(), BuildError> + Send + 'static,
-{
-    use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
-
-    let (event_tx, event_rx) = std::sync::mpsc::channel();
-    let mut debouncer = match new_debouncer(Duration::from_millis(300), move |res| {
-        let _ = event_tx.send(res);
-    }) {
-        Ok(d) => d,
-        Err(e) => {
-            eprintln!("Failed to start file watcher: {e}");
-            return;
+/// Answer the `rules`' old paths with HTTP redirects. Layered outermost, so
+/// a redirected path never reaches the stub page the build wrote there.
+fn with_redirects(app: Router, rules: SharedRedirects, base_path: &str) -> Router {
+    let state = (rules, base_path.to_string());
+    app.layer(middleware::from_fn_with_state(state, redirects))
+}
+
+/// Answer a path with a redirect rule, under the base path, with the rule's
+/// status and resolved target.
+async fn redirects(
+    State((rules, base_path)): State<(SharedRedirects, String)>,
+    req: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let path = req.uri().path();
+    let redirect = match path.strip_prefix(base_path.as_str()) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            rules.read().ok().and_then(|rules| {
+                redirect::find(&rules, rest).map(|r| (r.status, r.target(&base_path)))
+            })
         }
+        _ => None,
     };
-    if let Err(e) = debouncer
-        .watcher()
-        .watch(&content_dir, RecursiveMode::Recursive)
-    {
-        eprintln!("Failed to watch {}: {e}", content_dir.display());
-        return;
-    }
-    for path in &watch_paths {
-        if let Err(e) = debouncer.watcher().watch(path, RecursiveMode::NonRecursive) {
-            eprintln!("Failed to watch {}: {e}", path.display());
-        }
+    let Some((status, target)) = redirect else {
+        return next.run(req).await;
+    };
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::MOVED_PERMANENTLY);
+    match HeaderValue::from_str(&target) {
+        Ok(location) => (status, [(header::LOCATION, location)]).into_response(),
+        Err(_) => next.run(req).await,
     }
+}
 
-    // Snapshot watched-file mtimes so we can ignore spurious events.
-    // Reading files during a rebuild updates `atime`, which fires `IN_ATTRIB`
-    // events on Linux even though the data hasn't changed — without this
-    // guard, every rebuild self-triggers another rebuild.
-    let mut snapshot = snapshot_watched(&content_dir, &watch_paths);
-
-    for res in event_rx {
-        match res {
-            Ok(events) if !events.is_empty() => {
-                let current = snapshot_watched(&content_dir, &watch_paths);
-                if current == snapshot {
-                    continue;
-                }
-                eprintln!("Change detected — rebuilding...");
-                match rebuild() {
-                    Ok(()) => {
-                        eprintln!("Rebuild complete.");
-                        let _ = reload_tx.send(());
-                    }
-                    Err(e) => eprintln!("Rebuild failed: {e}"),
-                }
-                snapshot = snapshot_watched(&content_dir, &watch_paths);
-            }
-            Ok(_) => {}
-            Err(e) => eprintln!("Watcher error: {e}"),
-        }
-    }
+/// The URL a browser on this machine should open for a server bound to
+/// `addr`. A wildcard bind (`0.0.0.0`, `::`) is reached through loopback.
+fn server_url(addr: SocketAddr, base_path: &str) -> String {
+    let ip = match addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    format!("http://{}{base_path}/", SocketAddr::new(ip, addr.port()))
 }
 
-/// Mtime snapshot of the content tree plus any extra watched files (`--asset`
-/// override sources), so changes to either defeat the spurious-event guard.
-fn snapshot_watched(
-    content_dir: &Path,
-    watch_paths: &[PathBuf],
-) -> std::collections::HashMap<PathBuf, std::time::SystemTime> {
-    let mut map = snapshot_mtimes(content_dir);
-    for path in watch_paths {
-        if let Ok(meta) = std::fs::metadata(path)
-            && let Ok(mtime) = meta.modified()
-        {
-            map.insert(path.clone(), mtime);
-        }
-    }
-    map
+/// The URL other devices (a phone on the same Wi-Fi) can use when the server
+/// listens beyond loopback. For a wildcard bind that's the machine's LAN
+/// address; `None` if it can't be determined.
+fn network_url(addr: SocketAddr, base_path: &str) -> Option<String> {
+    let ip = match addr.ip() {
+        ip if ip.is_loopback() => return None,
+        ip if ip.is_unspecified() => lan_ip(ip.is_ipv6())?,
+        ip => ip,
+    };
+    Some(server_url(SocketAddr::new(ip, addr.port()), base_path))
 }
 
-fn snapshot_mtimes(root: &Path) -> std::collections::HashMap<PathBuf, std::time::SystemTime> {
-    use walkdir::WalkDir;
-    let mut map = std::collections::HashMap::new();
-    for entry in WalkDir::new(root).into_iter().flatten() {
-        if entry.file_type().is_file()
-            && let Ok(meta) = entry.metadata()
-            && let Ok(mtime) = meta.modified()
-        {
-            map.insert(entry.path().to_owned(), mtime);
-        }
-    }
-    map
+/// The address of the interface the OS would route outbound traffic
+/// through. Connecting a UDP socket sends nothing; it only picks a route.
+fn lan_ip(ipv6: bool) -> Option<IpAddr> {
+    let (bind, probe) = if ipv6 {
+        ("[::]:0", "[2001:4860:4860::8888]:80")
+    } else {
+        ("0.0.0.0:0", "8.8.8.8:80")
+    };
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(probe).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
 }
 
 async fn ws_handler(
@@ -234,7 +387,13 @@ async fn handle_socket(mut socket: WebSocket, rx: &mut broadcast::Receiver<()>)
     }
 }
 
-async fn inject_reload_script(req: Request<Body>, next: Next) -> Response<Body> {
+/// Add the live-reload script, and the error overlay while the last rebuild
+/// failed, to an HTML response.
+async fn inject_reload_script(
+    State(failure): State<SharedFailure>,
+    req: Request<Body>,
+    next: Next,
+) -> Response<Body> {
     let resp = next.run(req).await;
     let (mut parts, body) = resp.into_parts();
     let is_html = parts
@@ -243,23 +402,117 @@ async fn inject_reload_script(req: Request<Body>, next: Next) -> Response<Body>
         .and_then(|v| v.to_str().ok())
         .map(|v| v.starts_with("text/html"))
         .unwrap_or(false);
-    if !is_html {
+    let fits = parts
+        .headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok()?.parse::<usize>().ok())
+        .is_some_and(|len| len <= MAX_BUFFERED_BODY);
+    // A partial response is a slice of the file; leave it alone, and a page
+    // too big to buffer too.
+    if !is_html || parts.status != StatusCode::OK || !fits {
         return Response::from_parts(parts, body);
     }
-    let bytes = match to_bytes(body, usize::MAX).await {
+    let bytes = match to_bytes(body, MAX_BUFFERED_BODY).await {
         Ok(b) => b,
         Err(_) => return Response::from_parts(parts, Body::empty()),
     };
     let mut html = String::from_utf8_lossy(&bytes).into_owned();
+    let mut snippet = failure
+        .read()
+        .ok()
+        .and_then(|f| f.as_ref().map(BuildFailure::to_html))
+        .unwrap_or_default();
+    snippet.push_str(LIVE_RELOAD_SNIPPET);
     if let Some(pos) = html.rfind("</body>") {
-        html.insert_str(pos, LIVE_RELOAD_SNIPPET);
+        html.insert_str(pos, &snippet);
     } else {
-        html.push_str(LIVE_RELOAD_SNIPPET);
+        html.push_str(&snippet);
     }
     parts.headers.remove(header::CONTENT_LENGTH);
+    // Byte offsets into the file no longer match the body sent.
+    parts.headers.remove(header::ACCEPT_RANGES);
     Response::from_parts(parts, Body::from(html))
 }
 
+/// Set `Cache-Control` and an `ETag` on successful GETs, and answer a
+/// matching `If-None-Match` with `304 Not Modified`. Bodies up to
+/// [`MAX_BUFFERED_BODY`] — files from disk and generated HTML alike — are
+/// tagged with a hash of the bytes sent, so a rebuild that rewrites a file
+/// within the same second still changes its tag. Bigger files are streamed
+/// with a weak tag from their metadata. Other responses (404s, ranges, the
+/// websocket upgrade) pass through untouched.
+async fn cache_headers(
+    State(cache_control): State<HeaderValue>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let is_get = req.method() == Method::GET;
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+    if if_none_match.is_some() {
+        // RFC 9110 §13.1.3: `If-None-Match` wins. Left in, `ServeDir` would
+        // answer `304` from the one-second `Last-Modified` before the tag is
+        // ever compared.
+        req.headers_mut().remove(header::IF_MODIFIED_SINCE);
+    }
+    let resp = next.run(req).await;
+    if !is_get || resp.status() != StatusCode::OK {
+        return resp;
+    }
+    let (mut parts, body) = resp.into_parts();
+    parts.headers.insert(header::CACHE_CONTROL, cache_control);
+    let len = body.size_hint().exact().or_else(|| {
+        parts
+            .headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok()?.parse().ok())
+    });
+    let (etag, body) = match len {
+        Some(len) if len <= MAX_BUFFERED_BODY as u64 => {
+            let bytes = match to_bytes(body, MAX_BUFFERED_BODY).await {
+                Ok(b) => b,
+                Err(_) => return Response::from_parts(parts, Body::empty()),
+            };
+            (format!("\"{:016x}\"", fnv1a(&bytes)), Body::from(bytes))
+        }
+        _ => match file_etag(&parts.headers) {
+            Some(etag) => (etag, body),
+            None => return Response::from_parts(parts, body),
+        },
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(header::ETAG, value);
+    }
+    let matched = if_none_match
+        .as_ref()
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| etag_matches(v, &etag));
+    if matched {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        return Response::from_parts(parts, Body::empty());
+    }
+    Response::from_parts(parts, body)
+}
+
+/// A weak `ETag` for a file too big to buffer, from the `Last-Modified` and
+/// `Content-Length` headers `ServeDir` sets, so it is never read to tag it.
+/// `None` once a header is missing, as for a rewritten body.
+fn file_etag(headers: &HeaderMap) -> Option<String> {
+    let modified = headers.get(header::LAST_MODIFIED)?;
+    let len = headers.get(header::CONTENT_LENGTH)?.to_str().ok()?;
+    Some(format!("W/\"{len}-{:016x}\"", fnv1a(modified.as_bytes())))
+}
+
+/// Whether an `If-None-Match` list names `etag`. Comparison is weak, per
+/// RFC 9110: a `W/` prefix on either side is ignored.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +558,167 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn sets_etag_and_answers_if_none_match() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("index.html"), "<h1>hi</h1>").unwrap();
+        let get = |etag: Option<&str>| {
+            let mut req = Request::builder().uri("/index.html");
+            if let Some(etag) = etag {
+                req = req.header(header::IF_NONE_MATCH, etag);
+            }
+            router(tmp.path(), "").oneshot(req.body(Body::empty()).unwrap())
+        };
+
+        let resp = get(None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[header::CACHE_CONTROL], PREVIEW_CACHE_CONTROL);
+        let etag = resp.headers()[header::ETAG].to_str().unwrap().to_string();
+        // A file from disk is tagged by a hash of its contents.
+        assert_eq!(etag, format!("\"{:016x}\"", fnv1a(b"<h1>hi</h1>")));
+
+        let resp = get(Some(&format!("\"stale\", {etag}"))).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+        assert!(bytes.is_empty());
+
+        fs::write(tmp.path().join("index.html"), "<h1>changed</h1>").unwrap();
+        let resp = get(Some(&etag)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_ne!(resp.headers()[header::ETAG], etag.as_str());
+    }
+
+    #[tokio::test]
+    async fn same_second_same_length_edit_changes_the_etag() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("index.html");
+        fs::write(&path, "<h1>one</h1>").unwrap();
+        let resp = router(tmp.path(), "")
+            .oneshot(
+                Request::builder()
+                    .uri("/index.html")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let etag = resp.headers()[header::ETAG].clone();
+        let last_modified = resp.headers()[header::LAST_MODIFIED].clone();
+
+        fs::write(&path, "<h1>two</h1>").unwrap();
+        let resp = router(tmp.path(), "")
+            .oneshot(
+                Request::builder()
+                    .uri("/index.html")
+                    .header(header::IF_NONE_MATCH, &etag)
+                    .header(header::IF_MODIFIED_SINCE, &last_modified)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_ne!(resp.headers()[header::ETAG], etag);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"<h1>two</h1>");
+    }
+
+    #[tokio::test]
+    async fn dev_server_requires_revalidation() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("index.html"), "<body></body>").unwrap();
+        let (tx, _rx) = broadcast::channel::<()>(1);
+        let resp = router_with_reload(tmp.path(), tx, SharedFailure::default(), "")
+            .oneshot(
+                Request::builder()
+                    .uri("/index.html")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.headers()[header::CACHE_CONTROL], DEV_CACHE_CONTROL);
+        // The injected page is hashed: its body isn't the file on disk.
+        let etag = resp.headers()[header::ETAG].to_str().unwrap();
+        assert!(etag.starts_with('"'), "{etag}");
+    }
+
+    #[test]
+    fn etag_matching_is_weak() {
+        assert!(etag_matches("\"a\"", "\"a\""));
+        assert!(etag_matches("\"b\", W/\"a\"", "\"a\""));
+        assert!(etag_matches("*", "\"a\""));
+        assert!(etag_matches("W/\"a\"", "W/\"a\""));
+        assert!(!etag_matches("\"b\"", "\"a\""));
+    }
+
+    #[tokio::test]
+    async fn honours_single_byte_ranges() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("clip.bin"), b"0123456789").unwrap();
+        let get = |range: &str| {
+            router(tmp.path(), "").oneshot(
+                Request::builder()
+                    .uri("/clip.bin")
+                    .header(header::RANGE, range)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let resp = get("bytes=2-5").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(resp.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+        assert_eq!(resp.headers()[header::ACCEPT_RANGES], "bytes");
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"2345");
+
+        let resp = get("bytes=-3").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"789");
+
+        for malformed in ["bytes=5-2", "bytes=a-b", "items=0-1", "bytes=0-1,4-5"] {
+            let resp = get(malformed).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "{malformed}");
+            let body = resp.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(&body[..], b"0123456789", "{malformed}");
+        }
+    }
+
+    #[tokio::test]
+    async fn large_pages_are_streamed_untouched() {
+        let tmp = TempDir::new().unwrap();
+        let page = format!("<body>{}</body>", "x".repeat(MAX_BUFFERED_BODY));
+        fs::write(tmp.path().join("index.html"), &page).unwrap();
+        let (tx, _rx) = broadcast::channel::<()>(1);
+        let resp = router_with_reload(tmp.path(), tx, SharedFailure::default(), "")
+            .oneshot(
+                Request::builder()
+                    .uri("/index.html")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[header::ACCEPT_RANGES], "bytes");
+        let etag = resp.headers()[header::ETAG].to_str().unwrap();
+        assert!(etag.starts_with("W/"), "{etag}");
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body.len(), page.len());
+    }
+
+    #[test]
+    fn recognises_single_byte_ranges() {
+        for ok in ["bytes=0-499", "bytes=500-", "bytes=-500", " bytes=0-0 "] {
+            assert!(is_single_byte_range(ok), "{ok}");
+        }
+        for bad in ["bytes=", "bytes=-", "bytes=1-2,3-4", "bytes=9-1", "0-1"] {
+            assert!(!is_single_byte_range(bad), "{bad}");
+        }
+    }
+
     #[tokio::test]
     async fn returns_404_for_missing() {
         let tmp = TempDir::new().unwrap();
@@ -358,6 +772,55 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn redirect_rules_answer_before_the_stub() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("old")).unwrap();
+        fs::write(tmp.path().join("old/index.html"), "stub").unwrap();
+        let rules = [
+            RedirectRule::new("/old/", "/new/"),
+            RedirectRule::new("/sale", "https://shop.example.com/").with_status(302),
+        ];
+        let rules = SharedRedirects::new(RwLock::new(rules.to_vec()));
+        let app = with_redirects(router(tmp.path(), "/docs"), rules, "/docs");
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/docs/old")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(resp.headers()[header::LOCATION], "/docs/new/");
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/docs/sale/")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FOUND);
+        assert_eq!(
+            resp.headers()[header::LOCATION],
+            "https://shop.example.com/"
+        );
+
+        // Outside the base path the rule doesn't apply.
+        let resp = app
+            .oneshot(Request::builder().uri("/old/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn reload_router_injects_script_into_html() {
         let tmp = TempDir::new().unwrap();
@@ -367,7 +830,7 @@ mod tests {
         )
         .unwrap();
         let (tx, _rx) = broadcast::channel::<()>(4);
-        let resp = router_with_reload(tmp.path(), tx, "")
+        let resp = router_with_reload(tmp.path(), tx, SharedFailure::default(), "")
             .oneshot(
                 Request::builder()
                     .uri("/index.html")
@@ -387,60 +850,67 @@ mod tests {
         assert!(script_pos < body_pos);
     }
 
-    #[test]
-    fn snapshot_mtimes_changes_when_content_changes() {
-        let tmp = TempDir::new().unwrap();
-        let f = tmp.path().join("page.md");
-        fs::write(&f, "v1").unwrap();
-        let snap1 = snapshot_mtimes(tmp.path());
-        // mtime has filesystem-dependent resolution; sleep to ensure tick.
-        std::thread::sleep(std::time::Duration::from_millis(50));
-        fs::write(&f, "v2 with more bytes").unwrap();
-        let snap2 = snapshot_mtimes(tmp.path());
-        assert_ne!(snap1, snap2, "rewriting a file must change snapshot");
-    }
-
-    #[test]
-    fn snapshot_mtimes_unchanged_when_file_only_read() {
-        // This is the load-bearing assertion for the live-reload watch loop:
-        // reading content files during a rebuild must not change the mtime
-        // snapshot, otherwise the loop would keep self-triggering.
+    #[tokio::test]
+    async fn reload_router_overlays_the_last_build_error() {
         let tmp = TempDir::new().unwrap();
-        let f = tmp.path().join("page.md");
-        fs::write(&f, "data").unwrap();
-        let snap1 = snapshot_mtimes(tmp.path());
-        std::thread::sleep(std::time::Duration::from_millis(50));
-        let _ = fs::read_to_string(&f).unwrap();
-        let snap2 = snapshot_mtimes(tmp.path());
-        assert_eq!(snap1, snap2, "reading a file must not change snapshot");
+        fs::write(tmp.path().join("index.html"), "<body><p>hi</p></body>").unwrap();
+        let (tx, _rx) = broadcast::channel::<()>(4);
+        let failure = SharedFailure::default();
+        let app = router_with_reload(tmp.path(), tx, failure.clone(), "");
+        let get = || {
+            app.clone().oneshot(
+                Request::builder()
+                    .uri("/index.html")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+        let body = |resp: Response<Body>| async move {
+            let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+            String::from_utf8(bytes.to_vec()).unwrap()
+        };
+
+        let error = BuildError::Page(PageError::InvalidDate {
+            path: PathBuf::from("content/post.md"),
+            date: "<soon>".to_string(),
+        });
+        *failure.write().unwrap() = Some(BuildFailure::new(&error));
+        let html = body(get().await.unwrap()).await;
+        assert!(html.contains(ERROR_OVERLAY_ID), "{html}");
+        assert!(html.contains("content/post.md"), "{html}");
+        assert!(html.contains("`&lt;soon&gt;`"), "{html}");
+        assert!(html.find(ERROR_OVERLAY_ID) < html.find("</body>"));
+
+        *failure.write().unwrap() = None;
+        let html = body(get().await.unwrap()).await;
+        assert!(!html.contains(ERROR_OVERLAY_ID), "{html}");
+        assert!(html.contains("/_sherwood/reload"));
     }
 
-    #[test]
-    fn snapshot_watched_detects_extra_path_change() {
-        // An --asset override source lives outside the content dir; editing
-        // it must change the snapshot or the watch loop would discard the
-        // event as spurious.
-        let tmp = TempDir::new().unwrap();
-        let content = tmp.path().join("content");
-        fs::create_dir_all(&content).unwrap();
-        let style = tmp.path().join("style.css");
-        fs::write(&style, "v1").unwrap();
-        let extra = vec![style.clone()];
-        let snap1 = snapshot_watched(&content, &extra);
-        std::thread::sleep(std::time::Duration::from_millis(50));
-        fs::write(&style, "v2 with more bytes").unwrap();
-        let snap2 = snapshot_watched(&content, &extra);
-        assert_ne!(snap1, snap2);
+    #[tokio::test]
+    async fn server_url_reports_os_assigned_port() {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        assert_ne!(addr.port(), 0);
+        assert_eq!(
+            server_url(addr, "/docs"),
+            format!("http://127.0.0.1:{}/docs/", addr.port())
+        );
     }
 
     #[test]
-    fn snapshot_mtimes_detects_added_file() {
-        let tmp = TempDir::new().unwrap();
-        fs::write(tmp.path().join("a.md"), "a").unwrap();
-        let snap1 = snapshot_mtimes(tmp.path());
-        fs::write(tmp.path().join("b.md"), "b").unwrap();
-        let snap2 = snapshot_mtimes(tmp.path());
-        assert_ne!(snap1, snap2);
+    fn server_urls_for_wildcard_and_lan_binds() {
+        let wildcard: SocketAddr = "0.0.0.0:4000".parse().unwrap();
+        assert_eq!(server_url(wildcard, ""), "http://127.0.0.1:4000/");
+        let loopback: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        assert_eq!(network_url(loopback, ""), None);
+        let lan: SocketAddr = "192.168.1.20:4000".parse().unwrap();
+        assert_eq!(
+            network_url(lan, "/docs").as_deref(),
+            Some("http://192.168.1.20:4000/docs/")
+        );
     }
 
     #[tokio::test]
@@ -448,7 +918,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         fs::write(tmp.path().join("style.css"), "body{}").unwrap();
         let (tx, _rx) = broadcast::channel::<()>(4);
-        let resp = router_with_reload(tmp.path(), tx, "")
+        let resp = router_with_reload(tmp.path(), tx, SharedFailure::default(), "")
             .oneshot(
                 Request::builder()
                     .uri("/style.css")
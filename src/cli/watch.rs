@@ -0,0 +1,202 @@
+//! File watching shared by `serve` (rebuild + live reload) and
+//! `build --watch` (rebuild only).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::log::Log;
+use crate::core::build::BuildError;
+use crate::core::date::format_system_time;
+
+/// Watch the `content_dirs` (and any extra `watch_paths`, e.g. `--asset`
+/// override source files) and rerun `rebuild` after each real change, passing
+/// each outcome to `on_rebuilt`, failures included. Runs until the watcher
+/// shuts down, which in practice means until the process is interrupted.
+pub(super) fn watch_loop<F, R>(
+    content_dirs: Vec<PathBuf>,
+    watch_paths: &[PathBuf],
+    log: Log,
+    mut rebuild: F,
+    mut on_rebuilt: R,
+) where
+    F: FnMut() -> Result<(), BuildError>,
+    R: FnMut(Result<(), &BuildError>),
+{
+    use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+
+    let (event_tx, event_rx) = std::sync::mpsc::channel();
+    let mut debouncer = match new_debouncer(Duration::from_millis(300), move |res| {
+        let _ = event_tx.send(res);
+    }) {
+        Ok(d) => d,
+        Err(e) => {
+            log.error("watch_error", format!("Failed to start file watcher: {e}"));
+            return;
+        }
+    };
+    for dir in &content_dirs {
+        if let Err(e) = debouncer.watcher().watch(dir, RecursiveMode::Recursive) {
+            log.error(
+                "watch_error",
+                format!("Failed to watch {}: {e}", dir.display()),
+            );
+            return;
+        }
+    }
+    for path in watch_paths {
+        if let Err(e) = debouncer.watcher().watch(path, RecursiveMode::NonRecursive) {
+            log.error(
+                "watch_error",
+                format!("Failed to watch {}: {e}", path.display()),
+            );
+        }
+    }
+
+    // Snapshot watched-file mtimes so we can ignore spurious events.
+    // Reading files during a rebuild updates `atime`, which fires `IN_ATTRIB`
+    // events on Linux even though the data hasn't changed — without this
+    // guard, every rebuild self-triggers another rebuild.
+    let mut snapshot = snapshot_watched(&content_dirs, watch_paths);
+
+    for res in event_rx {
+        match res {
+            Ok(events) if !events.is_empty() => {
+                let current = snapshot_watched(&content_dirs, watch_paths);
+                if current == snapshot {
+                    continue;
+                }
+                log.verbose("change", "Change detected — rebuilding...", &[]);
+                let started = Instant::now();
+                match rebuild() {
+                    Ok(()) => {
+                        let ms = started.elapsed().as_millis();
+                        log.info(
+                            "rebuild",
+                            format!("[{}] Rebuilt in {ms}ms.", clock_time(SystemTime::now())),
+                            &[("ms", ms.into())],
+                        );
+                        on_rebuilt(Ok(()));
+                    }
+                    Err(e) => {
+                        log.error("rebuild_failed", format!("Rebuild failed: {e}"));
+                        on_rebuilt(Err(&e));
+                    }
+                }
+                snapshot = snapshot_watched(&content_dirs, watch_paths);
+            }
+            Ok(_) => {}
+            Err(e) => log.error("watch_error", format!("Watcher error: {e}")),
+        }
+    }
+}
+
+/// `HH:MM:SSZ` (UTC) for log lines.
+fn clock_time(time: SystemTime) -> String {
+    let stamp = format_system_time(time);
+    stamp.get(11..).unwrap_or(&stamp).to_string()
+}
+
+/// Mtime snapshot of the content trees plus any extra watched files
+/// (`--asset` override sources), so changes to either defeat the
+/// spurious-event guard.
+fn snapshot_watched(
+    content_dirs: &[PathBuf],
+    watch_paths: &[PathBuf],
+) -> HashMap<PathBuf, SystemTime> {
+    let mut map = HashMap::new();
+    for dir in content_dirs {
+        map.extend(snapshot_mtimes(dir));
+    }
+    for path in watch_paths {
+        if let Ok(meta) = std::fs::metadata(path)
+            && let Ok(mtime) = meta.modified()
+        {
+            map.insert(path.clone(), mtime);
+        }
+    }
+    map
+}
+
+fn snapshot_mtimes(root: &Path) -> HashMap<PathBuf, SystemTime> {
+    use walkdir::WalkDir;
+    let mut map = HashMap::new();
+    for entry in WalkDir::new(root).into_iter().flatten() {
+        if entry.file_type().is_file()
+            && let Ok(meta) = entry.metadata()
+            && let Ok(mtime) = meta.modified()
+        {
+            map.insert(entry.path().to_owned(), mtime);
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn clock_time_is_utc_time_of_day() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(3_723);
+        assert_eq!(clock_time(time), "01:02:03Z");
+    }
+
+    #[test]
+    fn snapshot_mtimes_changes_when_content_changes() {
+        let tmp = TempDir::new().unwrap();
+        let f = tmp.path().join("page.md");
+        fs::write(&f, "v1").unwrap();
+        let snap1 = snapshot_mtimes(tmp.path());
+        // mtime has filesystem-dependent resolution; sleep to ensure tick.
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::write(&f, "v2 with more bytes").unwrap();
+        let snap2 = snapshot_mtimes(tmp.path());
+        assert_ne!(snap1, snap2, "rewriting a file must change snapshot");
+    }
+
+    #[test]
+    fn snapshot_mtimes_unchanged_when_file_only_read() {
+        // This is the load-bearing assertion for the live-reload watch loop:
+        // reading content files during a rebuild must not change the mtime
+        // snapshot, otherwise the loop would keep self-triggering.
+        let tmp = TempDir::new().unwrap();
+        let f = tmp.path().join("page.md");
+        fs::write(&f, "data").unwrap();
+        let snap1 = snapshot_mtimes(tmp.path());
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let _ = fs::read_to_string(&f).unwrap();
+        let snap2 = snapshot_mtimes(tmp.path());
+        assert_eq!(snap1, snap2, "reading a file must not change snapshot");
+    }
+
+    #[test]
+    fn snapshot_watched_detects_extra_path_change() {
+        // An --asset override source lives outside the content dir; editing
+        // it must change the snapshot or the watch loop would discard the
+        // event as spurious.
+        let tmp = TempDir::new().unwrap();
+        let content = tmp.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        let style = tmp.path().join("style.css");
+        fs::write(&style, "v1").unwrap();
+        let extra = vec![style.clone()];
+        let snap1 = snapshot_watched(std::slice::from_ref(&content), &extra);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::write(&style, "v2 with more bytes").unwrap();
+        let snap2 = snapshot_watched(&[content], &extra);
+        assert_ne!(snap1, snap2);
+    }
+
+    #[test]
+    fn snapshot_mtimes_detects_added_file() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("a.md"), "a").unwrap();
+        let snap1 = snapshot_mtimes(tmp.path());
+        fs::write(tmp.path().join("b.md"), "b").unwrap();
+        let snap2 = snapshot_mtimes(tmp.path());
+        assert_ne!(snap1, snap2);
+    }
+}
@@ -0,0 +1,284 @@
+//! Accessibility lint over rendered page content: the common WCAG smells an
+//! author can fix in their markdown, not a full validator.
+//!
+//! Checks each page's `content_html` (the template is the site author's
+//! concern and the same on every page) for images without alt text, links
+//! whose text says nothing about their target ("click here"), and headings
+//! that skip a level (`<h2>` straight to `<h4>`). Line numbers are found by
+//! searching the page's source for the offending text, so they're a best
+//! effort; an issue whose text can't be located has none.
+
+use crate::core::build::{BuildError, load_site};
+use crate::core::config::SiteConfig;
+use crate::core::content::images::tag_attr;
+use crate::core::content::page::Page;
+use crate::core::content::parser::ParserRegistry;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Link texts that don't describe where the link goes. Compared
+/// case-insensitively, ignoring trailing punctuation.
+const VAGUE_LINK_TEXT: &[&str] = &[
+    "click here",
+    "click",
+    "here",
+    "link",
+    "this link",
+    "this",
+    "more",
+    "read more",
+    "learn more",
+];
+
+/// What kind of problem an [`A11yIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum A11yIssueKind {
+    /// An `<img>` with no `alt` attribute, or an empty one.
+    MissingAlt,
+    /// A link whose text, read alone, doesn't say where it goes.
+    VagueLinkText,
+    /// A heading more than one level below the previous heading.
+    SkippedHeadingLevel,
+}
+
+/// One accessibility problem in one page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct A11yIssue {
+    /// The page's source file.
+    pub path: PathBuf,
+    /// 1-based line in the source, when the offending text was found there.
+    pub line: Option<usize>,
+    pub kind: A11yIssueKind,
+    pub message: String,
+}
+
+impl fmt::Display for A11yIssue {
+    /// `content/about.md:12: image without alt text (team.jpg)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}: {}", self.path.display(), self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Load the site and lint every page, without writing anything. Issues come
+/// in page order, then document order within a page.
+pub fn check_accessibility(
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+) -> Result<Vec<A11yIssue>, BuildError> {
+    let (pages, _assets) = load_site(config, registry)?;
+    Ok(pages.iter().flat_map(page_issues).collect())
+}
+
+fn page_issues(page: &Page) -> Vec<A11yIssue> {
+    // Generated pages (series landings) have no source to search.
+    let source = std::fs::read_to_string(&page.source_path).unwrap_or_default();
+    let issue = |kind, message: String, needle: &str| A11yIssue {
+        path: page.source_path.clone(),
+        line: line_of(&source, needle),
+        kind,
+        message,
+    };
+
+    let html = &page.content_html;
+    let mut issues = Vec::new();
+    let mut last_heading: Option<u8> = None;
+    let mut pos = 0;
+    while let Some(rel) = html[pos..].find('<') {
+        let start = pos + rel;
+        let Some(len) = html[start..].find('>') else {
+            break;
+        };
+        let tag = &html[start + 1..start + len];
+        pos = start + len + 1;
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "img" => {
+                if tag_attr(tag, "alt").is_none_or(|alt| alt.trim().is_empty()) {
+                    let src = tag_attr(tag, "src").unwrap_or_default();
+                    issues.push(issue(
+                        A11yIssueKind::MissingAlt,
+                        format!("image without alt text ({src})"),
+                        src,
+                    ));
+                }
+            }
+            "a" => {
+                let text = element_text(&html[pos..], "a");
+                let normalized = text
+                    .trim()
+                    .trim_end_matches(['.', '!', ':', '…'])
+                    .to_lowercase();
+                if VAGUE_LINK_TEXT.contains(&normalized.as_str()) {
+                    issues.push(issue(
+                        A11yIssueKind::VagueLinkText,
+                        format!("link text \"{}\" doesn't describe its target", text.trim()),
+                        text.trim(),
+                    ));
+                }
+            }
+            _ => {
+                let Some(level) = heading_level(&name) else {
+                    continue;
+                };
+                if let Some(prev) = last_heading
+                    && level > prev + 1
+                {
+                    let text = element_text(&html[pos..], &name);
+                    issues.push(issue(
+                        A11yIssueKind::SkippedHeadingLevel,
+                        format!(
+                            "heading level skips from h{prev} to h{level} (\"{}\")",
+                            text.trim()
+                        ),
+                        text.trim(),
+                    ));
+                }
+                last_heading = Some(level);
+            }
+        }
+    }
+    issues
+}
+
+/// `h1`–`h6` → 1–6.
+fn heading_level(name: &str) -> Option<u8> {
+    let level = name.strip_prefix('h')?.parse().ok()?;
+    (1..=6).contains(&level).then_some(level)
+}
+
+/// The text content of an element whose opening tag ends just before
+/// `html`, up to its closing tag, with nested tags removed and common
+/// entities decoded.
+fn element_text(html: &str, name: &str) -> String {
+    let inner = html
+        .find(&format!("</{name}"))
+        .map_or(html, |end| &html[..end]);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in inner.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// 1-based line of the first occurrence of `needle` in `source`.
+fn line_of(source: &str, needle: &str) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+    let at = source.find(needle)?;
+    Some(source[..at].matches('\n').count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::make_page;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn kinds(html: &str) -> Vec<(A11yIssueKind, String)> {
+        let mut page = make_page("a", "A");
+        page.content_html = html.to_string();
+        page_issues(&page)
+            .into_iter()
+            .map(|i| (i.kind, i.message))
+            .collect()
+    }
+
+    #[test]
+    fn flags_images_without_alt() {
+        let issues = kinds(
+            "<p><img src=\"a.png\" alt=\"\" /><img src='b.png'>\
+             <img src=\"c.png\" alt=\"A cat\" /></p>",
+        );
+        assert_eq!(
+            issues,
+            [
+                (
+                    A11yIssueKind::MissingAlt,
+                    "image without alt text (a.png)".to_string()
+                ),
+                (
+                    A11yIssueKind::MissingAlt,
+                    "image without alt text (b.png)".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_vague_link_text() {
+        let issues = kinds(
+            "<p><a href=\"/x/\">Click <em>here</em>!</a> \
+             <a href=\"/docs/\">the install guide</a></p>",
+        );
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].0, A11yIssueKind::VagueLinkText);
+        assert!(issues[0].1.contains("\"Click here!\""), "{issues:?}");
+    }
+
+    #[test]
+    fn flags_skipped_heading_levels() {
+        let issues =
+            kinds("<h1 id=\"a\">A</h1><h2>B</h2><h4 id=\"c\">C</h4><h2>D</h2><h3>E</h3><h5>F</h5>");
+        let messages: Vec<&str> = issues.iter().map(|(_, m)| m.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "heading level skips from h2 to h4 (\"C\")",
+                "heading level skips from h3 to h5 (\"F\")"
+            ]
+        );
+        // The first heading sets the baseline; content may start below h1.
+        assert!(kinds("<h3>Start</h3><h4>Next</h4>").is_empty());
+    }
+
+    #[test]
+    fn reports_source_lines() {
+        let tmp = TempDir::new().unwrap();
+        let content_dir = tmp.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::write(
+            content_dir.join("index.md"),
+            "---\ntitle: Home\n---\n\n# Home\n\n![](cat.png)\n\n[here](/about/)\n",
+        )
+        .unwrap();
+        let config = SiteConfig::new()
+            .with_content_dir(&content_dir)
+            .with_output_dir(tmp.path().join("_site"));
+
+        let issues = check_accessibility(&config, &ParserRegistry::default()).unwrap();
+        let lines: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+        let path = content_dir.join("index.md");
+        assert_eq!(
+            lines,
+            [
+                format!("{}:7: image without alt text (cat.png)", path.display()),
+                format!(
+                    "{}:9: link text \"here\" doesn't describe its target",
+                    path.display()
+                ),
+            ]
+        );
+        assert!(!config.output_dir.exists());
+    }
+}
@@ -1,9 +1,18 @@
 use crate::core::config::SiteConfig;
-use crate::core::content::page::{Page, PageError, load_page};
+use crate::core::content::ignore::{DRAFTS_DIR, IgnoreRules};
+use crate::core::content::images::{ImageFingerprints, is_image};
+use crate::core::content::page::{Page, PageError, is_published, load_page_with_defaults};
 use crate::core::content::parser::ParserRegistry;
-use crate::core::nav::{self, PageContext, is_root_index};
+use crate::core::content::section::{SECTION_FILE, SectionDefaults, SectionError};
+use crate::core::feed;
+use crate::core::fingerprint::fingerprinted_name;
+use crate::core::nav::{self, PageContext, is_root_index, series_landing_pages};
+use crate::core::redirect::{self, Redirect, RedirectError};
+use gray_matter::Pod;
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use walkdir::WalkDir;
 
@@ -11,10 +20,29 @@ use walkdir::WalkDir;
 pub enum BuildError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+    /// Reading a file from the content tree failed.
+    #[error("reading {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// Writing a page, copied file or directory under the output directory
+    /// failed.
+    #[error("writing {}: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
     #[error("Walk error: {0}")]
     Walk(#[from] walkdir::Error),
     #[error(transparent)]
     Page(#[from] PageError),
+    #[error(transparent)]
+    Redirect(#[from] RedirectError),
+    #[error(transparent)]
+    Section(#[from] SectionError),
     #[error("Render error: {0}")]
     Render(String),
     #[error("{} and {} both write {}", first.display(), second.display(), output.display())]
@@ -23,83 +51,493 @@ pub enum BuildError {
         second: PathBuf,
         output: PathBuf,
     },
+    /// A content directory doesn't exist, or isn't a directory.
+    #[error("content directory {} does not exist", .0.display())]
+    MissingContentDir(PathBuf),
+    /// The output directory is a content directory or lies inside one, so
+    /// each build would read back the last one's output as content.
+    #[error(
+        "output directory {} is inside content directory {}; choose an output directory outside it",
+        output.display(),
+        content.display()
+    )]
+    OutputInContent { output: PathBuf, content: PathBuf },
+}
+
+/// What a build wrote and where its time went, from
+/// [`build_site_with_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BuildReport {
+    /// Pages rendered, generated series landings included.
+    pub pages: usize,
+    /// How many of `pages` are section indexes (list pages).
+    pub section_pages: usize,
+    /// Static files copied from the content tree.
+    pub assets: usize,
+    /// Redirect stub pages written ([`SiteConfig::redirects`] and page
+    /// `aliases`).
+    pub redirects: usize,
+    /// The redirect rules behind those stubs, for servers that answer them
+    /// with real redirects.
+    pub redirect_rules: Vec<Redirect>,
+    /// Bytes written to the output directory: pages, copied files, feeds and
+    /// redirect stubs.
+    pub bytes_written: u64,
+    /// Every file written, with its size in bytes, in write order. With
+    /// [`SiteConfig::dry_run`], the files that would have been.
+    pub outputs: Vec<(PathBuf, u64)>,
+    /// Walking the content tree and parsing every page.
+    pub parse_time: Duration,
+    /// Building each page's context, rendering and writing it, plus feeds.
+    pub render_time: Duration,
+    /// The whole build, copying assets included.
+    pub elapsed: Duration,
+}
+
+impl BuildReport {
+    fn record(&mut self, path: &Path, bytes: u64) {
+        self.bytes_written += bytes;
+        self.outputs.push((path.to_owned(), bytes));
+    }
+}
+
+/// A parsed content tree, from [`load_content`]: every page ready to render
+/// and every static file to copy. Inspect or rewrite it — index it, drop
+/// pages, edit their HTML — then hand it to [`build_content`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct SiteContent {
+    /// Pages in build order: the root index first, then by output path.
+    /// Generated series landing pages are included.
+    pub pages: Vec<Page>,
+    /// Files no parser claims, as `(source, destination)` pairs, in
+    /// content-tree order.
+    pub assets: Vec<(PathBuf, PathBuf)>,
 }
 
 pub fn build_site<F, P>(
     config: &SiteConfig,
     registry: &ParserRegistry,
+    renderer: F,
+    progress: P,
+) -> Result<(), BuildError>
+where
+    F: FnMut(&Page, &PageContext) -> Result<String, BuildError>,
+    P: FnMut(&Page),
+{
+    build_site_with_report(config, registry, renderer, progress).map(drop)
+}
+
+/// [`build_site`], returning a [`BuildReport`] of page counts, bytes written
+/// and per-phase timings. Files written outside the build (the CLI's
+/// stylesheet, say) aren't counted.
+pub fn build_site_with_report<F, P>(
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+    renderer: F,
+    progress: P,
+) -> Result<BuildReport, BuildError>
+where
+    F: FnMut(&Page, &PageContext) -> Result<String, BuildError>,
+    P: FnMut(&Page),
+{
+    let started = Instant::now();
+    let content = load_content(config, registry)?;
+    let parse_time = started.elapsed();
+    let mut report = build_content(config, content, renderer, progress)?;
+    report.parse_time += parse_time;
+    report.elapsed = started.elapsed();
+    Ok(report)
+}
+
+/// Walk and parse the content tree without writing anything: the first half
+/// of [`build_site`], for pipelines that index, analyse or transform pages
+/// before rendering (or never render at all).
+pub fn load_content(
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+) -> Result<SiteContent, BuildError> {
+    let (pages, assets) = load_site(config, registry)?;
+    Ok(SiteContent { pages, assets })
+}
+
+/// Render and write already-loaded content: the second half of
+/// [`build_site`]. Redirects (configured rules and page `aliases`), feeds
+/// and copied files all come from `content` as given, so pages added,
+/// removed or edited since [`load_content`] are built as they are now.
+/// Two pages or files sharing an output path fail with
+/// [`BuildError::DuplicateOutput`] before anything is written.
+pub fn build_content<F, P>(
+    config: &SiteConfig,
+    content: SiteContent,
     mut renderer: F,
     mut progress: P,
-) -> Result<(), BuildError>
+) -> Result<BuildReport, BuildError>
 where
     F: FnMut(&Page, &PageContext) -> Result<String, BuildError>,
     P: FnMut(&Page),
 {
-    std::fs::create_dir_all(&config.output_dir)?;
+    let started = Instant::now();
+    let mut report = BuildReport::default();
+    let SiteContent { mut pages, assets } = content;
+    let fingerprinted = check_outputs(config, &pages, &assets)?;
+    let redirects = redirect::site_redirects(config, &mut pages, &assets);
+    check_redirects(config, &redirects, &pages, &assets)?;
+    report.parse_time = started.elapsed();
 
+    if !config.dry_run {
+        create_dir(&config.output_dir)?;
+    }
+    for ((source, dest), hashed) in assets.iter().zip(&fingerprinted) {
+        report.record(dest, copy_asset(source, dest, config)?);
+        if let Some(hashed) = hashed {
+            report.record(hashed, copy_asset(source, hashed, config)?);
+        }
+    }
+    report.assets = assets.len();
+
+    let render_started = Instant::now();
+    for page in &pages {
+        let ctx = nav::compute_context(page, &pages, config);
+        let html = renderer(page, &ctx)?;
+        if !config.dry_run {
+            write_page(&page.output_path, &html)?;
+        }
+        report.record(&page.output_path, html.len() as u64);
+        report.pages += 1;
+        report.section_pages += usize::from(page.is_section_index);
+        progress(page);
+    }
+
+    for (feed, bytes) in feed::write_feeds(&pages, config)? {
+        report.record(&feed, bytes);
+    }
+    for (stub, bytes) in redirect::write_stubs(&redirects, config)? {
+        report.record(&stub, bytes);
+        report.redirects += 1;
+    }
+    report.redirect_rules = redirects;
+    report.render_time = render_started.elapsed();
+    report.elapsed = started.elapsed();
+
+    Ok(report)
+}
+
+/// Regenerate only the feeds: every page is parsed for its metadata, but no
+/// HTML is rendered and nothing is copied. For deploy hooks that refresh
+/// feeds on a large site without a full rebuild. Returns the feed files
+/// written — none when [`SiteConfig::feed`] selects no format (and, with
+/// [`SiteConfig::dry_run`], the files that would have been).
+pub fn build_feeds(
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+) -> Result<Vec<PathBuf>, BuildError> {
+    let (pages, _) = load_site(config, registry)?;
+    let written = feed::write_feeds(&pages, config)?;
+    Ok(written.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Pages and `(source, destination)` asset pairs, as [`load_site`] returns
+/// them.
+type LoadedSite = (Vec<Page>, Vec<(PathBuf, PathBuf)>);
+
+/// Walk the content tree (and any extra content roots, merged in): load
+/// every page, and pair each file no parser
+/// claims (image, CSS, …) with its mirrored output path. Pages come back in
+/// nav order — root index first, then by output path — which keeps the
+/// homepage at the front of the nav rather than buried after alphabetical
+/// siblings like "about.html".
+pub(crate) fn load_site(
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+) -> Result<LoadedSite, BuildError> {
+    check_dirs(config)?;
     let mut pages: Vec<Page> = Vec::new();
+    let mut assets: Vec<(PathBuf, PathBuf)> = Vec::new();
     // output path -> source path, so two sources mapping to the same output
-    // file (e.g. content/about.md and content/about/index.md) fail loudly
-    // instead of one silently overwriting the other.
+    // file (e.g. content/about.md and content/about/index.md, or the same
+    // page in two content roots) fail loudly instead of one silently
+    // overwriting the other.
     let mut claimed: HashMap<PathBuf, PathBuf> = HashMap::new();
-    for entry in WalkDir::new(&config.content_dir) {
-        let entry = entry?;
-        if !entry.file_type().is_file() {
-            continue;
+    let mut sections = SectionDefaults::default();
+    // (content root, file, its `_section.toml` defaults), in walk order.
+    let mut files: Vec<(&Path, PathBuf, HashMap<String, Pod>)> = Vec::new();
+    for root in config.content_roots() {
+        // Each root reads its own `.sherwoodignore`. Ignored directories are
+        // pruned, not just filtered, so nothing beneath them (node_modules,
+        // drafts) is ever walked. Entries come sorted by name, so asset
+        // order and output conflicts don't depend on the filesystem.
+        let ignore = IgnoreRules::load(root)?;
+        let walker = WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                let is_dir = entry.file_type().is_dir();
+                if is_dir && !config.drafts && entry.depth() > 0 && entry.file_name() == DRAFTS_DIR
+                {
+                    return false;
+                }
+                let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+                !ignore.is_ignored(relative, is_dir)
+            });
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_file() || entry.file_name() == SECTION_FILE {
+                continue;
+            }
+            let defaults = sections.for_page(root, entry.path())?;
+            files.push((root, entry.into_path(), defaults));
         }
-        match load_page(entry.path(), config, registry)? {
+    }
+
+    // Loading runs in parallel; outputs are claimed in walk order, so
+    // conflicts and errors don't depend on which thread finished first.
+    for ((root, path, _), loaded) in files.iter().zip(load_pages(&files, config, registry)) {
+        match loaded? {
+            // Unpublished: neither written nor listed, and not copied either.
+            Some(page) if !config.unpublished && !is_published(&page) => {}
             Some(page) => {
                 claim_output(&mut claimed, &page.output_path, &page.source_path)?;
                 pages.push(page);
             }
-            // No parser claims the extension: a static asset (image, CSS, …)
-            // living in the content tree. Copy it verbatim to the mirrored
-            // output path.
+            // A static asset living in the content tree, copied verbatim
+            // to the mirrored output path.
             None => {
-                let relative = entry
-                    .path()
-                    .strip_prefix(&config.content_dir)
-                    .unwrap_or(entry.path());
+                let relative = path.strip_prefix(root).unwrap_or(path);
                 let dest = config.output_dir.join(relative);
-                claim_output(&mut claimed, &dest, entry.path())?;
-                copy_asset(entry.path(), &dest)?;
+                claim_output(&mut claimed, &dest, path)?;
+                assets.push((path.clone(), dest));
             }
         }
     }
 
-    // Root index first, then remaining pages by output path. This keeps the
-    // homepage at the front of the nav rather than buried after alphabetical
-    // siblings like "about.html".
+    // Each series gets a generated landing page unless an authored page (or
+    // asset) already sits at its URL.
+    for landing in series_landing_pages(&pages, config) {
+        if !claimed.contains_key(&landing.output_path) {
+            pages.push(landing);
+        }
+    }
+
     pages.sort_by(|a, b| {
         let ka = (!is_root_index(a, config), a.output_path.clone());
         let kb = (!is_root_index(b, config), b.output_path.clone());
         ka.cmp(&kb)
     });
+    Ok((pages, assets))
+}
 
-    for page in &pages {
-        let ctx = nav::compute_context(page, &pages, config);
-        let html = renderer(page, &ctx)?;
-        write_page(&page.output_path, &html)?;
-        progress(page);
+/// Fail early, and by name, on a content directory that isn't there or an
+/// output directory that would end up inside the content it's built from.
+fn check_dirs(config: &SiteConfig) -> Result<(), BuildError> {
+    let output = resolved(&config.output_dir);
+    for root in config.content_roots() {
+        if !root.is_dir() {
+            return Err(BuildError::MissingContentDir(root.to_owned()));
+        }
+        if output.starts_with(resolved(root)) {
+            return Err(BuildError::OutputInContent {
+                output: config.output_dir.clone(),
+                content: root.to_owned(),
+            });
+        }
     }
+    Ok(())
+}
 
+/// `path` made absolute, with `.` and `..` folded away and symlinks resolved
+/// as far as it exists, so two spellings of one directory compare equal even
+/// before the output directory is created.
+fn resolved(path: &Path) -> PathBuf {
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_owned();
+    };
+    // Lexically, since `out/../content` names `content` even while `out`
+    // doesn't exist yet.
+    let mut normal = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normal.pop();
+            }
+            other => normal.push(other),
+        }
+    }
+    let mut existing = normal.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(real) = existing.canonicalize() {
+            return missing.iter().rev().fold(real, |dir, name| dir.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => return normal,
+        }
+    }
+}
+
+/// Load every file on up to [`SiteConfig::concurrency`] threads, returning
+/// the results in `files` order.
+fn load_pages(
+    files: &[(&Path, PathBuf, HashMap<String, Pod>)],
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+) -> Vec<Result<Option<Page>, PageError>> {
+    let load = |(_, path, defaults): &(&Path, PathBuf, HashMap<String, Pod>)| {
+        load_page_with_defaults(path, config, registry, defaults)
+    };
+    let workers = config.concurrency().min(files.len());
+    if workers <= 1 {
+        return files.iter().map(load).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut loaded: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(file) = files.get(i) else {
+                            return done;
+                        };
+                        done.push((i, load(file)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    loaded.sort_by_key(|(i, _)| *i);
+    loaded.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Fail on two pages or files writing the same output path before anything
+/// is written. [`load_site`] already refuses such a tree; this catches pages
+/// an embedder added or moved between [`load_content`] and
+/// [`build_content`]. Fingerprinted image copies claim their hashed paths
+/// too, which are returned in `assets` order (`None` for files without one).
+fn check_outputs(
+    config: &SiteConfig,
+    pages: &[Page],
+    assets: &[(PathBuf, PathBuf)],
+) -> Result<Vec<Option<PathBuf>>, BuildError> {
+    let mut claimed = HashMap::new();
+    for page in pages {
+        claim_output(&mut claimed, &page.output_path, &page.source_path)?;
+    }
+    for (source, dest) in assets {
+        claim_output(&mut claimed, dest, source)?;
+    }
+    let mut fingerprinted = Vec::with_capacity(assets.len());
+    for (source, dest) in assets {
+        let hashed = fingerprinted_path(source, dest, config)?;
+        if let Some(hashed) = &hashed {
+            claim_output(&mut claimed, hashed, source)?;
+        }
+        fingerprinted.push(hashed);
+    }
+    Ok(fingerprinted)
+}
+
+/// Validate the redirect rules, and make sure no stub would replace a page
+/// or file the content tree writes.
+fn check_redirects(
+    config: &SiteConfig,
+    redirects: &[Redirect],
+    pages: &[Page],
+    assets: &[(PathBuf, PathBuf)],
+) -> Result<(), BuildError> {
+    redirect::validate(redirects)?;
+    for r in redirects {
+        let stub = redirect::stub_path(&r.from, config);
+        let existing = pages
+            .iter()
+            .find(|p| p.output_path == stub)
+            .map(|p| &p.source_path)
+            .or_else(|| {
+                assets
+                    .iter()
+                    .find(|(_, dest)| *dest == stub)
+                    .map(|(src, _)| src)
+            });
+        if let Some(existing) = existing {
+            return Err(RedirectError::Conflict {
+                from: r.from.clone(),
+                existing: existing.clone(),
+            }
+            .into());
+        }
+    }
     Ok(())
 }
 
 fn write_page(output_path: &Path, html: &str) -> Result<(), BuildError> {
     if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent)?;
+        create_dir(parent)?;
     }
-    std::fs::write(output_path, html)?;
-    Ok(())
+    std::fs::write(output_path, html).map_err(|source| write_error(output_path, source))
 }
 
-fn copy_asset(source: &Path, dest: &Path) -> Result<(), BuildError> {
+/// Copy a content-tree file to `dest`, returning the bytes copied (only
+/// measured in a dry run).
+fn copy_asset(source: &Path, dest: &Path, config: &SiteConfig) -> Result<u64, BuildError> {
+    if config.dry_run {
+        return std::fs::metadata(source)
+            .map(|meta| meta.len())
+            .map_err(|e| read_error(source, e));
+    }
     if let Some(parent) = dest.parent() {
-        std::fs::create_dir_all(parent)?;
+        create_dir(parent)?;
+    }
+    std::fs::copy(source, dest).map_err(|e| write_error(dest, e))
+}
+
+fn create_dir(dir: &Path) -> Result<(), BuildError> {
+    std::fs::create_dir_all(dir).map_err(|source| write_error(dir, source))
+}
+
+fn write_error(path: &Path, source: std::io::Error) -> BuildError {
+    BuildError::Write {
+        path: path.to_owned(),
+        source,
     }
-    std::fs::copy(source, dest)?;
-    Ok(())
+}
+
+fn read_error(path: &Path, source: std::io::Error) -> BuildError {
+    BuildError::Read {
+        path: path.to_owned(),
+        source,
+    }
+}
+
+/// With [`SiteConfig::fingerprint_images`] on, where an image is also
+/// written under its fingerprinted name: `cat.<hash>.png` next to `dest`.
+fn fingerprinted_path(
+    source: &Path,
+    dest: &Path,
+    config: &SiteConfig,
+) -> Result<Option<PathBuf>, BuildError> {
+    if config.fingerprint_images == ImageFingerprints::Off || !is_image(source) {
+        return Ok(None);
+    }
+    let Some(name) = dest.file_name().and_then(|name| name.to_str()) else {
+        return Ok(None);
+    };
+    let bytes = std::fs::read(source).map_err(|e| read_error(source, e))?;
+    Ok(Some(dest.with_file_name(fingerprinted_name(name, &bytes))))
 }
 
 fn claim_output(
@@ -120,6 +558,9 @@ fn claim_output(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::content::frontmatter::FrontMatter;
+    use crate::core::content::url_strategy::UrlStrategy;
+    use crate::core::feed::{FeedConfig, FeedFormat};
     use std::fs;
     use tempfile::TempDir;
 
@@ -165,6 +606,434 @@ mod tests {
         assert!(config.output_dir.join("about/index.html").exists());
     }
 
+    #[test]
+    fn build_writes_not_found_page_at_the_root() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("404.md", "---\ntitle: Not Found\n---\n\nNothing here."),
+        ]);
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |page, _ctx| Ok(page.frontmatter.title.clone()),
+            |_| {},
+        )
+        .unwrap();
+        let html = fs::read_to_string(config.output_dir.join("404.html")).unwrap();
+        assert_eq!(html, "Not Found");
+        assert!(!config.output_dir.join("404").exists());
+    }
+
+    #[test]
+    fn build_writes_redirect_stubs_and_rejects_conflicts() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("about.md", "---\ntitle: About\n---\n"),
+        ]);
+        let config = config.with_redirects([Redirect::new("/old-about", "/about/")]);
+        let report = build_site_with_report(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(report.redirects, 1);
+        assert_eq!(report.redirect_rules, config.redirects);
+        let stub = fs::read_to_string(config.output_dir.join("old-about/index.html")).unwrap();
+        assert!(stub.contains("url=/about/"), "{stub}");
+
+        let config = config.with_redirects([Redirect::new("/about/", "/")]);
+        let err = build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, BuildError::Redirect(RedirectError::Conflict { .. })),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn build_writes_fingerprinted_images() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n![Cat](cat.png)\n"),
+            ("cat.png", "meow"),
+            ("notes.txt", "x"),
+        ]);
+        let config = config.with_fingerprint_images(ImageFingerprints::Relative);
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |p, _c| Ok(p.content_html.clone()),
+            |_| {},
+        )
+        .unwrap();
+        let hashed = fingerprinted_name("cat.png", b"meow");
+        let html = fs::read_to_string(config.output_dir.join("index.html")).unwrap();
+        assert!(html.contains(&format!("src=\"{hashed}\"")), "{html}");
+        assert_eq!(fs::read(config.output_dir.join(&hashed)).unwrap(), b"meow");
+        // The original stays for references the build didn't rewrite.
+        assert!(config.output_dir.join("cat.png").exists());
+        assert_eq!(fs::read_dir(&config.output_dir).unwrap().count(), 4);
+    }
+
+    #[test]
+    fn fingerprinted_name_clashing_with_a_file_fails() {
+        let hashed = fingerprinted_name("cat.png", b"meow");
+        let (_tmp, config) = setup(&[("cat.png", "meow"), (&hashed, "other")]);
+        let config = config.with_fingerprint_images(ImageFingerprints::Relative);
+        let err = build_site(
+            &config,
+            &ParserRegistry::default(),
+            |p, _c| Ok(p.content_html.clone()),
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, BuildError::DuplicateOutput { output, .. } if output.ends_with(&hashed)),
+            "{err}"
+        );
+        assert!(!config.output_dir.join(&hashed).exists());
+    }
+
+    #[test]
+    fn dry_run_names_an_unreadable_file() {
+        let (_tmp, config) = setup(&[("index.md", "---\ntitle: Home\n---\n")]);
+        let config = config.with_dry_run(true);
+        let mut content = load_content(&config, &ParserRegistry::default()).unwrap();
+        let missing = config.content_dir.join("gone.txt");
+        content
+            .assets
+            .push((missing.clone(), config.output_dir.join("gone.txt")));
+        let err = build_content(&config, content, |p, _c| Ok(p.content_html.clone()), |_| {})
+            .unwrap_err();
+        assert!(
+            matches!(&err, BuildError::Read { path, .. } if *path == missing),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn unreadable_image_names_its_path() {
+        let (_tmp, config) = setup(&[("index.md", "---\ntitle: Home\n---\n")]);
+        let config = config.with_fingerprint_images(ImageFingerprints::Relative);
+        let mut content = load_content(&config, &ParserRegistry::default()).unwrap();
+        let missing = config.content_dir.join("gone.png");
+        content
+            .assets
+            .push((missing.clone(), config.output_dir.join("gone.png")));
+        let err = build_content(&config, content, |p, _c| Ok(p.content_html.clone()), |_| {})
+            .unwrap_err();
+        assert!(
+            matches!(&err, BuildError::Read { path, .. } if *path == missing),
+            "{err}"
+        );
+        assert!(err.to_string().contains("gone.png"), "{err}");
+    }
+
+    #[test]
+    fn build_writes_stubs_for_page_aliases() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            (
+                "blog/post.md",
+                "---\ntitle: Post\naliases: [/old-post/]\nredirect_from: /\n---\n",
+            ),
+        ]);
+        let mut warnings = Vec::new();
+        let report = build_site_with_report(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            |p| warnings.extend(p.warnings.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            report.redirect_rules,
+            [Redirect::new("/old-post/", "/blog/post/")]
+        );
+        let stub = fs::read_to_string(config.output_dir.join("old-post/index.html")).unwrap();
+        assert!(stub.contains("url=/blog/post/"), "{stub}");
+        // `/` is the home page, so that alias is skipped with a warning.
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+    }
+
+    #[test]
+    fn build_report_counts_pages_and_bytes() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("blog/index.md", "---\ntitle: Blog\n---\n"),
+            ("blog/post.md", "---\ntitle: Post\n---\n"),
+            ("logo.svg", "<svg/>"),
+        ]);
+        let report = build_site_with_report(
+            &config,
+            &ParserRegistry::default(),
+            |page, _ctx| Ok(page.frontmatter.title.clone()),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(report.pages, 3);
+        assert_eq!(report.section_pages, 2);
+        assert_eq!(report.assets, 1);
+        // "Home" + "Blog" + "Post" + "<svg/>".
+        assert_eq!(report.bytes_written, 18);
+        assert!(report.elapsed >= report.parse_time + report.render_time);
+    }
+
+    #[test]
+    fn dry_run_reports_outputs_without_writing() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("blog/post.md", "---\ntitle: Post\ndate: 2026-05-31\n---\n"),
+            ("logo.svg", "<svg/>"),
+        ]);
+        let config = config
+            .with_feed(FeedConfig::new().with_formats([FeedFormat::Rss]))
+            .with_redirects([Redirect::new("/old/", "/blog/post/")]);
+        let build = |config: &SiteConfig| {
+            build_site_with_report(
+                config,
+                &ParserRegistry::default(),
+                |page, _ctx| Ok(page.frontmatter.title.clone()),
+                |_| {},
+            )
+            .unwrap()
+        };
+        let dry = build(&config.clone().with_dry_run(true));
+        assert!(!config.output_dir.exists());
+        let real = build(&config);
+        assert_eq!(dry.outputs, real.outputs);
+        assert_eq!(dry.bytes_written, real.bytes_written);
+        for (path, bytes) in &real.outputs {
+            assert_eq!(fs::metadata(path).unwrap().len(), *bytes, "{path:?}");
+        }
+        assert_eq!(real.outputs.len(), 5);
+    }
+
+    #[test]
+    fn build_skips_sherwoodignore_matches() {
+        let (_tmp, config) = setup(&[
+            (".sherwoodignore", "drafts/\n*.tmp\n!keep.tmp\n"),
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("drafts/wip.md", "---\ntitle: WIP\n---\n"),
+            ("notes.tmp", "scratch"),
+            ("keep.tmp", "kept"),
+        ]);
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _c| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap();
+        assert!(config.output_dir.join("index.html").exists());
+        assert!(!config.output_dir.join("drafts").exists());
+        assert!(!config.output_dir.join("notes.tmp").exists());
+        assert!(config.output_dir.join("keep.tmp").exists());
+        assert!(!config.output_dir.join(".sherwoodignore").exists());
+    }
+
+    #[test]
+    fn build_leaves_out_drafts_unless_asked() {
+        let (_tmp, config) = setup(&[
+            (".sherwoodignore", "*.tmp\n"),
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("_drafts/idea.md", "---\ntitle: Idea\n---\n"),
+            ("blog/_drafts/post.md", "---\ntitle: Post\n---\n"),
+            ("blog/_drafts/notes.tmp", "scratch"),
+        ]);
+        let registry = ParserRegistry::default();
+        let (pages, _) = load_site(&config, &registry).unwrap();
+        assert_eq!(pages.len(), 1);
+
+        let config = config.with_drafts(true);
+        let (pages, assets) = load_site(&config, &registry).unwrap();
+        let urls: Vec<&str> = pages.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["/", "/_drafts/idea/", "/blog/_drafts/post/"]);
+        assert!(assets.is_empty(), "{assets:?}");
+    }
+
+    #[test]
+    fn build_leaves_out_unpublished_pages_unless_asked() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\npublished: true\n---\n"),
+            ("old.md", "---\ntitle: Old\npublished: false\n---\n"),
+            (
+                "_drafts/gone.md",
+                "---\ntitle: Gone\npublished: false\n---\n",
+            ),
+        ]);
+        let registry = ParserRegistry::default();
+        // Including drafts doesn't bring back an unpublished one.
+        let config = config.with_drafts(true);
+        let (pages, assets) = load_site(&config, &registry).unwrap();
+        let urls: Vec<&str> = pages.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["/"]);
+        assert!(assets.is_empty(), "{assets:?}");
+
+        let (pages, _) = load_site(&config.with_unpublished(true), &registry).unwrap();
+        let urls: Vec<&str> = pages.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["/", "/_drafts/gone/", "/old/"]);
+    }
+
+    #[test]
+    fn section_files_set_frontmatter_defaults() {
+        let (_tmp, config) = setup(&[
+            (
+                "docs/_section.toml",
+                "title = \"Docs\"\ntoc = true\nsort_by = \"title\"\n",
+            ),
+            ("docs/index.md", "---\ntitle: Docs\n---\n"),
+            (
+                "docs/setup.md",
+                "---\ntitle: Setup\ntoc: false\n---\n## Step\n",
+            ),
+            ("docs/api.md", "---\ntitle: API\n---\n## Calls\n"),
+        ]);
+        let (pages, assets) = load_site(&config, &ParserRegistry::default()).unwrap();
+        assert!(assets.is_empty(), "{assets:?}");
+        let page = |url: &str| pages.iter().find(|p| p.url == url).unwrap();
+        assert!(page("/docs/api/").toc_html.is_some());
+        assert!(page("/docs/setup/").toc_html.is_none());
+        assert_eq!(
+            page("/docs/").frontmatter.get_string("sort_by").as_deref(),
+            Some("title")
+        );
+        assert_eq!(page("/docs/api/").frontmatter.title, "API");
+        assert_eq!(
+            page("/docs/api/")
+                .frontmatter
+                .get_string("section_title")
+                .as_deref(),
+            Some("Docs")
+        );
+    }
+
+    #[test]
+    fn parallel_loading_matches_a_single_thread() {
+        let files: Vec<(String, String)> = (0..20)
+            .map(|n| {
+                (
+                    format!("posts/p{n:02}.md"),
+                    format!("---\ntitle: Post {n}\n---\n\nBody {n}."),
+                )
+            })
+            .collect();
+        let refs: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, body)| (path.as_str(), body.as_str()))
+            .collect();
+        let (_tmp, config) = setup(&refs);
+        let urls = |config: &SiteConfig| {
+            let (pages, _) = load_site(config, &ParserRegistry::default()).unwrap();
+            pages.into_iter().map(|p| p.url).collect::<Vec<_>>()
+        };
+        let serial = urls(&config.clone().with_max_concurrency(1));
+        assert_eq!(serial.len(), 20);
+        assert_eq!(urls(&config.with_max_concurrency(4)), serial);
+    }
+
+    #[test]
+    fn load_site_lists_assets_in_name_order() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("c.txt", "c"),
+            ("a.txt", "a"),
+            ("img/b.png", "b"),
+            ("b.txt", "b"),
+        ]);
+        let (_, assets) = load_site(&config, &ParserRegistry::default()).unwrap();
+        let names: Vec<_> = assets
+            .iter()
+            .map(|(src, _)| src.strip_prefix(&config.content_dir).unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                Path::new("a.txt"),
+                Path::new("b.txt"),
+                Path::new("c.txt"),
+                Path::new("img/b.png"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_content_dirs_merge_and_conflicts_fail() {
+        let (tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("about.md", "---\ntitle: About\n---\n"),
+        ]);
+        let docs = tmp.path().join("docs");
+        fs::create_dir_all(docs.join("guide")).unwrap();
+        fs::write(docs.join("guide/setup.md"), "---\ntitle: Setup\n---\n").unwrap();
+        fs::write(docs.join("logo.png"), "png").unwrap();
+        let config = config.with_extra_content_dirs([&docs]);
+        let (pages, assets) = load_site(&config, &ParserRegistry::default()).unwrap();
+        let urls: Vec<_> = pages.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["/", "/about/", "/guide/setup/"]);
+        assert!(!pages[2].is_section_index);
+        assert_eq!(
+            assets,
+            [(docs.join("logo.png"), config.output_dir.join("logo.png"))]
+        );
+
+        fs::write(docs.join("about.md"), "---\ntitle: Other\n---\n").unwrap();
+        let err = load_site(&config, &ParserRegistry::default()).unwrap_err();
+        assert!(
+            matches!(&err, BuildError::DuplicateOutput { second, .. } if *second == docs.join("about.md")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn builds_of_the_same_tree_are_identical() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("zeta.md", "---\ntitle: Zeta\n---\n"),
+            ("blog/index.md", "---\ntitle: Blog\n---\n"),
+            ("blog/b.md", "---\ntitle: B\ndate: 2026-05-31\n---\n"),
+            ("blog/a.md", "---\ntitle: A\ndate: 2026-05-31\n---\n"),
+            ("about.md", "---\ntitle: About\n---\n"),
+            ("img/x.png", "x"),
+        ]);
+        let run = || {
+            let _ = fs::remove_dir_all(&config.output_dir);
+            let mut written = Vec::new();
+            build_site(
+                &config,
+                &ParserRegistry::default(),
+                |page, ctx| {
+                    let nav: Vec<_> = ctx.nav.iter().map(|n| n.title.as_str()).collect();
+                    let next = ctx.next.as_ref().map(|n| n.title.as_str());
+                    Ok(format!("{} {nav:?} {next:?}", page.frontmatter.title))
+                },
+                |page| written.push(page.output_path.clone()),
+            )
+            .unwrap();
+            let files: Vec<_> = WalkDir::new(&config.output_dir)
+                .sort_by_file_name()
+                .into_iter()
+                .map(|e| e.unwrap().into_path())
+                .filter(|p| p.is_file())
+                .map(|p| {
+                    let body = fs::read(&p).unwrap();
+                    (p, body)
+                })
+                .collect();
+            (written, files)
+        };
+        let first = run();
+        assert_eq!(first.0.len(), 6);
+        assert_eq!(first.0[0], config.output_dir.join("index.html"));
+        assert_eq!(run(), first);
+    }
+
     #[test]
     fn build_renderer_receives_all_pages_in_nav() {
         let (_tmp, config) = setup(&[
@@ -217,14 +1086,57 @@ mod tests {
             output_dir: tmp.path().join("_site"),
             ..SiteConfig::default()
         };
+        let err = build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_page, _ctx| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap_err();
         assert!(
-            build_site(
-                &config,
-                &ParserRegistry::default(),
-                |_page, _ctx| Ok(String::new()),
-                |_| {}
-            )
-            .is_err()
+            matches!(&err, BuildError::MissingContentDir(dir) if *dir == config.content_dir),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn output_inside_content_is_refused() {
+        let (_tmp, config) = setup(&[("index.md", "---\ntitle: Home\n---\n")]);
+        let registry = ParserRegistry::default();
+        for output in [
+            config.content_dir.clone(),
+            config.content_dir.join("_site"),
+            // Another spelling of a directory inside the content.
+            config.output_dir.join("../content/out"),
+        ] {
+            let config = config.clone().with_output_dir(&output);
+            let err = load_site(&config, &registry).unwrap_err();
+            assert!(
+                matches!(&err, BuildError::OutputInContent { output: o, .. } if *o == output),
+                "{err}"
+            );
+        }
+        // A sibling whose name merely starts with the content directory's.
+        let sibling = config.content_dir.with_file_name("content-out");
+        assert!(load_site(&config.with_output_dir(sibling), &registry).is_ok());
+    }
+
+    #[test]
+    fn build_write_errors_name_the_output_path() {
+        let (_tmp, config) = setup(&[("about.md", "---\ntitle: About\n---\n")]);
+        // A file where the page's directory should go.
+        fs::create_dir_all(&config.output_dir).unwrap();
+        fs::write(config.output_dir.join("about"), "").unwrap();
+        let err = build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _ctx| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, BuildError::Write { path, .. } if *path == config.output_dir.join("about")),
+            "{err}"
         );
     }
 
@@ -301,6 +1213,24 @@ mod tests {
         assert!(msg.contains("index.html"), "{msg}");
     }
 
+    #[test]
+    fn build_content_rejects_added_pages_that_collide() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("about.md", "---\ntitle: About\n---\n"),
+        ]);
+        let mut content = load_content(&config, &ParserRegistry::default()).unwrap();
+        let mut copy = content.pages[1].clone();
+        copy.source_path = config.content_dir.join("generated.md");
+        content.pages.push(copy);
+        let err = build_content(&config, content, |_p, _c| Ok(String::new()), |_| {}).unwrap_err();
+        assert!(
+            matches!(&err, BuildError::DuplicateOutput { second, .. } if second.ends_with("generated.md")),
+            "{err}"
+        );
+        assert!(!config.output_dir.exists());
+    }
+
     #[test]
     fn build_asset_colliding_with_page_output_errors() {
         // A static about/index.html would be overwritten by the page rendered
@@ -319,6 +1249,66 @@ mod tests {
         assert!(matches!(err, BuildError::DuplicateOutput { .. }), "{err}");
     }
 
+    #[test]
+    fn build_writes_configured_feeds() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("blog/post.md", "---\ntitle: Post\ndate: 2026-05-31\n---\n"),
+        ]);
+        let config = config.with_site_url("https://example.com").with_feed(
+            FeedConfig::new().with_formats([FeedFormat::Atom, FeedFormat::Rss, FeedFormat::Json]),
+        );
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _ctx| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap();
+        let atom = fs::read_to_string(config.output_dir.join("atom.xml")).unwrap();
+        assert!(atom.contains("<title>Home</title>"), "{atom}");
+        assert!(atom.contains("https://example.com/blog/post/"), "{atom}");
+        assert!(config.output_dir.join("rss.xml").exists());
+        let json = fs::read_to_string(config.output_dir.join("feed.json")).unwrap();
+        assert!(
+            json.contains("\"date_published\": \"2026-05-31T00:00:00Z\""),
+            "{json}"
+        );
+    }
+
+    #[test]
+    fn build_feeds_writes_only_feeds() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("blog/post.md", "---\ntitle: Post\ndate: 2026-05-31\n---\n"),
+            ("blog/cat.png", "png"),
+        ]);
+        let config = config
+            .with_site_url("https://example.com")
+            .with_feed(FeedConfig::new().with_formats([FeedFormat::Rss]));
+        let written = build_feeds(&config, &ParserRegistry::default()).unwrap();
+        assert_eq!(written, [config.output_dir.join("rss.xml")]);
+        let rss = fs::read_to_string(&written[0]).unwrap();
+        assert!(rss.contains("https://example.com/blog/post/"), "{rss}");
+        assert!(!config.output_dir.join("index.html").exists());
+        assert!(!config.output_dir.join("blog/post/index.html").exists());
+        assert!(!config.output_dir.join("blog/cat.png").exists());
+    }
+
+    #[test]
+    fn build_writes_no_feeds_by_default() {
+        let (_tmp, config) = setup(&[("index.md", "---\ntitle: Home\n---\n")]);
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_p, _ctx| Ok(String::new()),
+            |_| {},
+        )
+        .unwrap();
+        assert!(!config.output_dir.join("atom.xml").exists());
+        assert!(!config.output_dir.join("rss.xml").exists());
+    }
+
     #[test]
     fn build_root_index_sorts_first() {
         let (_tmp, config) = setup(&[
@@ -341,4 +1331,82 @@ mod tests {
         .unwrap();
         assert_eq!(titles[0], "Home");
     }
+
+    #[test]
+    fn build_generates_series_landing_unless_authored() {
+        let part = |n: u8| format!("---\ntitle: Part {n}\nseries: Intro\nweight: {n}\n---\n");
+        let (_tmp, config) = setup(&[
+            ("guide/two.md", part(2).as_str()),
+            ("guide/one.md", part(1).as_str()),
+            ("other.md", "---\ntitle: Other\nseries: Extras\n---\n"),
+            ("series/extras.md", "---\ntitle: Authored\n---\n"),
+        ]);
+        let mut parts = Vec::new();
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |page, ctx| {
+                if let Some(series) = &ctx.series {
+                    parts.push((page.url.clone(), series.part, series.parts.len()));
+                }
+                Ok(page.content_html.clone())
+            },
+            |_| {},
+        )
+        .unwrap();
+        parts.sort();
+        assert_eq!(
+            parts,
+            [
+                ("/guide/one/".to_string(), 1, 2),
+                ("/guide/two/".to_string(), 2, 2),
+                ("/other/".to_string(), 1, 1),
+            ]
+        );
+        let landing =
+            fs::read_to_string(config.output_dir.join("series/intro/index.html")).unwrap();
+        assert!(
+            landing.find("/guide/one/") < landing.find("/guide/two/"),
+            "{landing}"
+        );
+        let authored =
+            fs::read_to_string(config.output_dir.join("series/extras/index.html")).unwrap();
+        assert!(!authored.contains("series-parts"), "{authored}");
+    }
+
+    #[derive(Debug)]
+    struct DatedPosts;
+
+    impl UrlStrategy for DatedPosts {
+        fn url(&self, relative: &Path, frontmatter: &FrontMatter) -> Option<String> {
+            let date = frontmatter.get_string("date")?;
+            let slug = relative.file_stem()?.to_str()?;
+            Some(format!("/{}/{slug}/", date.get(..4)?))
+        }
+    }
+
+    #[test]
+    fn build_url_strategy_moves_output_and_links() {
+        let (_tmp, config) = setup(&[
+            ("index.md", "---\ntitle: Home\n---\n"),
+            ("hello.md", "---\ntitle: Hello\ndate: 2024-01-15\n---\n"),
+        ]);
+        let config = config.with_url_strategy(DatedPosts);
+        let mut hrefs = Vec::new();
+        build_site(
+            &config,
+            &ParserRegistry::default(),
+            |_p, ctx| {
+                if hrefs.is_empty() {
+                    hrefs = ctx.nav.iter().map(|n| n.href.clone()).collect();
+                }
+                Ok(String::new())
+            },
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(hrefs, ["/", "/2024/hello/"]);
+        assert!(config.output_dir.join("2024/hello/index.html").exists());
+        assert!(!config.output_dir.join("hello").exists());
+    }
 }
@@ -0,0 +1,255 @@
+//! Precompressed siblings (`page.html.gz`, `page.html.br`) for static hosts
+//! that serve them directly — nginx `gzip_static`, Cloudflare, Netlify.
+//!
+//! Compression is a post-build step over the whole output directory, so it
+//! also covers files written after [`build_site`](crate::build_site) (the
+//! CLI's stylesheet, feeds). It's off until a format is selected, and the CLI
+//! only runs it for `build`, never on `serve` rebuilds.
+
+#[cfg(feature = "compress")]
+use crate::core::config::SiteConfig;
+use std::str::FromStr;
+
+/// Extensions compressed unless [`CompressConfig::with_extensions`] says
+/// otherwise.
+pub const DEFAULT_COMPRESS_EXTENSIONS: &[&str] = &["html", "css", "js", "json", "xml", "svg"];
+
+/// Files smaller than this many bytes are left alone by default: the
+/// compressed copy would save little or even be larger.
+pub const DEFAULT_COMPRESS_MIN_SIZE: u64 = 1024;
+
+/// A precompression format. Each writes a sibling named with
+/// [`CompressFormat::extension`] appended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompressFormat {
+    /// gzip, written to `<file>.gz`.
+    Gzip,
+    /// Brotli, written to `<file>.br`.
+    Brotli,
+}
+
+impl CompressFormat {
+    /// Sibling file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            CompressFormat::Gzip => "gz",
+            CompressFormat::Brotli => "br",
+        }
+    }
+}
+
+impl FromStr for CompressFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "gz" => Ok(CompressFormat::Gzip),
+            "brotli" | "br" => Ok(CompressFormat::Brotli),
+            other => Err(format!(
+                "unknown compression format `{other}` (expected gzip or brotli)"
+            )),
+        }
+    }
+}
+
+/// Precompression settings. Off until at least one format is selected.
+///
+/// ```
+/// use sherwood::{CompressConfig, CompressFormat, SiteConfig};
+/// let config = SiteConfig::new().with_compress(
+///     CompressConfig::new()
+///         .with_formats([CompressFormat::Gzip, CompressFormat::Brotli])
+///         .with_min_size(2048),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CompressConfig {
+    /// Formats to write. Empty (the default) disables compression.
+    pub formats: Vec<CompressFormat>,
+    /// Smallest file, in bytes, worth compressing (default
+    /// [`DEFAULT_COMPRESS_MIN_SIZE`]).
+    pub min_size: u64,
+    /// File extensions to compress, without the dot (default
+    /// [`DEFAULT_COMPRESS_EXTENSIONS`]). Matched case-insensitively.
+    pub extensions: Vec<String>,
+}
+
+impl CompressConfig {
+    /// Compression disabled; add formats with
+    /// [`with_formats`](Self::with_formats).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the formats to write.
+    pub fn with_formats(mut self, formats: impl IntoIterator<Item = CompressFormat>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// Set the size threshold in bytes.
+    pub fn with_min_size(mut self, bytes: u64) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    /// Set the extensions to compress, e.g. `["html", "css"]`. A leading dot
+    /// is ignored.
+    pub fn with_extensions<S: AsRef<str>>(mut self, exts: impl IntoIterator<Item = S>) -> Self {
+        self.extensions = exts
+            .into_iter()
+            .map(|e| {
+                e.as_ref()
+                    .trim()
+                    .trim_start_matches('.')
+                    .to_ascii_lowercase()
+            })
+            .collect();
+        self
+    }
+
+    /// Whether a file with this path and size should be compressed.
+    #[cfg_attr(not(feature = "compress"), allow(dead_code))]
+    fn wants(&self, path: &std::path::Path, size: u64) -> bool {
+        size >= self.min_size
+            && path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| self.extensions.iter().any(|x| x.eq_ignore_ascii_case(e)))
+    }
+}
+
+impl Default for CompressConfig {
+    fn default() -> Self {
+        Self {
+            formats: Vec::new(),
+            min_size: DEFAULT_COMPRESS_MIN_SIZE,
+            extensions: DEFAULT_COMPRESS_EXTENSIONS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Write a compressed sibling for every eligible file under the output
+/// directory, per [`SiteConfig::compress`]. A sibling is skipped when it
+/// wouldn't be smaller than the original, and stale siblings of files that
+/// no longer qualify are removed. Returns the number of files written.
+///
+/// Does nothing when no format is selected.
+#[cfg(feature = "compress")]
+pub fn compress_output(config: &SiteConfig) -> Result<usize, crate::core::build::BuildError> {
+    let settings = &config.compress;
+    if settings.formats.is_empty() {
+        return Ok(0);
+    }
+    let compressed_exts = [CompressFormat::Gzip, CompressFormat::Brotli].map(|f| f.extension());
+    let mut written = 0;
+    for entry in walkdir::WalkDir::new(&config.output_dir) {
+        let entry = entry?;
+        let path = entry.path();
+        let is_sibling = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| compressed_exts.contains(&e));
+        if !entry.file_type().is_file() || is_sibling {
+            continue;
+        }
+        let bytes = std::fs::read(path)?;
+        let wanted = settings.wants(path, bytes.len() as u64);
+        for format in [CompressFormat::Gzip, CompressFormat::Brotli] {
+            let mut sibling = path.as_os_str().to_owned();
+            sibling.push(".");
+            sibling.push(format.extension());
+            let sibling = std::path::PathBuf::from(sibling);
+            let compressed = if wanted && settings.formats.contains(&format) {
+                Some(encode(format, &bytes)?).filter(|c| c.len() < bytes.len())
+            } else {
+                None
+            };
+            match compressed {
+                Some(c) => {
+                    std::fs::write(&sibling, c)?;
+                    written += 1;
+                }
+                None if sibling.exists() => std::fs::remove_file(&sibling)?,
+                None => {}
+            }
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(feature = "compress")]
+fn encode(format: CompressFormat, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Write;
+    match format {
+        CompressFormat::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+            encoder.write_all(bytes)?;
+            encoder.finish()
+        }
+        CompressFormat::Brotli => {
+            // Quality 11 (max), 4 MiB window: slow, but this runs once per
+            // deploy build and the output is served many times.
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+            encoder.write_all(bytes)?;
+            Ok(encoder.into_inner())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_format_names() {
+        assert_eq!("gzip".parse::<CompressFormat>(), Ok(CompressFormat::Gzip));
+        assert_eq!("BR".parse::<CompressFormat>(), Ok(CompressFormat::Brotli));
+        assert!("zstd".parse::<CompressFormat>().is_err());
+    }
+
+    #[test]
+    fn wants_checks_extension_and_size() {
+        let config = CompressConfig::new().with_extensions([".HTML", "css"]);
+        assert!(config.wants(Path::new("a/index.html"), 4096));
+        assert!(config.wants(Path::new("style.CSS"), 1024));
+        assert!(!config.wants(Path::new("style.css"), 1023));
+        assert!(!config.wants(Path::new("logo.png"), 4096));
+        assert!(!config.wants(Path::new("README"), 4096));
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn writes_siblings_and_removes_stale_ones() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out = tmp.path();
+        let big = "<p>hello</p>".repeat(200);
+        std::fs::write(out.join("index.html"), &big).unwrap();
+        std::fs::write(out.join("small.css"), "body{}").unwrap();
+        std::fs::write(out.join("small.css.gz"), "stale").unwrap();
+        std::fs::write(out.join("logo.png"), &big).unwrap();
+        let config = SiteConfig::new().with_output_dir(out).with_compress(
+            CompressConfig::new().with_formats([CompressFormat::Gzip, CompressFormat::Brotli]),
+        );
+
+        assert_eq!(compress_output(&config).unwrap(), 2);
+        assert!(out.join("index.html.gz").exists());
+        assert!(out.join("index.html.br").exists());
+        assert!(!out.join("small.css.gz").exists());
+        assert!(!out.join("logo.png.gz").exists());
+
+        // Dropping brotli removes its siblings on the next run.
+        let config =
+            config.with_compress(CompressConfig::new().with_formats([CompressFormat::Gzip]));
+        assert_eq!(compress_output(&config).unwrap(), 1);
+        assert!(out.join("index.html.gz").exists());
+        assert!(!out.join("index.html.br").exists());
+    }
+}
@@ -0,0 +1,195 @@
+//! `${VAR}` interpolation in config string values, so deploy pipelines can
+//! set per-environment values (`site_url = "${DEPLOY_URL}"`) without
+//! committing them.
+//!
+//! - `${VAR}` is replaced by the variable's value; an unset variable is an
+//!   error.
+//! - `${VAR:-fallback}` uses `fallback` when `VAR` is unset or empty.
+//! - `$${` is a literal `${`. Any other `$` is kept as is.
+//!
+//! Variables come from the process environment, plus — when a loader asks
+//! for one — a `.env` file parsed by [`parse_dotenv`], whose values fill in
+//! only what the environment doesn't set.
+
+use std::collections::HashMap;
+
+/// Expand every `${…}` in `value`, looking variables up with `lookup`. The
+/// error completes a sentence about the key: "`site_url` {error}".
+pub(super) fn interpolate(
+    value: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(at) = rest.find('$') {
+        out.push_str(&rest[..at]);
+        let tail = &rest[at..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = after;
+            continue;
+        }
+        let Some(body) = tail.strip_prefix("${") else {
+            out.push('$');
+            rest = &tail[1..];
+            continue;
+        };
+        let end = body
+            .find('}')
+            .ok_or_else(|| format!("has an unterminated `${{` in `{value}`"))?;
+        let expr = &body[..end];
+        let (name, default) = match expr.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (expr, None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "has an invalid variable name `{name}` in `{value}`"
+            ));
+        }
+        match (lookup(name), default) {
+            (Some(v), Some(default)) if v.is_empty() => out.push_str(default),
+            (Some(v), _) => out.push_str(&v),
+            (None, Some(default)) => out.push_str(default),
+            (None, None) => {
+                return Err(format!(
+                    "references undefined environment variable `{name}` \
+                     (set it, or give a default: `${{{name}:-value}}`)"
+                ));
+            }
+        }
+        rest = &body[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Variables from a `.env` file: one `KEY=value` per line, optionally
+/// prefixed with `export `. Blank lines and `#` comments are skipped. A value
+/// in single quotes is taken literally; one in double quotes understands
+/// `\n`, `\"` and `\\`; an unquoted value is trimmed and ends at ` #`. The
+/// error names the offending line.
+pub(super) fn parse_dotenv(text: &str) -> Result<HashMap<String, String>, String> {
+    let mut vars = HashMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let invalid = |what: &str| format!("line {}: {what}", i + 1);
+        let (name, value) = line
+            .split_once('=')
+            .ok_or_else(|| invalid("expected `KEY=value`"))?;
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(invalid(&format!("invalid variable name `{name}`")));
+        }
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('\'') {
+            quoted
+                .split_once('\'')
+                .map(|(inner, _)| inner.to_string())
+                .ok_or_else(|| invalid("unterminated `'`"))?
+        } else if let Some(quoted) = value.strip_prefix('"') {
+            unescape(quoted).ok_or_else(|| invalid("unterminated `\"`"))?
+        } else {
+            match value.find(" #") {
+                Some(at) => value[..at].trim_end().to_string(),
+                None => value.to_string(),
+            }
+        };
+        vars.insert(name.to_string(), value);
+    }
+    Ok(vars)
+}
+
+/// The body of a double-quoted value, up to its closing quote, with escapes
+/// resolved. `None` when the quote is never closed.
+fn unescape(quoted: &str) -> Option<String> {
+    let mut out = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(out),
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                other => out.push(other),
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "DEPLOY_URL" => Some("https://example.com".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_variables_and_defaults() {
+        assert_eq!(
+            interpolate("${DEPLOY_URL}/docs", &env).unwrap(),
+            "https://example.com/docs"
+        );
+        assert_eq!(interpolate("${MISSING:-/x}", &env).unwrap(), "/x");
+        assert_eq!(interpolate("${EMPTY:-fallback}", &env).unwrap(), "fallback");
+        assert_eq!(interpolate("${EMPTY}", &env).unwrap(), "");
+        assert_eq!(interpolate("${MISSING:-}", &env).unwrap(), "");
+    }
+
+    #[test]
+    fn literal_dollars_pass_through() {
+        assert_eq!(
+            interpolate("$5 and $${HOME}", &env).unwrap(),
+            "$5 and ${HOME}"
+        );
+        assert_eq!(interpolate("no vars", &env).unwrap(), "no vars");
+    }
+
+    #[test]
+    fn parses_dotenv_lines() {
+        let vars = parse_dotenv(concat!(
+            "# local overrides\n",
+            "\n",
+            "DEPLOY_URL=http://localhost:4000 # dev server\n",
+            "export BASE = /docs\n",
+            "TITLE=\"My \\\"Site\\\"\"\n",
+            "RAW='${NOT_EXPANDED} #'\n",
+        ))
+        .unwrap();
+        assert_eq!(vars["DEPLOY_URL"], "http://localhost:4000");
+        assert_eq!(vars["BASE"], "/docs");
+        assert_eq!(vars["TITLE"], "My \"Site\"");
+        assert_eq!(vars["RAW"], "${NOT_EXPANDED} #");
+        assert_eq!(vars.len(), 4);
+    }
+
+    #[test]
+    fn malformed_dotenv_lines_error_with_line_number() {
+        assert_eq!(
+            parse_dotenv("A=1\nnot a pair\n").unwrap_err(),
+            "line 2: expected `KEY=value`"
+        );
+        assert!(parse_dotenv("A B=1").is_err());
+        assert!(parse_dotenv("A=\"open").is_err());
+        assert!(parse_dotenv("A='open").is_err());
+    }
+
+    #[test]
+    fn undefined_and_malformed_references_error() {
+        let err = interpolate("${MISSING}", &env).unwrap_err();
+        assert!(err.contains("`MISSING`"), "{err}");
+        assert!(interpolate("${DEPLOY_URL", &env).is_err());
+        assert!(interpolate("${}", &env).is_err());
+        assert!(interpolate("${A B}", &env).is_err());
+    }
+}
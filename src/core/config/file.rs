@@ -0,0 +1,949 @@
+//! `Sherwood.toml`: a [`SiteConfig`] read from disk, with override files
+//! layered on top.
+//!
+//! Every key is optional and mirrors a `with_*` builder:
+//!
+//! ```toml
+//! content_dir = "content"        # relative to the file's directory
+//! extra_content_dirs = ["../docs"] # merged into the same site, also relative
+//! output_dir = "_site"
+//! base_path = "/docs"
+//! trailing_slash = "never"       # auto, always or never
+//! site_url = "https://example.com"
+//! stylesheet = "css/main.css"
+//! print_stylesheet = "print.css"
+//! date_format = "%d %b %Y"
+//! strict_dates = true          # invalid `date` values fail the build
+//! timezone = "+02:00"            # offset for dates without one (default UTC)
+//! language = "de"               # <html lang>; a page's own lang wins
+//! git_last_modified = true
+//! lazy_images = true
+//! fingerprint_images = "relative" # off, relative or local
+//! wrap_tables = true
+//! excerpt_max_chars = 200        # 0 keeps excerpts whole
+//! excerpt_separator = "<!--more-->" # ends the excerpt; default <!-- more -->
+//! raw_content = true             # keep page sources for templates
+//! drafts = false
+//! unpublished = false            # build `published: false` pages too
+//! related_limit = 5
+//! max_concurrency = 2            # content-loading threads; 0 = one per CPU
+//! languages = ["en", "fr"]
+//! head = ["link rel=icon href=/favicon.ico"]
+//!
+//! [feed]
+//! formats = ["atom", "rss"]
+//! title = "My Blog"
+//! author = "Jane Doe"            # Atom feed author; footer owner by default
+//! full_content = false           # embed whole pages, not just excerpts
+//! limit = 20                     # newest items only; all when unset
+//!
+//! [toc]
+//! min_level = 2
+//! max_level = 4
+//!
+//! [markdown]                     # extensions, all but mermaid/emoji true by default
+//! tables = true
+//! strikethrough = true
+//! footnotes = false
+//! task_lists = true
+//! math = false
+//! smart_punctuation = true
+//! mermaid = true                 # ```mermaid blocks as <pre class="mermaid">
+//! emoji = true                   # :smile: shortcodes as 😄
+//!
+//! [compress]
+//! formats = ["gzip", "brotli"]
+//! min_size = 1024
+//! extensions = ["html", "css", "js", "json", "xml", "svg"]
+//!
+//! [footer]                       # copyright and license on every page
+//! owner = "Jane Doe"
+//! since = 2019                   # © 2019–<build year>
+//! license = "CC BY 4.0"
+//! license_url = "https://creativecommons.org/licenses/by/4.0/"
+//!
+//! [validation]                   # path glob -> required frontmatter
+//! "blog/**" = ["date", "author"]
+//! "docs/*.md" = ["weight"]
+//!
+//! [[menu]]
+//! name = "Blog"
+//! url = "/blog/"
+//! weight = 1
+//!
+//! [[redirects]]
+//! from = "/old-post/"
+//! to = "/blog/new-post/"
+//! status = 301                   # optional; 301, 302, 307 or 308
+//! ```
+//!
+//! A file only sets the keys it contains, so merging an override file keeps
+//! everything it doesn't mention: the override wins key by key, and within a
+//! table (`[toc] max_level` alone leaves `min_level` as it was, and a
+//! `[validation]` glob replaces only that glob's rule). Arrays
+//! (`languages`, `head`, `menu`, `formats`, `extensions`) are replaced whole. Unknown
+//! keys are errors, so typos don't pass silently.
+//!
+//! String values may reference environment variables — `${DEPLOY_URL}`, or
+//! `${DEPLOY_URL:-https://example.com}` with a fallback — expanded at load
+//! time. An unset variable without a fallback is an error; `$${` is a
+//! literal `${`. [`SiteConfig::load_with_env_file`] also takes values from a
+//! `.env` file, for local development; the process environment still wins.
+
+use super::SiteConfig;
+use super::env::{interpolate, parse_dotenv};
+use crate::core::compress::CompressFormat;
+use crate::core::content::images::ImageFingerprints;
+use crate::core::content::toc::toc_levels;
+use crate::core::content::validation::ValidationRule;
+use crate::core::date::UtcOffset;
+use crate::core::feed::FeedFormat;
+use crate::core::head::HeadTag;
+use crate::core::nav::{MenuItem, TrailingSlash};
+use crate::core::redirect::Redirect;
+use gray_matter::Pod;
+use gray_matter::engine::{Engine, TOML};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Conventional config file name, looked for at the project root.
+pub const CONFIG_FILE: &str = "Sherwood.toml";
+
+/// Failure modes when loading a config file. Each variant carries the file's
+/// path.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("reading {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// The file isn't valid TOML.
+    #[error("parsing {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+    /// A key is unknown or its value has the wrong type or format. `key` is
+    /// dotted for nested tables (`toc.min_level`, `menu[0].url`).
+    #[error("{}: `{key}` {message}", path.display())]
+    Invalid {
+        path: PathBuf,
+        key: String,
+        message: String,
+    },
+}
+
+impl SiteConfig {
+    /// Load a config file on top of [`SiteConfig::default`]. Relative
+    /// `content_dir` / `extra_content_dirs` / `output_dir` values are
+    /// resolved against the file's directory.
+    ///
+    /// ```no_run
+    /// use sherwood::SiteConfig;
+    /// # fn main() -> Result<(), sherwood::ConfigError> {
+    /// let config = SiteConfig::load("Sherwood.toml")?
+    ///     .merge_file("Sherwood.prod.toml")?
+    ///     .with_git_last_modified(true);
+    /// # Ok(()) }
+    /// ```
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::default().merge_file(path)
+    }
+
+    /// Apply the keys set in another config file on top of this config. Keys
+    /// the file doesn't mention keep their current values.
+    pub fn merge_file(self, path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        self.merge_str(&read(path)?, path)
+    }
+
+    /// [`load`](Self::load), with `${VAR}` values also looked up in a `.env`
+    /// file (`KEY=value` lines). A variable set in the process environment
+    /// wins over the file's; the file never changes the environment itself.
+    /// A missing or malformed `.env` file is an error.
+    ///
+    /// ```no_run
+    /// use sherwood::SiteConfig;
+    /// # fn main() -> Result<(), sherwood::ConfigError> {
+    /// let config = SiteConfig::load_with_env_file("Sherwood.toml", ".env")?;
+    /// # Ok(()) }
+    /// ```
+    pub fn load_with_env_file(
+        path: impl AsRef<Path>,
+        env_file: impl AsRef<Path>,
+    ) -> Result<Self, ConfigError> {
+        Self::default().merge_file_with_env_file(path, env_file)
+    }
+
+    /// [`merge_file`](Self::merge_file), with `${VAR}` values also looked up
+    /// in a `.env` file, as in [`load_with_env_file`](Self::load_with_env_file).
+    pub fn merge_file_with_env_file(
+        self,
+        path: impl AsRef<Path>,
+        env_file: impl AsRef<Path>,
+    ) -> Result<Self, ConfigError> {
+        let env_file = env_file.as_ref();
+        let vars = parse_dotenv(&read(env_file)?).map_err(|message| ConfigError::Parse {
+            path: env_file.to_owned(),
+            message,
+        })?;
+        let path = path.as_ref();
+        self.merge_toml(&read(path)?, path, &|name| {
+            std::env::var(name).ok().or_else(|| vars.get(name).cloned())
+        })
+    }
+
+    /// [`merge_file`](Self::merge_file) for TOML already in memory. `path`
+    /// names the source in errors and anchors relative directories.
+    pub fn merge_str(self, toml: &str, path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        self.merge_toml(toml, path.as_ref(), &|name| std::env::var(name).ok())
+    }
+
+    /// [`merge_str`](Self::merge_str) with an injectable environment, so
+    /// tests don't touch the process's.
+    fn merge_toml(
+        self,
+        toml: &str,
+        path: &Path,
+        env: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let parse_error = |message: String| ConfigError::Parse {
+            path: path.to_owned(),
+            message,
+        };
+        let root = match TOML::parse(toml).map_err(|e| parse_error(e.to_string()))? {
+            Pod::Hash(map) => map,
+            Pod::Null => HashMap::new(),
+            _ => return Err(parse_error("expected a table of settings".to_string())),
+        };
+        Table {
+            path,
+            prefix: String::new(),
+            map: &root,
+            env,
+        }
+        .apply(self, path.parent().unwrap_or(Path::new("")))
+    }
+}
+
+fn read(path: &Path) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// One TOML table being applied, with typed accessors that report errors
+/// against the table's dotted key prefix.
+struct Table<'a> {
+    path: &'a Path,
+    prefix: String,
+    map: &'a HashMap<String, Pod>,
+    env: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl<'a> Table<'a> {
+    fn apply(&self, mut config: SiteConfig, dir: &Path) -> Result<SiteConfig, ConfigError> {
+        self.only(&[
+            "content_dir",
+            "extra_content_dirs",
+            "output_dir",
+            "base_path",
+            "trailing_slash",
+            "site_url",
+            "stylesheet",
+            "print_stylesheet",
+            "date_format",
+            "strict_dates",
+            "timezone",
+            "language",
+            "git_last_modified",
+            "lazy_images",
+            "fingerprint_images",
+            "wrap_tables",
+            "excerpt_max_chars",
+            "excerpt_separator",
+            "raw_content",
+            "drafts",
+            "unpublished",
+            "related_limit",
+            "max_concurrency",
+            "languages",
+            "head",
+            "feed",
+            "toc",
+            "markdown",
+            "menu",
+            "compress",
+            "footer",
+            "validation",
+            "redirects",
+        ])?;
+        if let Some(v) = self.string("content_dir")? {
+            config.content_dir = dir.join(v);
+        }
+        if let Some(v) = self.strings("extra_content_dirs")? {
+            config = config.with_extra_content_dirs(v.iter().map(|d| dir.join(d)));
+        }
+        if let Some(v) = self.string("output_dir")? {
+            config.output_dir = dir.join(v);
+        }
+        if let Some(v) = self.string("base_path")? {
+            config = config.with_base_path(v);
+        }
+        if let Some(v) = self.string("trailing_slash")? {
+            let mode = v
+                .parse::<TrailingSlash>()
+                .map_err(|e| self.invalid("trailing_slash", e))?;
+            config = config.with_trailing_slash(mode);
+        }
+        if let Some(v) = self.string("site_url")? {
+            config = config.with_site_url(v);
+        }
+        if let Some(v) = self.string("stylesheet")? {
+            config = config.with_stylesheet(v);
+        }
+        if let Some(v) = self.string("print_stylesheet")? {
+            config = config.with_print_stylesheet(v);
+        }
+        if let Some(v) = self.string("date_format")? {
+            config = config.with_date_format(v);
+        }
+        if let Some(v) = self.bool("strict_dates")? {
+            config = config.with_strict_dates(v);
+        }
+        if let Some(v) = self.string("timezone")? {
+            let offset = v
+                .parse::<UtcOffset>()
+                .map_err(|e| self.invalid("timezone", e))?;
+            config = config.with_timezone(offset);
+        }
+        if let Some(v) = self.string("language")? {
+            config = config.with_language(v);
+        }
+        if let Some(v) = self.bool("git_last_modified")? {
+            config = config.with_git_last_modified(v);
+        }
+        if let Some(v) = self.bool("lazy_images")? {
+            config = config.with_lazy_images(v);
+        }
+        if let Some(v) = self.string("fingerprint_images")? {
+            let mode = v
+                .parse::<ImageFingerprints>()
+                .map_err(|e| self.invalid("fingerprint_images", e))?;
+            config = config.with_fingerprint_images(mode);
+        }
+        if let Some(v) = self.bool("wrap_tables")? {
+            config = config.with_wrap_tables(v);
+        }
+        if let Some(v) = self.integer("excerpt_max_chars")? {
+            let max = usize::try_from(v)
+                .map_err(|_| self.invalid("excerpt_max_chars", "must not be negative"))?;
+            config = config.with_excerpt_max_chars(max);
+        }
+        if let Some(v) = self.string("excerpt_separator")? {
+            if v.is_empty() {
+                return Err(self.invalid("excerpt_separator", "must not be empty"));
+            }
+            config = config.with_excerpt_separator(v);
+        }
+        if let Some(v) = self.bool("raw_content")? {
+            config = config.with_raw_content(v);
+        }
+        if let Some(v) = self.bool("drafts")? {
+            config = config.with_drafts(v);
+        }
+        if let Some(v) = self.bool("unpublished")? {
+            config = config.with_unpublished(v);
+        }
+        if let Some(v) = self.integer("related_limit")? {
+            let limit = usize::try_from(v)
+                .map_err(|_| self.invalid("related_limit", "must not be negative"))?;
+            config = config.with_related_limit(limit);
+        }
+        if let Some(v) = self.integer("max_concurrency")? {
+            let threads = usize::try_from(v)
+                .map_err(|_| self.invalid("max_concurrency", "must not be negative"))?;
+            config = config.with_max_concurrency(threads);
+        }
+        if let Some(v) = self.strings("languages")? {
+            config = config.with_languages(v);
+        }
+        if let Some(v) = self.strings("head")? {
+            let tags = v
+                .iter()
+                .map(|raw| {
+                    raw.parse::<HeadTag>()
+                        .and_then(|tag| tag.validate().map(|()| tag))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| self.invalid("head", e))?;
+            config = config.with_head(tags);
+        }
+        if let Some(feed) = self.table("feed")? {
+            feed.only(&["formats", "title", "author", "full_content", "limit"])?;
+            if let Some(v) = feed.strings("formats")? {
+                let formats =
+                    FeedFormat::parse_list(&v.join(",")).map_err(|e| feed.invalid("formats", e))?;
+                config.feed = config.feed.with_formats(formats);
+            }
+            if let Some(v) = feed.string("title")? {
+                config.feed = config.feed.with_title(v);
+            }
+            if let Some(v) = feed.string("author")? {
+                config.feed = config.feed.with_author(v);
+            }
+            if let Some(v) = feed.bool("full_content")? {
+                config.feed = config.feed.with_full_content(v);
+            }
+            if let Some(v) = feed.integer("limit")? {
+                let limit = usize::try_from(v)
+                    .ok()
+                    .filter(|&limit| limit > 0)
+                    .ok_or_else(|| feed.invalid("limit", "must be at least 1"))?;
+                config.feed = config.feed.with_limit(limit);
+            }
+        }
+        if let Some(toc) = self.table("toc")? {
+            toc.only(&["min_level", "max_level"])?;
+            let min = toc.integer("min_level")?;
+            let max = toc.integer("max_level")?;
+            let (range, warning) = toc_levels(
+                min.unwrap_or(i64::from(config.toc_min_level)),
+                max.unwrap_or(i64::from(config.toc_max_level)),
+            );
+            if warning.is_some() {
+                // Blame the key that's out of range, else the one that
+                // inverted the range.
+                let key = match (min, max) {
+                    (Some(n), _) if !(1..=6).contains(&n) => "min_level",
+                    (_, Some(n)) if !(1..=6).contains(&n) => "max_level",
+                    (Some(_), None) => "min_level",
+                    _ => "max_level",
+                };
+                return Err(toc.invalid(
+                    key,
+                    "must be between 1 and 6, with min_level no greater than max_level",
+                ));
+            }
+            (config.toc_min_level, config.toc_max_level) = (*range.start(), *range.end());
+        }
+        if let Some(markdown) = self.table("markdown")? {
+            markdown.only(&[
+                "tables",
+                "strikethrough",
+                "footnotes",
+                "task_lists",
+                "math",
+                "smart_punctuation",
+                "mermaid",
+                "emoji",
+            ])?;
+            let features = &mut config.markdown;
+            let toggles = [
+                ("tables", &mut features.tables),
+                ("strikethrough", &mut features.strikethrough),
+                ("footnotes", &mut features.footnotes),
+                ("task_lists", &mut features.task_lists),
+                ("math", &mut features.math),
+                ("smart_punctuation", &mut features.smart_punctuation),
+                ("mermaid", &mut features.mermaid),
+                ("emoji", &mut features.emoji),
+            ];
+            for (key, enabled) in toggles {
+                if let Some(v) = markdown.bool(key)? {
+                    *enabled = v;
+                }
+            }
+        }
+        if let Some(footer) = self.table("footer")? {
+            footer.only(&["owner", "since", "license", "license_url"])?;
+            if let Some(v) = footer.string("owner")? {
+                config.footer = config.footer.with_owner(v);
+            }
+            if let Some(v) = footer.integer("since")? {
+                config.footer = config.footer.with_since(v);
+            }
+            if let Some(v) = footer.string("license")? {
+                config.footer = config.footer.with_license(v);
+            }
+            if let Some(v) = footer.string("license_url")? {
+                config.footer = config.footer.with_license_url(v);
+            }
+        }
+        if let Some(compress) = self.table("compress")? {
+            compress.only(&["formats", "min_size", "extensions"])?;
+            if let Some(v) = compress.strings("formats")? {
+                let formats = v
+                    .iter()
+                    .map(|f| f.parse::<CompressFormat>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| compress.invalid("formats", e))?;
+                config.compress = config.compress.with_formats(formats);
+            }
+            if let Some(v) = compress.integer("min_size")? {
+                let bytes = u64::try_from(v)
+                    .map_err(|_| compress.invalid("min_size", "must not be negative"))?;
+                config.compress = config.compress.with_min_size(bytes);
+            }
+            if let Some(v) = compress.strings("extensions")? {
+                config.compress = config.compress.with_extensions(v);
+            }
+        }
+        if let Some(validation) = self.table("validation")? {
+            let mut patterns: Vec<&String> = validation.map.keys().collect();
+            patterns.sort();
+            for pattern in patterns {
+                let required = validation.strings(pattern)?.unwrap_or_default();
+                config.validation.retain(|rule| &rule.pattern != pattern);
+                config
+                    .validation
+                    .push(ValidationRule::new(pattern.as_str(), required));
+            }
+        }
+        if let Some(items) = self.tables("menu")? {
+            config.menu = items
+                .iter()
+                .map(|item| {
+                    item.only(&["name", "url", "weight"])?;
+                    let name = item.required_string("name")?;
+                    let url = item.required_string("url")?;
+                    let weight = item.integer("weight")?.unwrap_or_default();
+                    Ok(MenuItem::new(name, url).with_weight(weight))
+                })
+                .collect::<Result<_, ConfigError>>()?;
+        }
+        if let Some(rules) = self.tables("redirects")? {
+            config.redirects = rules
+                .iter()
+                .map(|rule| {
+                    rule.only(&["from", "to", "status"])?;
+                    let redirect =
+                        Redirect::new(rule.required_string("from")?, rule.required_string("to")?);
+                    Ok(match rule.integer("status")? {
+                        Some(status) => redirect.with_status(
+                            u16::try_from(status)
+                                .map_err(|_| rule.invalid("status", "must be an HTTP status"))?,
+                        ),
+                        None => redirect,
+                    })
+                })
+                .collect::<Result<_, ConfigError>>()?;
+        }
+        Ok(config)
+    }
+
+    fn invalid(&self, key: &str, message: impl Into<String>) -> ConfigError {
+        ConfigError::Invalid {
+            path: self.path.to_owned(),
+            key: format!("{}{key}", self.prefix),
+            message: message.into(),
+        }
+    }
+
+    /// Reject keys outside `known`.
+    fn only(&self, known: &[&str]) -> Result<(), ConfigError> {
+        match self.map.keys().find(|k| !known.contains(&k.as_str())) {
+            Some(unknown) => Err(self.invalid(unknown, "is not a known setting")),
+            None => Ok(()),
+        }
+    }
+
+    /// A string value, with `${VAR}` references expanded.
+    fn string(&self, key: &str) -> Result<Option<String>, ConfigError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(Pod::String(s)) => self.expand(key, s).map(Some),
+            Some(_) => Err(self.invalid(key, "must be a string")),
+        }
+    }
+
+    fn expand(&self, key: &str, value: &str) -> Result<String, ConfigError> {
+        interpolate(value, self.env).map_err(|e| self.invalid(key, e))
+    }
+
+    fn required_string(&self, key: &str) -> Result<String, ConfigError> {
+        self.string(key)?
+            .ok_or_else(|| self.invalid(key, "is required"))
+    }
+
+    fn bool(&self, key: &str) -> Result<Option<bool>, ConfigError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(Pod::Boolean(b)) => Ok(Some(*b)),
+            Some(_) => Err(self.invalid(key, "must be true or false")),
+        }
+    }
+
+    fn integer(&self, key: &str) -> Result<Option<i64>, ConfigError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(Pod::Integer(n)) => Ok(Some(*n)),
+            Some(_) => Err(self.invalid(key, "must be an integer")),
+        }
+    }
+
+    fn strings(&self, key: &str) -> Result<Option<Vec<String>>, ConfigError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(Pod::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    Pod::String(s) => self.expand(key, s),
+                    _ => Err(self.invalid(key, "must be an array of strings")),
+                })
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(_) => Err(self.invalid(key, "must be an array of strings")),
+        }
+    }
+
+    fn table(&self, key: &str) -> Result<Option<Table<'a>>, ConfigError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(Pod::Hash(map)) => Ok(Some(Table {
+                path: self.path,
+                prefix: format!("{}{key}.", self.prefix),
+                map,
+                env: self.env,
+            })),
+            Some(_) => Err(self.invalid(key, "must be a table")),
+        }
+    }
+
+    /// An array of tables (`[[menu]]`).
+    fn tables(&self, key: &str) -> Result<Option<Vec<Table<'a>>>, ConfigError> {
+        match self.map.get(key) {
+            None => Ok(None),
+            Some(Pod::Array(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| match item {
+                    Pod::Hash(map) => Ok(Table {
+                        path: self.path,
+                        prefix: format!("{}{key}[{i}].", self.prefix),
+                        map,
+                        env: self.env,
+                    }),
+                    _ => Err(self.invalid(key, "must be an array of tables")),
+                })
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(_) => Err(self.invalid(key, "must be an array of tables")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::content::parser::MarkdownFeatures;
+    use crate::core::footer::FooterConfig;
+
+    fn merge(config: SiteConfig, toml: &str) -> Result<SiteConfig, ConfigError> {
+        config.merge_toml(toml, Path::new("site/Sherwood.toml"), &|name| {
+            (name == "DEPLOY_URL").then(|| "https://example.com/".to_string())
+        })
+    }
+
+    #[test]
+    fn empty_file_keeps_defaults() {
+        let config = merge(SiteConfig::default(), "").unwrap();
+        assert_eq!(config.content_dir, PathBuf::from("content"));
+        assert_eq!(config.stylesheet, "style.css");
+    }
+
+    #[test]
+    fn reads_every_section() {
+        let config = merge(
+            SiteConfig::default(),
+            r#"
+content_dir = "src"
+extra_content_dirs = ["docs"]
+base_path = "docs/"
+trailing_slash = "always"
+language = "de"
+strict_dates = true
+timezone = "-05:00"
+languages = ["en", "fr"]
+head = ["link rel=icon href=/favicon.ico"]
+related_limit = 3
+max_concurrency = 2
+fingerprint_images = "local"
+wrap_tables = true
+excerpt_max_chars = 200
+excerpt_separator = "<!--more-->"
+raw_content = true
+unpublished = true
+
+[feed]
+formats = ["atom", "rss"]
+author = "Ada"
+full_content = true
+limit = 20
+
+[toc]
+max_level = 4
+
+[markdown]
+footnotes = false
+math = false
+mermaid = true
+emoji = true
+
+[compress]
+formats = ["gzip"]
+min_size = 512
+
+[footer]
+owner = "Jane Doe"
+since = 2019
+license = "MIT"
+
+[[menu]]
+name = "Blog"
+url = "/blog/"
+weight = 2
+
+[validation]
+"blog/**" = ["date"]
+
+[[menu]]
+name = "About"
+url = "/about/"
+
+[[redirects]]
+from = "/old/"
+to = "/new/"
+
+[[redirects]]
+from = "/sale/"
+to = "https://shop.example.com/"
+status = 302
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.content_dir, Path::new("site").join("src"));
+        assert_eq!(config.extra_content_dirs, [Path::new("site").join("docs")]);
+        assert_eq!(config.output_dir, PathBuf::from("_site"));
+        assert_eq!(config.base_path, "/docs");
+        assert_eq!(config.trailing_slash, TrailingSlash::Always);
+        assert_eq!(config.language, "de");
+        assert!(config.strict_dates);
+        assert_eq!(config.timezone.minutes(), -300);
+        assert_eq!(config.languages, ["en", "fr"]);
+        assert_eq!(config.head.len(), 1);
+        assert_eq!(config.related_limit, 3);
+        assert_eq!(config.max_concurrency, 2);
+        assert_eq!(config.fingerprint_images, ImageFingerprints::Local);
+        assert!(config.wrap_tables);
+        assert_eq!(config.excerpt_max_chars, 200);
+        assert_eq!(config.excerpt_separator, "<!--more-->");
+        assert!(config.raw_content);
+        assert!(config.unpublished);
+        assert_eq!(config.feed.formats, [FeedFormat::Atom, FeedFormat::Rss]);
+        assert_eq!(config.feed.author.as_deref(), Some("Ada"));
+        assert!(config.feed.full_content);
+        assert_eq!(config.feed.limit, Some(20));
+        assert_eq!((config.toc_min_level, config.toc_max_level), (2, 4));
+        assert_eq!(
+            config.markdown,
+            MarkdownFeatures::new()
+                .with_footnotes(false)
+                .with_math(false)
+                .with_mermaid(true)
+                .with_emoji(true)
+        );
+        assert_eq!(config.compress.formats, [CompressFormat::Gzip]);
+        assert_eq!(config.compress.min_size, 512);
+        assert_eq!(
+            config.footer,
+            FooterConfig::new()
+                .with_owner("Jane Doe")
+                .with_since(2019)
+                .with_license("MIT")
+        );
+        assert_eq!(
+            config.validation,
+            [ValidationRule::new("blog/**", ["date"])]
+        );
+        assert_eq!(
+            config.menu,
+            [
+                MenuItem::new("Blog", "/blog/").with_weight(2),
+                MenuItem::new("About", "/about/"),
+            ]
+        );
+        assert_eq!(
+            config.redirects,
+            [
+                Redirect::new("/old/", "/new/"),
+                Redirect::new("/sale/", "https://shop.example.com/").with_status(302),
+            ]
+        );
+    }
+
+    #[test]
+    fn override_wins_and_base_fills_gaps() {
+        let base = merge(
+            SiteConfig::default(),
+            "site_url = \"https://staging.example\"\ndate_format = \"%Y\"\n[toc]\nmin_level = 1\nmax_level = 2\n",
+        )
+        .unwrap();
+        let config = merge(
+            base,
+            "site_url = \"https://example.com\"\n[toc]\nmax_level = 5\n",
+        )
+        .unwrap();
+        assert_eq!(config.site_url, "https://example.com");
+        assert_eq!(config.date_format, "%Y");
+        assert_eq!((config.toc_min_level, config.toc_max_level), (1, 5));
+    }
+
+    #[test]
+    fn validation_globs_merge_one_by_one() {
+        let base = merge(
+            SiteConfig::default(),
+            "[validation]\n\"blog/**\" = [\"date\"]\n\"docs/**\" = [\"weight\"]\n",
+        )
+        .unwrap();
+        let config = merge(base, "[validation]\n\"docs/**\" = []\n").unwrap();
+        assert_eq!(
+            config.validation,
+            [
+                ValidationRule::new("blog/**", ["date"]),
+                ValidationRule::new("docs/**", Vec::<String>::new()),
+            ]
+        );
+        let err = merge(
+            SiteConfig::default(),
+            "[validation]\n\"blog/**\" = \"date\"\n",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Invalid { key, .. } if key == "validation.blog/**"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_or_inverted_toc_levels() {
+        for (toml, bad) in [
+            ("[toc]\nmin_level = 0", "toc.min_level"),
+            ("[toc]\nmin_level = 2\nmax_level = 7", "toc.max_level"),
+            ("[toc]\nmin_level = 4\nmax_level = 2", "toc.max_level"),
+            // The default max is 3, so min_level alone inverts the range.
+            ("[toc]\nmin_level = 5", "toc.min_level"),
+        ] {
+            let err = merge(SiteConfig::default(), toml).unwrap_err();
+            assert!(
+                matches!(&err, ConfigError::Invalid { key, .. } if key == bad),
+                "{toml}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_wrong_types() {
+        let err = merge(SiteConfig::default(), "base_url = \"/x\"").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Invalid { key, .. } if key == "base_url"),
+            "{err}"
+        );
+        let err = merge(SiteConfig::default(), "[toc]\nmin_level = \"two\"").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Invalid { key, .. } if key == "toc.min_level"),
+            "{err}"
+        );
+        let err = merge(SiteConfig::default(), "[[menu]]\nname = \"Blog\"").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Invalid { key, .. } if key == "menu[0].url"),
+            "{err}"
+        );
+        assert!(matches!(
+            merge(SiteConfig::default(), "head = [\"link rel=icon\"]"),
+            Err(ConfigError::Invalid { .. })
+        ));
+        let err = merge(SiteConfig::default(), "excerpt_separator = \"\"").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Invalid { key, .. } if key == "excerpt_separator"),
+            "{err}"
+        );
+        assert!(matches!(
+            merge(SiteConfig::default(), "related_limit = -1"),
+            Err(ConfigError::Invalid { .. })
+        ));
+        assert!(matches!(
+            merge(SiteConfig::default(), "[feed]\nlimit = 0"),
+            Err(ConfigError::Invalid { .. })
+        ));
+        assert!(matches!(
+            merge(SiteConfig::default(), "fingerprint_images = \"all\""),
+            Err(ConfigError::Invalid { .. })
+        ));
+        assert!(matches!(
+            merge(SiteConfig::default(), "base_path = "),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn string_values_expand_environment_variables() {
+        let config = merge(
+            SiteConfig::default(),
+            "site_url = \"${DEPLOY_URL}\"\nbase_path = \"${BASE:-/docs}\"\n\
+             [[menu]]\nname = \"Home\"\nurl = \"${DEPLOY_URL}\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.site_url, "https://example.com");
+        assert_eq!(config.base_path, "/docs");
+        assert_eq!(config.menu[0].url, "https://example.com/");
+        let err = merge(SiteConfig::default(), "languages = [\"${LANG_CODE}\"]").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Invalid { key, .. } if key == "languages"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_reads_from_disk() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(CONFIG_FILE);
+        std::fs::write(&path, "output_dir = \"public\"\n").unwrap();
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!(config.output_dir, tmp.path().join("public"));
+        assert!(matches!(
+            SiteConfig::load(tmp.path().join("missing.toml")),
+            Err(ConfigError::Read { .. })
+        ));
+    }
+
+    #[test]
+    fn load_with_env_file_fills_in_variables() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(CONFIG_FILE);
+        let env_file = tmp.path().join(".env");
+        std::fs::write(&path, "site_url = \"${SHERWOOD_TEST_DOTENV_URL}\"\n").unwrap();
+        std::fs::write(
+            &env_file,
+            "SHERWOOD_TEST_DOTENV_URL=http://localhost:4000\n",
+        )
+        .unwrap();
+        let config = SiteConfig::load_with_env_file(&path, &env_file).unwrap();
+        assert_eq!(config.site_url, "http://localhost:4000");
+        // Without the file, the variable is undefined.
+        assert!(matches!(
+            SiteConfig::load(&path),
+            Err(ConfigError::Invalid { .. })
+        ));
+        assert!(matches!(
+            SiteConfig::load_with_env_file(&path, tmp.path().join("missing.env")),
+            Err(ConfigError::Read { .. })
+        ));
+        std::fs::write(&env_file, "not a pair\n").unwrap();
+        assert!(matches!(
+            SiteConfig::load_with_env_file(&path, &env_file),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
+}
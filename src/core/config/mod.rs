@@ -0,0 +1,578 @@
+use crate::core::compress::CompressConfig;
+use crate::core::content::images::ImageFingerprints;
+use crate::core::content::parser::{DEFAULT_EXCERPT_SEPARATOR, MarkdownFeatures};
+use crate::core::content::toc::{DEFAULT_TOC_LEVELS, toc_levels};
+use crate::core::content::transform::HtmlTransform;
+use crate::core::content::url_strategy::UrlStrategy;
+use crate::core::content::validation::ValidationRule;
+use crate::core::date::{DEFAULT_DATE_FORMAT, UtcOffset};
+use crate::core::feed::FeedConfig;
+use crate::core::footer::FooterConfig;
+use crate::core::head::HeadTag;
+use crate::core::nav::{DEFAULT_RELATED_LIMIT, MenuItem, TrailingSlash};
+use crate::core::redirect::Redirect;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+mod env;
+mod file;
+
+pub use file::{CONFIG_FILE, ConfigError};
+
+/// The stylesheet file name used unless [`SiteConfig::with_stylesheet`] says
+/// otherwise.
+pub const DEFAULT_STYLESHEET: &str = "style.css";
+
+/// The `<html lang>` value used unless [`SiteConfig::with_language`] says
+/// otherwise.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Build configuration: where content is read from and where the site is
+/// written.
+///
+/// Marked `#[non_exhaustive]` so new fields can be added without breaking
+/// downstream crates. Library users outside this crate cannot use struct
+/// literal syntax — construct via [`SiteConfig::new`] or [`SiteConfig::default`]
+/// and the `with_*` builder methods instead:
+///
+/// ```
+/// use sherwood::SiteConfig;
+/// let config = SiteConfig::new()
+///     .with_content_dir("src")
+///     .with_output_dir("out")
+///     .with_base_path("/sherwood");
+/// ```
+///
+/// Or read one from a `Sherwood.toml` with [`SiteConfig::load`], layering
+/// environment-specific files on top with [`SiteConfig::merge_file`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SiteConfig {
+    pub content_dir: PathBuf,
+    /// More content trees merged into [`content_dir`](Self::content_dir)'s,
+    /// each mapped onto the output root the same way — e.g. `blog/` and
+    /// `docs/` kept as separate inputs in a monorepo. Two files landing on
+    /// the same output path, from one tree or across trees, fail the build.
+    /// Empty by default.
+    pub extra_content_dirs: Vec<PathBuf>,
+    pub output_dir: PathBuf,
+    /// URL prefix for a site served from a non-root path, e.g. `/sherwood` for
+    /// `https://host/sherwood/`. Normalized to either `""` (served at the
+    /// domain root — the default) or a leading-slash, no-trailing-slash string
+    /// like `"/sherwood"`. Affects generated URLs only, never output paths.
+    pub base_path: String,
+    /// Whether generated page links end with `/`: `Auto` (the default)
+    /// keeps them as generated, `Always` and `Never` normalize every one,
+    /// configured menu URLs included. See [`TrailingSlash`].
+    pub trailing_slash: TrailingSlash,
+    /// Resolve [`Page::last_modified`](crate::Page::last_modified) from git
+    /// history (`git log -1 --format=%cI`) instead of the filesystem mtime.
+    /// Off by default: it spawns one git process per content file.
+    pub git_last_modified: bool,
+    /// Absolute origin the site is published at, e.g. `https://example.com`
+    /// (no trailing slash). Needed wherever a URL must work off-site — feed
+    /// links and ids. Empty by default, in which case such URLs stay
+    /// root-relative.
+    pub site_url: String,
+    /// Syndication feed settings. No feeds are written by default.
+    pub feed: FeedConfig,
+    /// Path of the site stylesheet relative to the output directory
+    /// (default [`DEFAULT_STYLESHEET`]). Templates link it through
+    /// [`PageContext::stylesheet`](crate::PageContext::stylesheet); writing
+    /// the file is the caller's job (the CLI writes its bundled stylesheet
+    /// asset here).
+    pub stylesheet: String,
+    /// Path of an optional print stylesheet relative to the output
+    /// directory, linked with `media="print"` through
+    /// [`PageContext::print_stylesheet`](crate::PageContext::print_stylesheet).
+    /// `None` (the default) links none.
+    pub print_stylesheet: Option<String>,
+    /// Hand-curated site navigation, exposed to templates as
+    /// [`PageContext::menu`](crate::PageContext::menu). Empty by default.
+    pub menu: Vec<MenuItem>,
+    /// How page dates are displayed ([`Page::date`](crate::Page::date)): a
+    /// strftime-style pattern (default [`DEFAULT_DATE_FORMAT`], `January 15,
+    /// 2024`) or `"relative"` for `3 days ago`-style dates.
+    pub date_format: String,
+    /// Fail the build on a frontmatter `date` that isn't a valid
+    /// `YYYY-MM-DD` date, with
+    /// [`PageError::InvalidDate`](crate::PageError::InvalidDate). Off by
+    /// default: such a date is shown as written and the page gets a
+    /// warning.
+    pub strict_dates: bool,
+    /// The UTC offset of frontmatter dates that don't give one (`date:
+    /// 2024-01-15`, or a time without a zone), for feed timestamps. UTC by
+    /// default.
+    pub timezone: UtcOffset,
+    /// The site's language, emitted as `<html lang="...">` (default
+    /// [`DEFAULT_LANGUAGE`]). A page's own language — its `lang`
+    /// frontmatter field or language directory — takes precedence; see
+    /// [`PageContext::lang`](crate::PageContext::lang).
+    pub language: String,
+    /// Language codes whose top-level content directories hold language
+    /// variants (`content/en/`, `content/fr/`). Empty by default: a
+    /// single-language site. See [`Page::lang`](crate::Page::lang).
+    pub languages: Vec<String>,
+    /// Extra `<link>` / `<meta>` / `<script>` tags for every page's `<head>`, exposed
+    /// pre-rendered as [`PageContext::head`](crate::PageContext::head).
+    pub head: Vec<HeadTag>,
+    /// Copyright and license lines for every page's footer, exposed
+    /// pre-rendered as [`PageContext::footer`](crate::PageContext::footer).
+    /// Empty by default.
+    pub footer: FooterConfig,
+    /// Shallowest heading level in tables of contents (default 2).
+    pub toc_min_level: u8,
+    /// Deepest heading level in tables of contents (default 3).
+    pub toc_max_level: u8,
+    /// Markdown extensions the build's markdown parser recognizes
+    /// ([`MarkdownFeatures`] defaults otherwise), unless the registered
+    /// parser was given its own with `MarkdownParser::with_features`.
+    pub markdown: MarkdownFeatures,
+    /// Precompression of output files into `.gz` / `.br` siblings. Off by
+    /// default; applied by `compress_output` (feature `compress`) after the
+    /// build.
+    pub compress: CompressConfig,
+    /// Add `loading="lazy"` and `decoding="async"` to every `<img>` in page
+    /// content, and `width` / `height` read from the file for local images.
+    pub lazy_images: bool,
+    /// Which `<img>` sources point at content-hashed copies of their files
+    /// (`cat.<hash>.png`), for immutable caching. Off by default; when on,
+    /// the build writes a fingerprinted copy of every image it copies.
+    pub fingerprint_images: ImageFingerprints,
+    /// Wrap each content `<table>` in a `<div class="table-wrapper">`, so
+    /// themes can let wide tables scroll sideways on narrow screens.
+    pub wrap_tables: bool,
+    /// Longest page excerpt, in characters of text; a longer one is cut at
+    /// a word boundary and ends in `…`. 0 (the default) keeps excerpts
+    /// whole.
+    pub excerpt_max_chars: usize,
+    /// Marker that ends a page's excerpt in markdown and HTML content
+    /// (default [`DEFAULT_EXCERPT_SEPARATOR`], `<!-- more -->`). A markdown
+    /// parser given its own with
+    /// [`MarkdownParser::with_excerpt_separator`](crate::MarkdownParser::with_excerpt_separator)
+    /// keeps that one.
+    pub excerpt_separator: String,
+    /// Keep each page's source text (after the frontmatter) as
+    /// [`Page::raw_content`](crate::Page::raw_content), for templates that
+    /// show or diff it. Off by default, since it holds every source in
+    /// memory next to its HTML.
+    pub raw_content: bool,
+    /// Include content under `_drafts` directories (off by default; `serve`
+    /// turns it on). Drafts keep their path: `blog/_drafts/post.md` is
+    /// `/blog/_drafts/post/`.
+    pub drafts: bool,
+    /// Include pages whose frontmatter sets `published: false` (off by
+    /// default, `serve` included). Such a page stays out even when its
+    /// directory is an included `_drafts`: either gate leaves it out.
+    pub unpublished: bool,
+    /// Run the whole build — parsing, rendering, feeds — but write nothing
+    /// to the output directory. [`BuildReport::outputs`](crate::BuildReport::outputs)
+    /// lists what would have been written. Off by default.
+    pub dry_run: bool,
+    /// Most threads loading and parsing content files at once. 0 (the
+    /// default) means one per CPU; 1 loads them one at a time, for slow
+    /// disks or network filesystems where parallel reads thrash. Rendering
+    /// runs the caller's render closure, one page at a time.
+    pub max_concurrency: usize,
+    /// The most pages listed in
+    /// [`PageContext::related`](crate::PageContext::related) (default
+    /// [`DEFAULT_RELATED_LIMIT`]); 0 turns related pages off.
+    pub related_limit: usize,
+    /// Chooses page URLs in place of the path-mirroring default. Library
+    /// only; see [`UrlStrategy`].
+    pub url_strategy: Option<Arc<dyn UrlStrategy>>,
+    /// Rewrites of each page's rendered HTML, applied in order. Library
+    /// only; see [`HtmlTransform`].
+    pub html_transforms: Vec<Arc<dyn HtmlTransform>>,
+    /// Required frontmatter fields by path glob. A page missing one fails
+    /// to load with [`PageError::MissingFields`](crate::PageError::MissingFields).
+    pub validation: Vec<ValidationRule>,
+    /// Redirect rules: the build writes a stub page at each `from` path and
+    /// `serve` answers it with an HTTP redirect. Empty by default.
+    pub redirects: Vec<Redirect>,
+}
+
+impl SiteConfig {
+    /// A config with the default directories (`content/` → `_site/`) and no
+    /// base path. Equivalent to [`SiteConfig::default`]; chain `with_*` methods
+    /// to override individual fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content source directory.
+    pub fn with_content_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.content_dir = dir.into();
+        self
+    }
+
+    /// Set the extra content directories merged into the site (see
+    /// [`SiteConfig::extra_content_dirs`]).
+    pub fn with_extra_content_dirs<P: Into<PathBuf>>(
+        mut self,
+        dirs: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.extra_content_dirs = dirs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Every content root: [`content_dir`](Self::content_dir), then the
+    /// [`extra_content_dirs`](Self::extra_content_dirs).
+    pub(crate) fn content_roots(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.content_dir.as_path())
+            .chain(self.extra_content_dirs.iter().map(PathBuf::as_path))
+    }
+
+    /// `path` relative to the content root holding it, or `None` when it
+    /// lies under none of them.
+    pub(crate) fn content_relative<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        self.content_roots()
+            .find_map(|root| path.strip_prefix(root).ok())
+    }
+
+    /// Set the output directory.
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = dir.into();
+        self
+    }
+
+    /// Set the URL base path for serving the site from a subdirectory. The
+    /// value is normalized: surrounding slashes are trimmed and a single
+    /// leading slash is added, so `"sherwood"`, `"/sherwood/"`, and
+    /// `"/sherwood"` all become `"/sherwood"`. Empty or `"/"` clears it
+    /// (root-served).
+    pub fn with_base_path(mut self, path: impl AsRef<str>) -> Self {
+        self.base_path = normalize_base_path(path.as_ref());
+        self
+    }
+
+    /// Choose whether generated page links end with `/`.
+    pub fn with_trailing_slash(mut self, mode: TrailingSlash) -> Self {
+        self.trailing_slash = mode;
+        self
+    }
+
+    /// Take each page's last-modified timestamp from git history, falling back
+    /// to the file's mtime when it isn't tracked or git is unavailable.
+    pub fn with_git_last_modified(mut self, enabled: bool) -> Self {
+        self.git_last_modified = enabled;
+        self
+    }
+
+    /// Set the absolute site origin, e.g. `https://example.com`. A trailing
+    /// slash is trimmed.
+    pub fn with_site_url(mut self, url: impl AsRef<str>) -> Self {
+        self.site_url = url.as_ref().trim().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the stylesheet's output file name, e.g. `styles.css` or
+    /// `css/main.css`. Leading slashes are trimmed.
+    pub fn with_stylesheet(mut self, name: impl AsRef<str>) -> Self {
+        self.stylesheet = name.as_ref().trim().trim_start_matches('/').to_string();
+        self
+    }
+
+    /// Set the print stylesheet's output file name, e.g. `print.css`.
+    /// Leading slashes are trimmed.
+    pub fn with_print_stylesheet(mut self, name: impl AsRef<str>) -> Self {
+        self.print_stylesheet = Some(name.as_ref().trim().trim_start_matches('/').to_string());
+        self
+    }
+
+    /// Set the site menu. Items are ordered by
+    /// [`weight`](MenuItem::weight), then by their order here.
+    pub fn with_menu(mut self, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        self.menu = items.into_iter().collect();
+        self
+    }
+
+    /// Set the date display format, e.g. `"%d %b %Y"` or `"relative"`. See
+    /// [`date_format`](Self::date_format).
+    pub fn with_date_format(mut self, format: impl Into<String>) -> Self {
+        self.date_format = format.into();
+        self
+    }
+
+    /// Reject pages whose `date` doesn't parse, instead of warning.
+    pub fn with_strict_dates(mut self, enabled: bool) -> Self {
+        self.strict_dates = enabled;
+        self
+    }
+
+    /// Set the offset assumed for dates without one, e.g.
+    /// `"+02:00".parse()?`.
+    pub fn with_timezone(mut self, offset: UtcOffset) -> Self {
+        self.timezone = offset;
+        self
+    }
+
+    /// Set the site's language, e.g. `"de"`. An empty code restores
+    /// [`DEFAULT_LANGUAGE`].
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        let language = language.into();
+        self.language = if language.trim().is_empty() {
+            DEFAULT_LANGUAGE.to_string()
+        } else {
+            language.trim().to_string()
+        };
+        self
+    }
+
+    /// Set the site's languages, e.g. `["en", "fr"]`. Pages under a
+    /// matching top-level directory get that language, and pages sharing a
+    /// `translation_key` frontmatter value are linked as translations.
+    pub fn with_languages<S: Into<String>>(mut self, langs: impl IntoIterator<Item = S>) -> Self {
+        self.languages = langs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the extra head tags, emitted in this order. Tags that fail
+    /// [`HeadTag::validate`] are skipped.
+    pub fn with_head(mut self, tags: impl IntoIterator<Item = HeadTag>) -> Self {
+        self.head = tags.into_iter().collect();
+        self
+    }
+
+    /// Set the footer configuration.
+    pub fn with_footer(mut self, footer: FooterConfig) -> Self {
+        self.footer = footer;
+        self
+    }
+
+    /// Set the heading levels tables of contents include, e.g. `(2, 4)` for
+    /// `<h2>`–`<h4>`. Bounds are clamped to 1–6 and an inverted range is
+    /// swapped. Pages can override either bound with `toc_min_level` /
+    /// `toc_max_level` frontmatter.
+    pub fn with_toc_levels(mut self, min: u8, max: u8) -> Self {
+        let (range, _) = toc_levels(i64::from(min), i64::from(max));
+        (self.toc_min_level, self.toc_max_level) = (*range.start(), *range.end());
+        self
+    }
+
+    /// Set the markdown extensions (see [`SiteConfig::markdown`]).
+    pub fn with_markdown(mut self, markdown: MarkdownFeatures) -> Self {
+        self.markdown = markdown;
+        self
+    }
+
+    /// Set the precompression configuration.
+    pub fn with_compress(mut self, compress: CompressConfig) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Set the feed configuration.
+    pub fn with_feed(mut self, feed: FeedConfig) -> Self {
+        self.feed = feed;
+        self
+    }
+
+    /// Toggle lazy-loading and dimension attributes on content images.
+    pub fn with_lazy_images(mut self, enabled: bool) -> Self {
+        self.lazy_images = enabled;
+        self
+    }
+
+    /// Choose which image sources get fingerprinted file names.
+    pub fn with_fingerprint_images(mut self, mode: ImageFingerprints) -> Self {
+        self.fingerprint_images = mode;
+        self
+    }
+
+    /// Cap excerpts at `max_chars` characters of text; 0 turns the cap
+    /// off.
+    pub fn with_excerpt_max_chars(mut self, max_chars: usize) -> Self {
+        self.excerpt_max_chars = max_chars;
+        self
+    }
+
+    /// Set the marker that ends page excerpts, e.g. `<!--more-->`. An empty
+    /// marker matches nothing, so no page gets an excerpt.
+    pub fn with_excerpt_separator(mut self, separator: impl Into<String>) -> Self {
+        self.excerpt_separator = separator.into();
+        self
+    }
+
+    /// Keep each page's source text on [`Page::raw_content`](crate::Page::raw_content).
+    pub fn with_raw_content(mut self, enabled: bool) -> Self {
+        self.raw_content = enabled;
+        self
+    }
+
+    /// Toggle the scroll wrapper around content tables.
+    pub fn with_wrap_tables(mut self, enabled: bool) -> Self {
+        self.wrap_tables = enabled;
+        self
+    }
+
+    /// Include or leave out content under `_drafts` directories.
+    pub fn with_drafts(mut self, enabled: bool) -> Self {
+        self.drafts = enabled;
+        self
+    }
+
+    /// Include or leave out pages with `published: false` frontmatter.
+    pub fn with_unpublished(mut self, enabled: bool) -> Self {
+        self.unpublished = enabled;
+        self
+    }
+
+    /// Build without writing any output (see [`SiteConfig::dry_run`]).
+    pub fn with_dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Cap the threads loading content (see [`SiteConfig::max_concurrency`]).
+    pub fn with_max_concurrency(mut self, threads: usize) -> Self {
+        self.max_concurrency = threads;
+        self
+    }
+
+    /// [`max_concurrency`](Self::max_concurrency) resolved: the CPU count
+    /// when unset, and never below 1.
+    pub(crate) fn concurrency(&self) -> usize {
+        match self.max_concurrency {
+            0 => std::thread::available_parallelism().map_or(1, usize::from),
+            n => n,
+        }
+    }
+
+    /// Set how many related pages each page lists (0 for none).
+    pub fn with_related_limit(mut self, limit: usize) -> Self {
+        self.related_limit = limit;
+        self
+    }
+
+    /// Set the required-frontmatter rules.
+    pub fn with_validation(mut self, rules: impl IntoIterator<Item = ValidationRule>) -> Self {
+        self.validation = rules.into_iter().collect();
+        self
+    }
+
+    /// Set the redirect rules.
+    pub fn with_redirects(mut self, redirects: impl IntoIterator<Item = Redirect>) -> Self {
+        self.redirects = redirects.into_iter().collect();
+        self
+    }
+
+    /// Choose page URLs (and so output paths) with a custom strategy.
+    pub fn with_url_strategy(mut self, strategy: impl UrlStrategy + 'static) -> Self {
+        self.url_strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Add a rewrite of each page's rendered HTML, run after any added
+    /// before it.
+    pub fn with_html_transform(mut self, transform: impl HtmlTransform + 'static) -> Self {
+        self.html_transforms.push(Arc::new(transform));
+        self
+    }
+}
+
+/// Normalize a raw base path into `""` (root) or `"/segment[/segment...]"`.
+fn normalize_base_path(raw: &str) -> String {
+    let trimmed = raw.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{trimmed}")
+    }
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            content_dir: PathBuf::from("content"),
+            extra_content_dirs: Vec::new(),
+            output_dir: PathBuf::from("_site"),
+            base_path: String::new(),
+            trailing_slash: TrailingSlash::Auto,
+            git_last_modified: false,
+            site_url: String::new(),
+            feed: FeedConfig::default(),
+            stylesheet: DEFAULT_STYLESHEET.to_string(),
+            print_stylesheet: None,
+            menu: Vec::new(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            strict_dates: false,
+            timezone: UtcOffset::UTC,
+            language: DEFAULT_LANGUAGE.to_string(),
+            languages: Vec::new(),
+            head: Vec::new(),
+            footer: FooterConfig::default(),
+            toc_min_level: DEFAULT_TOC_LEVELS.0,
+            toc_max_level: DEFAULT_TOC_LEVELS.1,
+            markdown: MarkdownFeatures::default(),
+            compress: CompressConfig::default(),
+            lazy_images: false,
+            fingerprint_images: ImageFingerprints::Off,
+            wrap_tables: false,
+            excerpt_max_chars: 0,
+            excerpt_separator: DEFAULT_EXCERPT_SEPARATOR.to_string(),
+            raw_content: false,
+            drafts: false,
+            unpublished: false,
+            dry_run: false,
+            max_concurrency: 0,
+            related_limit: DEFAULT_RELATED_LIMIT,
+            url_strategy: None,
+            html_transforms: Vec::new(),
+            validation: Vec::new(),
+            redirects: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_path_normalizes() {
+        let cases = [
+            ("", ""),
+            ("/", ""),
+            ("sherwood", "/sherwood"),
+            ("/sherwood", "/sherwood"),
+            ("/sherwood/", "/sherwood"),
+            ("sherwood/", "/sherwood"),
+            ("  /docs/  ", "/docs"),
+            ("a/b", "/a/b"),
+        ];
+        for (input, want) in cases {
+            assert_eq!(
+                SiteConfig::new().with_base_path(input).base_path,
+                want,
+                "input {input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn site_url_trims_trailing_slash() {
+        assert_eq!(
+            SiteConfig::new()
+                .with_site_url("https://example.com/")
+                .site_url,
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn stylesheet_defaults_and_trims() {
+        assert_eq!(SiteConfig::default().stylesheet, "style.css");
+        assert_eq!(
+            SiteConfig::new()
+                .with_stylesheet("/css/main.css")
+                .stylesheet,
+            "css/main.css"
+        );
+    }
+
+    #[test]
+    fn default_base_path_is_empty() {
+        assert_eq!(SiteConfig::default().base_path, "");
+    }
+}
@@ -0,0 +1,134 @@
+//! Opt-in length limit for page excerpts, for uniform card layouts.
+//!
+//! Excerpts are rendered HTML by the time the limit applies, so truncation
+//! counts only text (an entity like `&amp;` is one character), cuts at the
+//! last word boundary, appends `…` inside the innermost open element, and
+//! closes whatever elements the cut left open.
+
+/// Elements that never have a closing tag.
+const VOID_ELEMENTS: [&str; 8] = ["br", "hr", "img", "input", "link", "meta", "source", "wbr"];
+
+/// `html` cut to at most `max_chars` characters of text, at a word boundary,
+/// with `…` appended. Shorter excerpts (and `max_chars == 0`) come back
+/// unchanged.
+pub(crate) fn truncate_excerpt(html: &str, max_chars: usize) -> String {
+    if max_chars == 0 {
+        return html.to_string();
+    }
+    let mut open: Vec<&str> = Vec::new();
+    // The last whitespace in text: where to cut, and the elements open there.
+    let mut word_break: Option<(usize, Vec<&str>)> = None;
+    let mut chars = 0;
+    let mut in_space = false;
+    let mut pos = 0;
+    while pos < html.len() {
+        let rest = &html[pos..];
+        if rest.starts_with("<!--") {
+            pos += rest.find("-->").map_or(rest.len(), |end| end + 3);
+            continue;
+        }
+        if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = rest[1..end].strip_suffix('>').unwrap_or(&rest[1..end]);
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = tag_name(name);
+                if let Some(at) = open.iter().rposition(|&n| n == name) {
+                    open.truncate(at);
+                }
+            } else {
+                let name = tag_name(tag);
+                if !tag.ends_with('/') && !VOID_ELEMENTS.contains(&name) && !name.is_empty() {
+                    open.push(name);
+                }
+            }
+            pos += end;
+            continue;
+        }
+        let c = rest.chars().next().unwrap_or_default();
+        if c.is_whitespace() {
+            // A run of whitespace reads as one space.
+            if !in_space {
+                chars += 1;
+                word_break = Some((pos, open.clone()));
+            }
+            in_space = true;
+            pos += c.len_utf8();
+            continue;
+        }
+        if chars >= max_chars {
+            let (cut, open) = word_break.unwrap_or((pos, open));
+            let mut out = html[..cut].trim_end().to_string();
+            out.push('…');
+            for name in open.iter().rev() {
+                out.push_str(&format!("</{name}>"));
+            }
+            return out;
+        }
+        chars += 1;
+        in_space = false;
+        pos += entity_len(rest).unwrap_or(c.len_utf8());
+    }
+    html.to_string()
+}
+
+/// Byte length of the character reference `text` starts with (`&amp;`,
+/// `&#8230;`), if it starts with one.
+fn entity_len(text: &str) -> Option<usize> {
+    let body = text.strip_prefix('&')?;
+    let end = body.find(';').filter(|&end| end > 0 && end <= 10)?;
+    body[..end]
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '#')
+        .then_some(end + 2)
+}
+
+/// The element name at the start of a tag's text, lowercase letters and
+/// digits only (`p class="x"` → `p`).
+fn tag_name(tag: &str) -> &str {
+    let end = tag
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_excerpts_and_zero_limit_are_unchanged() {
+        let html = "<p>Short and sweet.</p>\n";
+        assert_eq!(truncate_excerpt(html, 100), html);
+        assert_eq!(truncate_excerpt(html, 17), html);
+        assert_eq!(truncate_excerpt(html, 0), html);
+    }
+
+    #[test]
+    fn cuts_at_a_word_boundary_and_closes_tags() {
+        let html = "<p>The quick <em>brown fox</em> jumps over the lazy dog.</p>\n";
+        assert_eq!(
+            truncate_excerpt(html, 15),
+            "<p>The quick <em>brown…</em></p>"
+        );
+        assert_eq!(truncate_excerpt(html, 12), "<p>The quick…</p>");
+    }
+
+    #[test]
+    fn counts_entities_and_multibyte_characters_once() {
+        assert_eq!(
+            truncate_excerpt("<p>Fish &amp; chips, naïve café crêpes</p>", 26),
+            "<p>Fish &amp; chips, naïve café…</p>"
+        );
+        // No word boundary at all: cut mid-word, on a character boundary.
+        assert_eq!(truncate_excerpt("<p>ééééé</p>", 3), "<p>ééé…</p>");
+    }
+
+    #[test]
+    fn void_elements_and_comments_are_not_left_open() {
+        let html = "<p>One<br>two <img src=\"x.png\"> three<!-- note --> four</p>";
+        assert_eq!(
+            truncate_excerpt(html, 14),
+            "<p>One<br>two <img src=\"x.png\"> three<!-- note -->…</p>"
+        );
+    }
+}
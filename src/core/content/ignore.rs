@@ -0,0 +1,205 @@
+//! `.sherwoodignore`: gitignore-style exclusions for content discovery.
+//!
+//! The file lives at the content root and holds one pattern per line,
+//! matched against paths relative to that root:
+//!
+//! - blank lines and `#` comments are skipped;
+//! - `*` matches within a path segment, `?` one character, `**` across
+//!   segments (`drafts/**`, `**/scratch.md`);
+//! - a pattern containing a `/` (other than a trailing one) is anchored to
+//!   the content root; otherwise it matches at any depth (`node_modules`);
+//! - a trailing `/` matches directories only (`drafts/`);
+//! - a leading `!` re-includes what an earlier pattern excluded. The last
+//!   matching pattern wins. As in git, a file inside an excluded directory
+//!   can't be re-included — the directory is never walked.
+//!
+//! Without the file, discovery is unchanged.
+//!
+//! Separately, any directory named [`DRAFTS_DIR`] (`_drafts`, at any depth)
+//! is skipped unless [`SiteConfig::drafts`](crate::SiteConfig::drafts) is
+//! set. The two combine: a draft must pass both, so `.sherwoodignore` still
+//! excludes files inside an included `_drafts`, and a `!` pattern can't pull
+//! drafts into a build that leaves them out.
+
+use std::path::{Component, Path};
+
+/// File name of the ignore file, at the content root. The file itself is
+/// never treated as content.
+pub(crate) const IGNORE_FILE: &str = ".sherwoodignore";
+
+/// Directory name marking its contents as drafts: left out of builds unless
+/// [`SiteConfig::with_drafts`](crate::SiteConfig::with_drafts) includes them.
+pub const DRAFTS_DIR: &str = "_drafts";
+
+#[derive(Debug, Default)]
+pub(crate) struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    glob: Vec<char>,
+    negate: bool,
+    dir_only: bool,
+}
+
+impl IgnoreRules {
+    /// Read `<content_dir>/.sherwoodignore`. A missing file yields no rules.
+    pub(crate) fn load(content_dir: &Path) -> std::io::Result<Self> {
+        match std::fs::read_to_string(content_dir.join(IGNORE_FILE)) {
+            Ok(text) => Ok(Self::parse(&text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn parse(text: &str) -> Self {
+        let rules = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (negate, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                if line.is_empty() {
+                    return None;
+                }
+                Some(Rule {
+                    glob: compile(line),
+                    negate,
+                    dir_only,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Whether `relative` (a path under the content root) is excluded.
+    pub(crate) fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        let path = slash_path(relative);
+        if path.is_empty() {
+            return false;
+        }
+        if !is_dir && relative == Path::new(IGNORE_FILE) {
+            return true;
+        }
+        let mut ignored = false;
+        for rule in &self.rules {
+            if (!rule.dir_only || is_dir) && glob_match(&rule.glob, &path) {
+                ignored = !rule.negate;
+            }
+        }
+        ignored
+    }
+}
+
+/// Whether `relative` (a file path under the content root) matches a single
+/// pattern in `.sherwoodignore` syntax, minus `!` and the trailing-`/`
+/// directory marker. Shared with frontmatter validation rules.
+pub(crate) fn path_matches(pattern: &str, relative: &Path) -> bool {
+    glob_match(&compile(pattern), &slash_path(relative))
+}
+
+/// Anchored patterns match from the root; the rest may match at any depth,
+/// as if prefixed with `**/`.
+fn compile(pattern: &str) -> Vec<char> {
+    match pattern.strip_prefix('/') {
+        Some(rest) => rest.chars().collect(),
+        None if pattern.contains('/') => pattern.chars().collect(),
+        None => format!("**/{pattern}").chars().collect(),
+    }
+}
+
+/// `relative`'s normal components joined with `/`, whatever the platform.
+fn slash_path(relative: &Path) -> Vec<char> {
+    relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+        .chars()
+        .collect()
+}
+
+/// Match a gitignore-style glob against a `/`-separated path.
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', '/', rest @ ..] => {
+            // Zero or more whole segments.
+            glob_match(rest, path)
+                || path
+                    .iter()
+                    .enumerate()
+                    .any(|(i, &c)| c == '/' && glob_match(rest, &path[i + 1..]))
+        }
+        ['*', '*', ..] => true,
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != '/')
+            .any(|i| glob_match(rest, &path[i..])),
+        ['?', rest @ ..] => matches!(path, [c, tail @ ..] if *c != '/' && glob_match(rest, tail)),
+        [p, rest @ ..] => matches!(path, [c, tail @ ..] if c == p && glob_match(rest, tail)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(rules: &str, path: &str, is_dir: bool) -> bool {
+        IgnoreRules::parse(rules).is_ignored(Path::new(path), is_dir)
+    }
+
+    #[test]
+    fn unanchored_name_matches_at_any_depth() {
+        assert!(ignored("node_modules", "node_modules", true));
+        assert!(ignored("node_modules", "blog/node_modules", true));
+        assert!(ignored("*.tmp", "blog/notes.tmp", false));
+        assert!(!ignored("*.tmp", "blog/notes.md", false));
+    }
+
+    #[test]
+    fn slash_anchors_to_content_root() {
+        assert!(ignored("/drafts", "drafts", true));
+        assert!(!ignored("/drafts", "blog/drafts", true));
+        assert!(ignored("blog/*.md", "blog/post.md", false));
+        assert!(!ignored("blog/*.md", "blog/2026/post.md", false));
+        assert!(ignored("blog/**/*.md", "blog/2026/post.md", false));
+    }
+
+    #[test]
+    fn trailing_slash_matches_directories_only() {
+        assert!(ignored("scratch/", "scratch", true));
+        assert!(!ignored("scratch/", "scratch", false));
+    }
+
+    #[test]
+    fn negation_re_includes_and_last_match_wins() {
+        let rules = "*.md\n!keep.md\n# comment\n";
+        assert!(ignored(rules, "post.md", false));
+        assert!(!ignored(rules, "keep.md", false));
+        assert!(ignored("!keep.md\n*.md", "keep.md", false));
+    }
+
+    #[test]
+    fn ignore_file_itself_is_skipped() {
+        assert!(ignored("", IGNORE_FILE, false));
+        assert!(!ignored("", "index.md", false));
+    }
+
+    #[test]
+    fn missing_file_loads_no_rules() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let rules = IgnoreRules::load(tmp.path()).unwrap();
+        assert!(!rules.is_ignored(Path::new("anything.md"), false));
+    }
+}
@@ -0,0 +1,455 @@
+//! Opt-in `<img>` hints for faster, steadier page loads: `loading="lazy"` and
+//! `decoding="async"` on every image, plus `width` / `height` for local
+//! images, so the browser reserves their space before they arrive (no layout
+//! shift).
+//!
+//! Works on rendered HTML, so images from any parser (or raw HTML in
+//! markdown) are covered. Attributes the author already wrote are kept.
+//! Dimensions are read from the file's header — PNG, GIF, JPEG and WebP;
+//! anything else, remote images, and files that don't exist get no size.
+//! Only the header is read, once per file per page.
+//!
+//! Also opt-in: [`ImageFingerprints`], which points local image `src`s at
+//! content-hashed copies of the files so they can be cached forever.
+
+use crate::core::config::SiteConfig;
+use crate::core::fingerprint::fingerprinted_name;
+use crate::core::nav::{is_external, normalize_segments};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Bytes first read from an image to find its size. Every format's size
+/// sits within them, except a JPEG's behind large metadata segments, for
+/// which the read doubles until the size turns up.
+const HEADER_BYTES: usize = 4096;
+
+/// Extensions (lowercase) of the files [`ImageFingerprints`] renames.
+const IMAGE_EXTENSIONS: [&str; 9] = [
+    "avif", "bmp", "gif", "ico", "jpeg", "jpg", "png", "svg", "webp",
+];
+
+/// Which `<img>` sources get content-hashed file names
+/// ([`SiteConfig::fingerprint_images`]): `cat.png` becomes
+/// `cat.<hash>.png`, and the build writes that copy next to the original.
+/// Only images that resolve to a file in the content tree are renamed;
+/// remote images never are.
+///
+/// ```
+/// use sherwood::{ImageFingerprints, SiteConfig};
+/// let config = SiteConfig::new().with_fingerprint_images(ImageFingerprints::Relative);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ImageFingerprints {
+    /// Keep every `src` as written (the default).
+    #[default]
+    Off,
+    /// Page-relative sources only, such as `cat.png` or `./img/cat.png`.
+    Relative,
+    /// Page-relative and root-absolute (`/img/logo.png`) sources.
+    Local,
+}
+
+impl FromStr for ImageFingerprints {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(ImageFingerprints::Off),
+            "relative" => Ok(ImageFingerprints::Relative),
+            "local" => Ok(ImageFingerprints::Local),
+            other => Err(format!(
+                "unknown image fingerprinting `{other}` (expected off, relative or local)"
+            )),
+        }
+    }
+}
+
+/// `html` with hints added to each `<img>`. `page_url` is the page's
+/// canonical URL, which relative `src`s resolve against as in the browser.
+pub(crate) fn add_image_hints(html: &str, page_url: &str, config: &SiteConfig) -> String {
+    let mut sizes: HashMap<PathBuf, Option<(u32, u32)>> = HashMap::new();
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(at) = rest.find("<img") {
+        let after = &rest[at + 4..];
+        let is_img = after.starts_with(|c: char| c.is_whitespace() || c == '/' || c == '>');
+        let Some(end) = after.find('>').filter(|_| is_img) else {
+            out.push_str(&rest[..at + 4]);
+            rest = after;
+            continue;
+        };
+        out.push_str(&rest[..at]);
+        let tag = &after[..end];
+        let body = tag.trim_end();
+        let (body, self_closing) = match body.strip_suffix('/') {
+            Some(b) => (b.trim_end(), true),
+            None => (body, false),
+        };
+        out.push_str("<img");
+        out.push_str(body);
+        if tag_attr(tag, "width").is_none()
+            && tag_attr(tag, "height").is_none()
+            && let Some((width, height)) = tag_attr(tag, "src")
+                .and_then(|src| local_file(src, page_url, config))
+                .and_then(|file| *sizes.entry(file).or_insert_with_key(|file| file_size(file)))
+        {
+            out.push_str(&format!(" width=\"{width}\" height=\"{height}\""));
+        }
+        if tag_attr(tag, "loading").is_none() {
+            out.push_str(" loading=\"lazy\"");
+        }
+        if tag_attr(tag, "decoding").is_none() {
+            out.push_str(" decoding=\"async\"");
+        }
+        out.push_str(if self_closing { " />" } else { ">" });
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `html` with each local image `src` that `config.fingerprint_images`
+/// covers pointed at the file's fingerprinted copy. The query string and
+/// fragment are kept; remote, missing and non-image files are left alone.
+pub(crate) fn fingerprint_images(html: &str, page_url: &str, config: &SiteConfig) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(at) = rest.find("<img") {
+        let after = &rest[at + 4..];
+        let is_img = after.starts_with(|c: char| c.is_whitespace() || c == '/' || c == '>');
+        let end = after.find('>').filter(|_| is_img).unwrap_or(0);
+        let tag = &after[..end];
+        out.push_str(&rest[..at + 4]);
+        if let Some(src) = tag_attr(tag, "src").filter(|src| !src.is_empty())
+            && let Some(renamed) = fingerprinted_src(src, page_url, config)
+        {
+            // `src` borrows from `tag`, so its address gives its offset.
+            let start = src.as_ptr() as usize - tag.as_ptr() as usize;
+            out.push_str(&tag[..start]);
+            out.push_str(&renamed);
+            out.push_str(&tag[start + src.len()..]);
+        } else {
+            out.push_str(tag);
+        }
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `src` with its file name fingerprinted, if the mode covers it and it
+/// names a local image.
+fn fingerprinted_src(src: &str, page_url: &str, config: &SiteConfig) -> Option<String> {
+    let covered = match config.fingerprint_images {
+        ImageFingerprints::Off => false,
+        ImageFingerprints::Relative => !src.starts_with('/'),
+        ImageFingerprints::Local => true,
+    };
+    if !covered {
+        return None;
+    }
+    let file = local_file(src, page_url, config).filter(|file| is_image(file))?;
+    let bytes = std::fs::read(file).ok()?;
+    let (path, suffix) = src.split_at(src.find(['?', '#']).unwrap_or(src.len()));
+    Some(format!("{}{suffix}", fingerprinted_name(path, &bytes)))
+}
+
+/// Whether `path` has an image extension [`ImageFingerprints`] renames.
+pub(crate) fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// The value of attribute `name` (case-insensitive) in a tag's text (after
+/// `<`, before `>`, with or without the element name). Attributes are read
+/// one after another, so a name inside another attribute's value
+/// (`alt="src=x"`) never matches. Handles double-, single-, and unquoted
+/// values; an attribute without one (`<img ismap>`) has the empty value.
+pub(crate) fn tag_attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let is_space = |c: char| c.is_whitespace() || c == '/';
+    // The element name, if the tag starts with one.
+    let mut rest = tag.trim_start_matches(|c: char| !is_space(c));
+    loop {
+        rest = rest.trim_start_matches(is_space);
+        if rest.is_empty() {
+            return None;
+        }
+        // A name runs to whitespace, `/` or `=`; a stray leading `=` is
+        // part of it, as in the HTML tokenizer.
+        let first = rest.chars().next().map_or(0, char::len_utf8);
+        let name_end = rest[first..]
+            .find(|c: char| is_space(c) || c == '=')
+            .map_or(rest.len(), |i| first + i);
+        let attr = &rest[..name_end];
+        rest = &rest[name_end..];
+        let value = match rest.trim_start().strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, next) = match after.chars().next() {
+                    Some(q @ ('"' | '\'')) => {
+                        let quoted = &after[1..];
+                        let end = quoted.find(q).unwrap_or(quoted.len());
+                        (&quoted[..end], quoted.get(end + 1..).unwrap_or_default())
+                    }
+                    _ => after.split_at(after.find(char::is_whitespace).unwrap_or(after.len())),
+                };
+                rest = next;
+                value
+            }
+            None => &rest[..0],
+        };
+        if attr.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+/// The content-tree file an image `src` points at, if it's local. Files in
+/// the content tree are copied to the same path under the output
+/// directory, so a URL maps straight back onto a content root (the first
+/// one holding the file).
+fn local_file(src: &str, page_url: &str, config: &SiteConfig) -> Option<PathBuf> {
+    let src = src.replace("&amp;", "&");
+    let src = src.split(['?', '#']).next().unwrap_or_default();
+    if src.is_empty() || is_external(src) {
+        return None;
+    }
+    let url = match src.strip_prefix('/') {
+        Some(root_relative) => root_relative.to_string(),
+        None => format!("{}{src}", page_url.trim_start_matches('/')),
+    };
+    // Drop `.` / `..` so a URL can't reach outside the content directory.
+    let segments = normalize_segments(&url);
+    config.content_roots().find_map(|root| {
+        let mut path = root.to_path_buf();
+        path.extend(&segments);
+        path.is_file().then_some(path)
+    })
+}
+
+/// Pixel `(width, height)` of the image file at `path`, reading only as much
+/// of it as the header needs.
+fn file_size(path: &Path) -> Option<(u32, u32)> {
+    let mut file = File::open(path).ok()?;
+    let mut bytes = Vec::new();
+    let mut want = HEADER_BYTES;
+    loop {
+        let missing = (want - bytes.len()) as u64;
+        (&mut file).take(missing).read_to_end(&mut bytes).ok()?;
+        if let Some(size) = image_size(&bytes) {
+            return Some(size);
+        }
+        // At the end of the file, or not a JPEG: reading on won't help.
+        if bytes.len() < want || !bytes.starts_with(&[0xff, 0xd8]) {
+            return None;
+        }
+        want *= 2;
+    }
+}
+
+/// Pixel `(width, height)` from a PNG, GIF, JPEG or WebP header.
+fn image_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| {
+        bytes
+            .get(at..at + 2)
+            .map(|b| (u32::from(b[0]) << 8) | u32::from(b[1]))
+    };
+    let le16 = |at: usize| {
+        bytes
+            .get(at..at + 2)
+            .map(|b| u32::from(b[0]) | (u32::from(b[1]) << 8))
+    };
+    let le24 = |at: usize| {
+        bytes
+            .get(at..at + 3)
+            .map(|b| u32::from(b[0]) | (u32::from(b[1]) << 8) | (u32::from(b[2]) << 16))
+    };
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+        return Some((width, height));
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some((le16(6)?, le16(8)?));
+    }
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(&b"WEBP"[..]) {
+        return match bytes.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let b: Vec<u32> = bytes.get(21..25)?.iter().map(|&b| u32::from(b)).collect();
+                let (b0, b1, b2, b3) = (b[0], b[1], b[2], b[3]);
+                let width = 1 + (((b1 & 0x3f) << 8) | b0);
+                let height = 1 + (((b3 & 0x0f) << 10) | (b2 << 2) | ((b1 & 0xc0) >> 6));
+                Some((width, height))
+            }
+            b"VP8X" => Some((1 + le24(24)?, 1 + le24(27)?)),
+            _ => None,
+        };
+    }
+    if bytes.starts_with(&[0xff, 0xd8]) {
+        // Walk the marker segments to the first start-of-frame.
+        let mut pos = 2;
+        loop {
+            while *bytes.get(pos)? != 0xff {
+                pos += 1;
+            }
+            while *bytes.get(pos)? == 0xff {
+                pos += 1;
+            }
+            let marker = *bytes.get(pos)?;
+            pos += 1;
+            match marker {
+                0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                    return Some((be16(pos + 5)?, be16(pos + 3)?));
+                }
+                // Markers without a length field.
+                0x01 | 0xd0..=0xd9 => {}
+                _ => pos += usize::try_from(be16(pos)?).ok()?,
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend(width.to_be_bytes());
+        bytes.extend(height.to_be_bytes());
+        bytes.extend([8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn reads_header_dimensions() {
+        assert_eq!(image_size(&png(640, 480)), Some((640, 480)));
+        assert_eq!(image_size(b"GIF89a\x20\x03\x58\x02"), Some((800, 600)));
+        // SOI, an APP0 segment to skip, then SOF0 (precision 8, 200 × 100).
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x00,
+            0x64, 0x00, 0xc8,
+        ];
+        assert_eq!(image_size(&jpeg), Some((200, 100)));
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\0\0\0\0\0\0\0\0".to_vec();
+        webp.extend([0x7f, 0x02, 0x00, 0xdf, 0x01, 0x00]);
+        assert_eq!(image_size(&webp), Some((640, 480)));
+        assert_eq!(image_size(b"<svg/>"), None);
+        assert_eq!(image_size(&png(1, 1)[..20]), None);
+    }
+
+    #[test]
+    fn adds_hints_and_local_dimensions() {
+        let tmp = TempDir::new().unwrap();
+        let config = SiteConfig::new().with_content_dir(tmp.path());
+        fs::create_dir_all(tmp.path().join("blog/post")).unwrap();
+        fs::write(tmp.path().join("blog/post/cat.png"), png(32, 16)).unwrap();
+        fs::write(tmp.path().join("logo.png"), png(8, 8)).unwrap();
+
+        let html = add_image_hints(
+            "<p><img src=\"cat.png\" alt=\"Cat\" /> <img src=\"/logo.png?v=2\">\
+             <img src=\"https://example.com/a.png\" loading=\"eager\" />\
+             <img src=\"missing.png\" width=\"10\" /><imgur></p>",
+            "/blog/post/",
+            &config,
+        );
+        assert_eq!(
+            html,
+            "<p><img src=\"cat.png\" alt=\"Cat\" width=\"32\" height=\"16\" loading=\"lazy\" \
+             decoding=\"async\" /> <img src=\"/logo.png?v=2\" width=\"8\" height=\"8\" \
+             loading=\"lazy\" decoding=\"async\">\
+             <img src=\"https://example.com/a.png\" loading=\"eager\" decoding=\"async\" />\
+             <img src=\"missing.png\" width=\"10\" loading=\"lazy\" decoding=\"async\" /><imgur></p>"
+        );
+    }
+
+    #[test]
+    fn reads_jpeg_size_behind_large_segments() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("big.jpg");
+        // SOI, a 60 KB APP1 segment, then SOF0 (200 × 100).
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe1, 0xea, 0x60];
+        jpeg.resize(jpeg.len() + 0xea5e, 0);
+        jpeg.extend([0xff, 0xc0, 0x00, 0x11, 0x08, 0x00, 0x64, 0x00, 0xc8]);
+        fs::write(&path, &jpeg).unwrap();
+        assert_eq!(file_size(&path), Some((200, 100)));
+        fs::write(&path, &jpeg[..jpeg.len() - 4]).unwrap();
+        assert_eq!(file_size(&path), None);
+    }
+
+    #[test]
+    fn reads_attributes_in_order() {
+        let tag = r#" alt="src=x width=3" data-src='a.png' SRC=cat.png ismap"#;
+        assert_eq!(tag_attr(tag, "src"), Some("cat.png"));
+        assert_eq!(tag_attr(tag, "alt"), Some("src=x width=3"));
+        assert_eq!(tag_attr(tag, "width"), None);
+        assert_eq!(tag_attr(tag, "ismap"), Some(""));
+        assert_eq!(tag_attr("a href=/about/ class=x", "href"), Some("/about/"));
+        assert_eq!(tag_attr("a title = 'x' href = \"/\"", "href"), Some("/"));
+        assert_eq!(tag_attr("img src", "img"), None);
+    }
+
+    #[test]
+    fn fingerprints_local_images_per_mode() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("post/img")).unwrap();
+        fs::write(tmp.path().join("post/img/cat.png"), png(2, 2)).unwrap();
+        fs::write(tmp.path().join("logo.svg"), "<svg/>").unwrap();
+        fs::write(tmp.path().join("post/notes.txt"), "x").unwrap();
+        let cat = fingerprinted_name("cat.png", &png(2, 2));
+        let logo = fingerprinted_name("/logo.svg", b"<svg/>");
+        let html = "<img alt=\"./img/cat.png\" src=\"./img/cat.png?v=1\"><img src='/logo.svg'>\
+                    <img src=\"notes.txt\"><img src=\"https://example.com/cat.png\"><image>";
+
+        let config = SiteConfig::new()
+            .with_content_dir(tmp.path())
+            .with_fingerprint_images(ImageFingerprints::Relative);
+        assert_eq!(
+            fingerprint_images(html, "/post/", &config),
+            format!(
+                "<img alt=\"./img/cat.png\" src=\"./img/{cat}?v=1\"><img src='/logo.svg'>\
+                 <img src=\"notes.txt\"><img src=\"https://example.com/cat.png\"><image>"
+            )
+        );
+
+        let config = config.with_fingerprint_images(ImageFingerprints::Local);
+        assert!(
+            fingerprint_images(html, "/post/", &config).contains(&format!("src='{logo}'")),
+            "{logo}"
+        );
+        let config = config.with_fingerprint_images(ImageFingerprints::Off);
+        assert_eq!(fingerprint_images(html, "/post/", &config), html);
+    }
+
+    #[test]
+    fn parses_fingerprint_modes() {
+        assert_eq!(
+            "Local".parse::<ImageFingerprints>(),
+            Ok(ImageFingerprints::Local)
+        );
+        assert_eq!(
+            " off ".parse::<ImageFingerprints>(),
+            Ok(ImageFingerprints::Off)
+        );
+        assert!("all".parse::<ImageFingerprints>().is_err());
+    }
+
+    #[test]
+    fn relative_src_cannot_escape_content_dir() {
+        let tmp = TempDir::new().unwrap();
+        let content = tmp.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(tmp.path().join("outside.png"), png(4, 4)).unwrap();
+        let config = SiteConfig::new().with_content_dir(&content);
+        assert_eq!(local_file("../../outside.png", "/a/", &config), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! "Last updated" timestamps for content files.
+//!
+//! With git lookup enabled the timestamp is the committer date of the last
+//! commit touching the file (`git log -1 --format=%cI`). Untracked files, or
+//! hosts without git, fall back to the filesystem mtime; if that is
+//! unavailable too there is no timestamp. Spawning git once per file is slow on
+//! large sites, hence the opt-in [`crate::SiteConfig::git_last_modified`] flag —
+//! with it off, the mtime is used directly.
+
+use crate::core::date::format_system_time;
+use std::path::Path;
+use std::process::Command;
+
+/// Resolve the last-modified timestamp for `path` as an ISO 8601 / RFC 3339
+/// string, e.g. `2026-05-31T09:30:00+02:00` (git) or `2026-05-31T07:30:00Z`
+/// (mtime, always UTC).
+pub(crate) fn last_modified(path: &Path, use_git: bool) -> Option<String> {
+    if use_git && let Some(stamp) = git_last_modified(path) {
+        return Some(stamp);
+    }
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(format_system_time(mtime))
+}
+
+/// Committer date of the last commit touching `path`, or `None` when git is
+/// missing, the file lives outside a repository, or it has never been
+/// committed (git prints nothing in that case).
+fn git_last_modified(path: &Path) -> Option<String> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty())?;
+    let file = path.file_name()?;
+    let output = Command::new("git")
+        .arg("log")
+        .arg("-1")
+        .arg("--format=%cI")
+        .arg("--")
+        .arg(file)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stamp = String::from_utf8(output.stdout).ok()?;
+    let stamp = stamp.trim();
+    (!stamp.is_empty()).then(|| stamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn falls_back_to_mtime_without_git() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("page.md");
+        std::fs::write(&file, "x").unwrap();
+        let stamp = last_modified(&file, false).expect("mtime should resolve");
+        assert!(stamp.ends_with('Z'), "{stamp}");
+        assert_eq!(stamp.len(), "1970-01-01T00:00:00Z".len());
+    }
+
+    #[test]
+    fn untracked_file_falls_back_to_mtime_with_git() {
+        // A temp dir is outside any repository, so git yields nothing.
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("page.md");
+        std::fs::write(&file, "x").unwrap();
+        assert_eq!(last_modified(&file, true), last_modified(&file, false));
+    }
+
+    #[test]
+    fn missing_file_is_none() {
+        let tmp = TempDir::new().unwrap();
+        assert!(last_modified(&tmp.path().join("nope.md"), false).is_none());
+    }
+}
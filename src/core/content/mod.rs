@@ -2,8 +2,26 @@
 //!
 //! [`frontmatter`] splits the metadata block from the body, [`parser`] holds the
 //! pluggable [`parser::ContentParser`] system (markdown built in), and
-//! [`page::load_page`] ties them together with path-derived fields.
+//! [`page::load_page_with_defaults`] ties them together with path-derived fields.
+//! [`ignore`] applies `.sherwoodignore` and `_drafts` exclusions during
+//! discovery, [`section`] cascades `_section.toml` frontmatter defaults, and [`toc`] builds opt-in tables of contents from rendered
+//! headings whose ids come from [`slug`], [`excerpt`] caps excerpt length, [`images`] adds opt-in loading hints, dimensions and
+//! fingerprinted sources to `<img>` tags, [`tables`] wraps tables for
+//! horizontal scrolling, [`transform`] runs embedders' HTML rewrites, [`url_strategy`] lets
+//! embedders choose page URLs, and [`validation`] enforces required
+//! frontmatter fields.
 
+pub mod excerpt;
 pub mod frontmatter;
+pub mod ignore;
+pub mod images;
+pub mod last_modified;
 pub mod page;
 pub mod parser;
+pub mod section;
+pub mod slug;
+pub mod tables;
+pub mod toc;
+pub mod transform;
+pub mod url_strategy;
+pub mod validation;
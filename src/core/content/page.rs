@@ -1,7 +1,18 @@
 use crate::core::config::SiteConfig;
-use crate::core::content::frontmatter::FrontMatter;
+use crate::core::content::excerpt::truncate_excerpt;
+use crate::core::content::frontmatter::{FrontMatter, split_frontmatter};
+use crate::core::content::images::{ImageFingerprints, add_image_hints, fingerprint_images};
+use crate::core::content::last_modified::last_modified;
 use crate::core::content::parser::{ParserError, ParserRegistry};
-use crate::core::nav::href_for;
+use crate::core::content::section::apply_defaults;
+use crate::core::content::tables::wrap_tables;
+use crate::core::content::toc::{toc_html, toc_levels};
+use crate::core::content::transform::apply_transforms;
+use crate::core::content::url_strategy::page_location;
+use crate::core::content::validation::missing_fields;
+use crate::core::date::{Date, PageDate};
+use gray_matter::Pod;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -22,16 +33,41 @@ pub enum PageError {
         #[source]
         source: ParserError,
     },
+    /// The page matches a [`ValidationRule`](crate::ValidationRule) whose
+    /// required frontmatter fields it doesn't set.
+    #[error("{}: missing required frontmatter `{}`", path.display(), fields.join("`, `"))]
+    MissingFields { path: PathBuf, fields: Vec<String> },
+    /// The frontmatter `date` isn't a valid date (`YYYY-MM-DD`, optionally
+    /// with a time and UTC offset) and [`SiteConfig::strict_dates`] is on.
+    #[error(
+        "{}: frontmatter `date` `{date}` is not a valid date (`YYYY-MM-DD`, optionally with a time and UTC offset)",
+        path.display()
+    )]
+    InvalidDate { path: PathBuf, date: String },
 }
 
 #[derive(Debug, Clone)]
 pub struct Page {
     pub frontmatter: FrontMatter,
     pub content_html: String,
-    /// Pre-rendered excerpt HTML, when the source contains the `<!-- more -->`
-    /// delimiter. Everything before the delimiter is extracted, converted to
-    /// HTML, and stored here. `None` if the delimiter is absent.
+    /// Pre-rendered excerpt HTML, when the source contains the site's
+    /// [`SiteConfig::excerpt_separator`] (`<!-- more -->` by default) outside
+    /// code. Everything before the separator is extracted, converted to
+    /// HTML, and stored here. `None` if the separator is absent.
     pub excerpt_html: Option<String>,
+    /// A nested `<ul>` table of contents linking the page's headings (levels
+    /// per [`SiteConfig::toc_min_level`] / [`toc_max_level`], or the page's
+    /// `toc_min_level` / `toc_max_level` frontmatter), when the frontmatter
+    /// sets `toc: true` and the content has such headings with ids. `None`
+    /// otherwise.
+    ///
+    /// [`toc_max_level`]: SiteConfig::toc_max_level
+    pub toc_html: Option<String>,
+    /// The source text after the frontmatter block, unrendered, when
+    /// [`SiteConfig::raw_content`] is on — for "edit this page" views,
+    /// diffs, or rendering it another way. `None` otherwise, and for
+    /// generated pages.
+    pub raw_content: Option<String>,
     pub source_path: PathBuf,
     pub output_path: PathBuf,
     /// Absolute URL of the page, e.g. `/blog/first-post/`. Cross-platform
@@ -42,16 +78,47 @@ pub struct Page {
     /// `<stem>/index.html` directory for pretty URLs and have this flag set
     /// to `false`.
     pub is_section_index: bool,
+    /// When the source was last changed, as an RFC 3339 timestamp — from git
+    /// history when [`SiteConfig::git_last_modified`] is set, otherwise (or
+    /// for untracked files) the filesystem mtime. `None` if neither resolves.
+    pub last_modified: Option<String>,
+    /// The frontmatter `date`, formatted per [`SiteConfig::date_format`] with
+    /// its ISO form alongside. `None` when the frontmatter has no `date`.
+    pub date: Option<PageDate>,
+    /// The page's language: the frontmatter `lang` field, or else the
+    /// top-level content directory when it is one of
+    /// [`SiteConfig::languages`] (`content/fr/about.md` → `fr`). `None` for
+    /// single-language sites.
+    pub lang: Option<String>,
+    /// Non-fatal problems found while loading the page (e.g. an out-of-range
+    /// TOC level that was clamped, or an unknown shortcode). The build still succeeds; the CLI prints
+    /// these as warnings.
+    pub warnings: Vec<String>,
+}
+
+/// Frontmatter field that takes a live page out of the build when `false`,
+/// unless [`SiteConfig::unpublished`] is set.
+pub(crate) const PUBLISHED: &str = "published";
+
+/// Whether `page` is published: anything but `published: false`.
+pub(crate) fn is_published(page: &Page) -> bool {
+    !matches!(
+        page.frontmatter.get(PUBLISHED),
+        Some(gray_matter::Pod::Boolean(false))
+    )
 }
 
 /// Load one content file into a [`Page`], dispatching to the parser registered
-/// for its extension. Returns `Ok(None)` when no parser claims the extension,
-/// so the build can skip non-content files (images, CSS, …) living in the
-/// content tree.
-pub fn load_page(
+/// for its extension. Returns `Ok(None)` when no parser claims the extension
+/// (or the parser declines the file), so the build can skip non-content files
+/// (images, CSS, …) living in the content tree. Frontmatter fields the page
+/// doesn't set are filled from `defaults` (its `_section.toml` cascade)
+/// before anything reads them.
+pub(crate) fn load_page_with_defaults(
     source_path: &Path,
     config: &SiteConfig,
     registry: &ParserRegistry,
+    defaults: &HashMap<String, Pod>,
 ) -> Result<Option<Page>, PageError> {
     let ext = source_path
         .extension()
@@ -65,29 +132,137 @@ pub fn load_page(
         path: source_path.to_owned(),
         source: e,
     })?;
-    let parsed = parser
-        .parse(&source, source_path)
+    if !parser.claims(&source) {
+        return Ok(None);
+    }
+    let mut parsed = parser
+        .parse_with_config(&source, source_path, config)
         .map_err(|e| PageError::Parse {
             path: source_path.to_owned(),
             source: e,
         })?;
+    apply_defaults(&mut parsed.frontmatter.data, defaults);
+
+    let relative = config.content_relative(source_path).unwrap_or(source_path);
+    let missing = missing_fields(&config.validation, relative, &parsed.frontmatter);
+    if !missing.is_empty() {
+        return Err(PageError::MissingFields {
+            path: source_path.to_owned(),
+            fields: missing,
+        });
+    }
 
     let is_section_index = source_path.file_stem().and_then(|s| s.to_str()) == Some("index");
-    let output_path = output_path_for(source_path, config);
-    let url = href_for(&output_path, config);
+    let (output_path, url) = page_location(source_path, &parsed.frontmatter, config);
+    let date = parsed
+        .frontmatter
+        .get_string("date")
+        .map(|raw| PageDate::new(&raw, &config.date_format, Date::today()));
+    let lang = page_lang(source_path, &parsed.frontmatter, config);
+    let mut warnings = parsed.warnings;
+    if let Some(date) = &date
+        && date.iso.is_none()
+    {
+        if config.strict_dates {
+            return Err(PageError::InvalidDate {
+                path: source_path.to_owned(),
+                date: date.display.clone(),
+            });
+        }
+        warnings.push(format!(
+            "`date` `{}` is not a valid date (`YYYY-MM-DD`, optionally with a time and UTC offset); shown as written",
+            date.display
+        ));
+    }
+    let toc_html = match parsed.frontmatter.get("toc") {
+        Some(gray_matter::Pod::Boolean(true)) => {
+            let level = |key: &str, default: u8| match parsed.frontmatter.get(key) {
+                Some(gray_matter::Pod::Integer(n)) => *n,
+                _ => i64::from(default),
+            };
+            let (levels, warning) = toc_levels(
+                level("toc_min_level", config.toc_min_level),
+                level("toc_max_level", config.toc_max_level),
+            );
+            warnings.extend(warning);
+            toc_html(&parsed.content_html, levels)
+        }
+        _ => None,
+    };
+    let excerpt_html = match config.excerpt_max_chars {
+        0 => parsed.excerpt_html,
+        max => parsed.excerpt_html.map(|html| truncate_excerpt(&html, max)),
+    };
+    let (content_html, excerpt_html) = if config.lazy_images {
+        let hint = |html: &str| add_image_hints(html, &url, config);
+        (
+            hint(&parsed.content_html),
+            excerpt_html.as_deref().map(hint),
+        )
+    } else {
+        (parsed.content_html, excerpt_html)
+    };
+    // After the hints, which read dimensions from the original file names.
+    let (content_html, excerpt_html) = if config.fingerprint_images == ImageFingerprints::Off {
+        (content_html, excerpt_html)
+    } else {
+        let rename = |html: &str| fingerprint_images(html, &url, config);
+        (rename(&content_html), excerpt_html.as_deref().map(rename))
+    };
+    let (content_html, excerpt_html) = if config.wrap_tables {
+        (
+            wrap_tables(&content_html),
+            excerpt_html.as_deref().map(wrap_tables),
+        )
+    } else {
+        (content_html, excerpt_html)
+    };
+    let content_html = apply_transforms(content_html, &url, config);
+    let excerpt_html = excerpt_html.map(|html| apply_transforms(html, &url, config));
+    // A parser that reads its own frontmatter syntax keeps the whole source.
+    let raw_content = config
+        .raw_content
+        .then(|| match split_frontmatter(&source) {
+            Ok((_, body)) => body,
+            Err(_) => source,
+        });
     Ok(Some(Page {
         frontmatter: parsed.frontmatter,
-        content_html: parsed.content_html,
-        excerpt_html: parsed.excerpt_html,
+        content_html,
+        excerpt_html,
+        toc_html,
+        raw_content,
         source_path: source_path.to_owned(),
         output_path,
         url,
         is_section_index,
+        last_modified: last_modified(source_path, config.git_last_modified),
+        date,
+        lang,
+        warnings,
     }))
 }
 
+/// Frontmatter `lang` wins; otherwise a top-level content directory named
+/// after a configured language marks the page as that language.
+fn page_lang(source: &Path, frontmatter: &FrontMatter, config: &SiteConfig) -> Option<String> {
+    if let Some(lang) = frontmatter.get_string("lang") {
+        return Some(lang);
+    }
+    let relative = config.content_relative(source)?;
+    let mut components = relative.components();
+    let first = components.next()?.as_os_str().to_str()?;
+    // A bare `content/fr.md` is a page, not a language directory.
+    components.next()?;
+    config
+        .languages
+        .iter()
+        .find(|lang| lang.as_str() == first)
+        .cloned()
+}
+
 pub(crate) fn output_path_for(source: &Path, config: &SiteConfig) -> PathBuf {
-    let relative = source.strip_prefix(&config.content_dir).unwrap_or(source);
+    let relative = config.content_relative(source).unwrap_or(source);
     let stem = relative.file_stem().and_then(|s| s.to_str()).unwrap_or("");
     let parent = relative.parent().unwrap_or(Path::new(""));
     if stem == "index" {
@@ -101,9 +276,19 @@ pub(crate) fn output_path_for(source: &Path, config: &SiteConfig) -> PathBuf {
 mod tests {
     use super::*;
     use crate::core::config::SiteConfig;
+    use crate::core::content::validation::ValidationRule;
     use std::fs;
     use tempfile::TempDir;
 
+    /// Load `source_path` with no section defaults.
+    fn load_page(
+        source_path: &Path,
+        config: &SiteConfig,
+        registry: &ParserRegistry,
+    ) -> Result<Option<Page>, PageError> {
+        load_page_with_defaults(source_path, config, registry, &HashMap::new())
+    }
+
     fn default_config() -> SiteConfig {
         SiteConfig {
             content_dir: PathBuf::from("content"),
@@ -161,7 +346,7 @@ mod tests {
             .unwrap()
             .unwrap();
         assert_eq!(page.frontmatter.title, "About");
-        assert!(page.content_html.contains("<h1>About</h1>"));
+        assert!(page.content_html.contains("<h1 id=\"about\">About</h1>"));
     }
 
     #[test]
@@ -193,6 +378,32 @@ mod tests {
         assert!(load_page(&file, &config, &ParserRegistry::default()).is_err());
     }
 
+    #[test]
+    fn load_page_missing_required_fields_returns_error() {
+        let tmp = TempDir::new().unwrap();
+        let blog = tmp.path().join("blog");
+        fs::create_dir_all(&blog).unwrap();
+        let file = blog.join("post.md");
+        fs::write(&file, "---\ntitle: Post\nauthor: Thom\n---\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        }
+        .with_validation([ValidationRule::new("blog/**", ["date", "author", "tags"])]);
+        let err = load_page(&file, &config, &ParserRegistry::default()).unwrap_err();
+        assert!(
+            matches!(&err, PageError::MissingFields { fields, .. } if fields == &["date", "tags"]),
+            "{err}"
+        );
+        let msg = err.to_string();
+        assert!(msg.contains("post.md"), "{msg}");
+        assert!(
+            msg.ends_with("missing required frontmatter `date`, `tags`"),
+            "{msg}"
+        );
+    }
+
     #[test]
     fn load_page_sets_pretty_url() {
         let tmp = TempDir::new().unwrap();
@@ -248,6 +459,231 @@ mod tests {
         assert!(page.is_section_index);
     }
 
+    #[test]
+    fn load_page_sets_last_modified_from_mtime() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("about.md");
+        fs::write(&file, "---\ntitle: About\n---\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        };
+        let page = load_page(&file, &config, &ParserRegistry::default())
+            .unwrap()
+            .unwrap();
+        let stamp = page.last_modified.expect("mtime fallback");
+        assert!(stamp.ends_with('Z'), "{stamp}");
+    }
+
+    #[test]
+    fn load_page_formats_date_per_config() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("post.md");
+        fs::write(&file, "---\ntitle: Post\ndate: 2024-01-15\n---\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        }
+        .with_date_format("%d %b %Y");
+        let page = load_page(&file, &config, &ParserRegistry::default())
+            .unwrap()
+            .unwrap();
+        let date = page.date.expect("date should be set");
+        assert_eq!(date.iso.as_deref(), Some("2024-01-15"));
+        assert_eq!(date.display, "15 Jan 2024");
+    }
+
+    #[test]
+    fn load_page_invalid_date_warns_or_fails_when_strict() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("post.md");
+        fs::write(&file, "---\ntitle: Post\ndate: 2024-13-01\n---\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        };
+        let page = load_page(&file, &config, &ParserRegistry::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(page.date.unwrap().display, "2024-13-01");
+        assert_eq!(
+            page.warnings,
+            [
+                "`date` `2024-13-01` is not a valid date (`YYYY-MM-DD`, optionally with a time and UTC offset); shown as written"
+            ]
+        );
+
+        let config = config.with_strict_dates(true);
+        let err = load_page(&file, &config, &ParserRegistry::default()).unwrap_err();
+        assert!(
+            matches!(&err, PageError::InvalidDate { date, .. } if date == "2024-13-01"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("post.md"), "{err}");
+    }
+
+    #[test]
+    fn load_page_detects_language_directory() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("fr")).unwrap();
+        fs::create_dir_all(tmp.path().join("blog")).unwrap();
+        let fr = tmp.path().join("fr/about.md");
+        let blog = tmp.path().join("blog/post.md");
+        let tagged = tmp.path().join("blog/hola.md");
+        fs::write(&fr, "---\ntitle: À propos\n---\n").unwrap();
+        fs::write(&blog, "---\ntitle: Post\n---\n").unwrap();
+        fs::write(&tagged, "---\ntitle: Hola\nlang: es\n---\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        }
+        .with_languages(["en", "fr"]);
+        let load = |path: &Path| {
+            load_page(path, &config, &ParserRegistry::default())
+                .unwrap()
+                .unwrap()
+                .lang
+        };
+        assert_eq!(load(&fr).as_deref(), Some("fr"));
+        assert_eq!(load(&blog), None);
+        assert_eq!(load(&tagged).as_deref(), Some("es"));
+    }
+
+    #[test]
+    fn load_page_truncates_only_the_excerpt() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("post.md");
+        fs::write(
+            &path,
+            "---\ntitle: Post\n---\n\nA rather long introduction.\n<!-- more -->\nrest\n",
+        )
+        .unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        }
+        .with_excerpt_max_chars(12);
+        let page = load_page(&path, &config, &ParserRegistry::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(page.excerpt_html.as_deref(), Some("<p>A rather…</p>"));
+        assert!(page.content_html.contains("A rather long introduction."));
+    }
+
+    #[test]
+    fn load_page_keeps_raw_content_only_when_asked() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("post.md");
+        fs::write(&path, "---\ntitle: Post\n---\n\n# Hi *there*\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        };
+        let load = |config: &SiteConfig| {
+            load_page(&path, config, &ParserRegistry::default())
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(load(&config).raw_content, None);
+        let page = load(&config.with_raw_content(true));
+        assert_eq!(
+            page.raw_content.as_deref().map(str::trim),
+            Some("# Hi *there*")
+        );
+        assert!(page.content_html.contains("<em>there</em>"));
+    }
+
+    #[test]
+    fn load_page_applies_html_transforms_to_body_and_excerpt() {
+        #[derive(Debug)]
+        struct Shout;
+        impl crate::HtmlTransform for Shout {
+            fn transform(&self, html: String, url: &str) -> String {
+                format!("{}<!-- {url} -->", html.to_uppercase())
+            }
+        }
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("post.md");
+        fs::write(
+            &path,
+            "---\ntitle: Post\n---\n\nintro\n<!-- more -->\nrest\n",
+        )
+        .unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        }
+        .with_html_transform(Shout);
+        let page = load_page(&path, &config, &ParserRegistry::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            page.content_html,
+            "<P>INTRO</P>\n<P>REST</P>\n<!-- /post/ -->"
+        );
+        assert_eq!(
+            page.excerpt_html.as_deref(),
+            Some("<P>INTRO</P>\n<!-- /post/ -->")
+        );
+    }
+
+    #[test]
+    fn load_page_builds_toc_only_when_requested() {
+        let tmp = TempDir::new().unwrap();
+        let with = tmp.path().join("with.md");
+        let without = tmp.path().join("without.md");
+        fs::write(
+            &with,
+            "---\ntitle: With\ntoc: true\n---\n\n## Intro\n\n### Detail\n",
+        )
+        .unwrap();
+        fs::write(&without, "---\ntitle: Without\n---\n\n## Intro\n").unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        };
+        let load = |path: &Path| {
+            load_page(path, &config, &ParserRegistry::default())
+                .unwrap()
+                .unwrap()
+        };
+        let toc = load(&with).toc_html.expect("toc requested");
+        assert!(
+            toc.contains("<a href=\"#intro\">Intro</a><ul><li><a href=\"#detail\">"),
+            "{toc}"
+        );
+        assert!(load(&without).toc_html.is_none());
+    }
+
+    #[test]
+    fn load_page_toc_levels_from_frontmatter_are_clamped_with_warning() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("deep.md");
+        fs::write(
+            &file,
+            "---\ntitle: Deep\ntoc: true\ntoc_min_level: 2\ntoc_max_level: 9\n---\n\n## A\n\n###### F\n",
+        )
+        .unwrap();
+        let config = SiteConfig {
+            content_dir: tmp.path().to_owned(),
+            output_dir: tmp.path().join("_site"),
+            ..SiteConfig::default()
+        };
+        let page = load_page(&file, &config, &ParserRegistry::default())
+            .unwrap()
+            .unwrap();
+        assert!(page.toc_html.unwrap().contains("href=\"#f\""));
+        assert_eq!(page.warnings.len(), 1, "{:?}", page.warnings);
+    }
+
     #[test]
     fn load_page_extracts_excerpt_when_delimiter_present() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,869 @@
+//! A built-in parser for the common core of AsciiDoc, behind the `asciidoc`
+//! feature. It needs no extra dependencies and renders the same HTML shapes
+//! as the markdown parser (`<h2 id>`, `<pre><code class="language-…">`), so
+//! themes style both alike.
+//!
+//! Supported: the document header (`= Title`, author and revision lines,
+//! `:name: value` attributes), section titles, paragraphs with hard breaks
+//! (` +`), `*` / `-` and `.` lists (nest with more markers), listing,
+//! literal, quote, example, sidebar and passthrough blocks, block titles,
+//! `[[id]]` / `[#id]` anchors, `[source,lang]`, admonitions (`NOTE: …` or
+//! `[NOTE]` on a block), `image::` blocks, thematic breaks and comments.
+//! Inline: `` `code` ``, `*strong*`, `_emphasis_`, URLs with optional
+//! `[text]`, `link:`, `image:` and `<<id,text>>` cross references. Anything
+//! else, attribute references like `{name}` included, is kept as text.
+
+use super::{ContentParser, Parsed, ParserError};
+use crate::core::content::frontmatter::{FrontMatter, split_frontmatter};
+use crate::core::content::parser::escape_html;
+use crate::core::content::slug::{slugify, unique_slug};
+use gray_matter::Pod;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// File extensions [`AsciidocParser`] claims.
+pub const ASCIIDOC_EXTENSIONS: &[&str] = &["adoc", "asciidoc"];
+
+/// A comment line that ends a page's excerpt, like markdown's
+/// `<!-- more -->`. Being a comment, other AsciiDoc tools ignore it.
+const EXCERPT_COMMENT: &str = "// more";
+
+/// Header attributes holding comma-separated lists, read into arrays so they
+/// mean the same as the list-valued frontmatter fields.
+const LIST_ATTRIBUTES: [&str; 4] = ["tags", "aliases", "redirect_from", "stylesheets"];
+
+/// Admonition labels, as written before a paragraph (`NOTE: …`) or a block.
+const ADMONITIONS: [&str; 5] = ["NOTE", "TIP", "IMPORTANT", "WARNING", "CAUTION"];
+
+/// The built-in AsciiDoc parser ([`ASCIIDOC_EXTENSIONS`]). Metadata comes
+/// from the document header: `= Title` is the page title and each
+/// `:name: value` entry becomes a frontmatter field (`:date: 2024-01-15`,
+/// `:tags: rust, web`, and a bare `:toc:` meaning `toc: true`). YAML or TOML
+/// frontmatter works too and wins over header entries of the same name.
+/// A `// more` comment line ends the excerpt.
+///
+/// [`ParserRegistry::default`](super::ParserRegistry::default) registers it
+/// when the feature is on:
+///
+/// ```
+/// use sherwood::ParserRegistry;
+/// assert!(ParserRegistry::default().get("adoc").is_some());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AsciidocParser;
+
+impl AsciidocParser {
+    /// A parser with the default options.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ContentParser for AsciidocParser {
+    fn extensions(&self) -> &[&str] {
+        ASCIIDOC_EXTENSIONS
+    }
+
+    fn parse(&self, source: &str, _path: &Path) -> Result<Parsed, ParserError> {
+        let delimited = matches!(source.lines().next().map(str::trim), Some("---" | "+++"));
+        let (frontmatter, body) = if delimited {
+            let (frontmatter, body) = split_frontmatter(source)?;
+            (Some(frontmatter), body)
+        } else {
+            (None, source.to_string())
+        };
+        let lines: Vec<&str> = body.lines().collect();
+        let header = Header::parse(&lines);
+        let end = header.end;
+        let frontmatter = match frontmatter {
+            Some(frontmatter) => header.merge_into(frontmatter),
+            None => header.into_frontmatter().ok_or_else(|| {
+                ParserError::Message(
+                    "missing document title (start with `= Title` or frontmatter)".to_string(),
+                )
+            })?,
+        };
+
+        let body = &lines[end..];
+        let mut warnings = Vec::new();
+        let content_html = render(body, &mut warnings);
+        let excerpt_html = body
+            .iter()
+            .position(|line| line.trim() == EXCERPT_COMMENT)
+            .map(|end| render(&body[..end], &mut Vec::new()));
+        Ok(Parsed {
+            frontmatter,
+            content_html,
+            excerpt_html,
+            warnings,
+        })
+    }
+}
+
+/// The document header: an optional `= Title` with its author and revision
+/// lines, then attribute entries, up to the first blank line.
+#[derive(Debug, Default)]
+struct Header {
+    title: Option<String>,
+    attributes: Vec<(String, Pod)>,
+    /// Index of the first body line.
+    end: usize,
+}
+
+impl Header {
+    fn parse(lines: &[&str]) -> Self {
+        let mut header = Header::default();
+        let mut i = lines
+            .iter()
+            .position(|l| {
+                let l = l.trim();
+                let comment = l.starts_with("//") && l != EXCERPT_COMMENT;
+                !l.is_empty() && !comment
+            })
+            .unwrap_or(lines.len());
+        if let Some(title) = lines.get(i).and_then(|l| l.strip_prefix("= ")) {
+            header.title = Some(title.trim().to_string());
+            i += 1;
+            // Author, then revision: free text before any attribute entry.
+            for _ in 0..2 {
+                match lines.get(i) {
+                    Some(l) if !l.trim().is_empty() && attribute_entry(l).is_none() => i += 1,
+                    _ => break,
+                }
+            }
+        }
+        let start = i;
+        while let Some(line) = lines.get(i) {
+            match attribute_entry(line) {
+                Some((name, value)) => {
+                    header.attributes.retain(|(n, _)| *n != name);
+                    if let Some(value) = value {
+                        header
+                            .attributes
+                            .push((name.to_string(), attribute_value(name, value)));
+                    }
+                    i += 1;
+                }
+                None if line.trim().starts_with("//") && line.trim() != EXCERPT_COMMENT => i += 1,
+                None => break,
+            }
+        }
+        header.end = if header.title.is_some() || i > start {
+            i
+        } else {
+            0
+        };
+        header
+    }
+
+    /// Frontmatter from the header alone: `= Title`, else a `:title:` entry.
+    fn into_frontmatter(self) -> Option<FrontMatter> {
+        let mut map: HashMap<String, Pod> = self.attributes.into_iter().collect();
+        let title = match self.title {
+            Some(title) => title,
+            None => match map.get("title") {
+                Some(Pod::String(title)) => title.clone(),
+                _ => return None,
+            },
+        };
+        map.insert("title".to_string(), Pod::String(title.clone()));
+        Some(FrontMatter {
+            title,
+            data: Pod::Hash(map),
+        })
+    }
+
+    /// `frontmatter` plus the header's attributes it doesn't set itself.
+    fn merge_into(self, mut frontmatter: FrontMatter) -> FrontMatter {
+        if let Pod::Hash(map) = &mut frontmatter.data {
+            for (name, value) in self.attributes {
+                map.entry(name).or_insert(value);
+            }
+        }
+        frontmatter
+    }
+}
+
+/// `:name: value` → `(name, Some(value))`; `:name!:` or `:!name:` (unset) →
+/// `(name, None)`. `None` for any other line.
+fn attribute_entry(line: &str) -> Option<(&str, Option<&str>)> {
+    let rest = line.strip_prefix(':')?;
+    let (name, value) = rest.split_once(':')?;
+    let (name, unset) = match name.strip_prefix('!').or_else(|| name.strip_suffix('!')) {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| (name, (!unset).then(|| value.trim())))
+}
+
+/// An attribute's value as frontmatter: an empty value is `true` (as in
+/// `:toc:`), `true` / `false` and integers are typed, and list attributes
+/// are split on commas.
+fn attribute_value(name: &str, value: &str) -> Pod {
+    if LIST_ATTRIBUTES.contains(&name) {
+        return Pod::Array(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| Pod::String(v.to_string()))
+                .collect(),
+        );
+    }
+    match value {
+        "" | "true" => Pod::Boolean(true),
+        "false" => Pod::Boolean(false),
+        _ => match value.parse::<i64>() {
+            Ok(n) => Pod::Integer(n),
+            Err(_) => Pod::String(value.to_string()),
+        },
+    }
+}
+
+/// Render body lines to HTML. Section ids are slugged from their titles and
+/// made unique like markdown heading ids, after reserving explicit ids.
+fn render(lines: &[&str], warnings: &mut Vec<String>) -> String {
+    let mut ids: HashSet<String> = lines
+        .iter()
+        .filter_map(|l| block_attributes(l.trim()))
+        .filter_map(|attrs| attrs.id)
+        .collect();
+    let mut out = String::new();
+    render_blocks(lines, &mut ids, warnings, &mut out);
+    out
+}
+
+/// Attributes from the line before a block: an id, a style (`source`,
+/// `NOTE`, …) and, for `source`, the language. A block title (`.Title`) is
+/// kept alongside.
+#[derive(Debug, Default)]
+struct BlockAttributes {
+    id: Option<String>,
+    style: Option<String>,
+    lang: Option<String>,
+    title: Option<String>,
+}
+
+/// `[[id]]`, `[#id]`, `[source,rust]`, `[NOTE]`…, or `None` when `line` isn't
+/// a block attribute line.
+fn block_attributes(line: &str) -> Option<BlockAttributes> {
+    if let Some(id) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+        let id = id.split(',').next().unwrap_or_default().trim();
+        return (!id.is_empty()).then(|| BlockAttributes {
+            id: Some(id.to_string()),
+            ..BlockAttributes::default()
+        });
+    }
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    if inner.is_empty() || inner.contains(['[', ']']) {
+        return None;
+    }
+    let mut attrs = BlockAttributes::default();
+    let mut positional = inner.split(',').map(str::trim);
+    let first = positional.next().unwrap_or_default();
+    let (style, id) = match first.split_once('#') {
+        Some((style, id)) => (style, Some(id.split('.').next().unwrap_or_default())),
+        None => (first, None),
+    };
+    attrs.id = id.filter(|id| !id.is_empty()).map(str::to_string);
+    attrs.style = (!style.is_empty()).then(|| style.to_string());
+    if attrs.style.as_deref() == Some("source") {
+        attrs.lang = positional
+            .next()
+            .filter(|l| !l.is_empty())
+            .map(str::to_string);
+    }
+    Some(attrs)
+}
+
+/// The delimiter a line opens a block with: four or more of one of
+/// `-.=_*+` (`----` listing, `....` literal, `====` example, `____` quote,
+/// `****` sidebar, `++++` passthrough), or `////` for a comment block.
+fn delimiter(line: &str) -> Option<&str> {
+    let first = line.chars().next()?;
+    (line.len() >= 4 && "-.=_*+/".contains(first) && line.chars().all(|c| c == first))
+        .then_some(line)
+}
+
+fn render_blocks(
+    lines: &[&str],
+    ids: &mut HashSet<String>,
+    warnings: &mut Vec<String>,
+    out: &mut String,
+) {
+    let mut pending = BlockAttributes::default();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            i += 1;
+            continue;
+        }
+        if let Some(delim) = delimiter(trimmed) {
+            let close = lines[i + 1..]
+                .iter()
+                .position(|l| l.trim() == delim)
+                .map(|n| i + 1 + n);
+            if close.is_none() {
+                warnings.push(format!(
+                    "unclosed `{delim}` block runs to the end of the page"
+                ));
+            }
+            let end = close.unwrap_or(lines.len());
+            let inner = &lines[i + 1..end];
+            render_delimited(
+                delim,
+                inner,
+                std::mem::take(&mut pending),
+                ids,
+                warnings,
+                out,
+            );
+            i = end + 1;
+            continue;
+        }
+        if trimmed.starts_with("//") || attribute_entry(trimmed).is_some() {
+            i += 1;
+            continue;
+        }
+        if let Some(attrs) = block_attributes(trimmed) {
+            pending.id = attrs.id.or(pending.id);
+            if attrs.style.is_some() {
+                pending.style = attrs.style;
+                pending.lang = attrs.lang;
+            }
+            i += 1;
+            continue;
+        }
+        if let Some(title) = trimmed.strip_prefix('.')
+            && title.starts_with(|c: char| !c.is_whitespace() && c != '.')
+        {
+            pending.title = Some(title.to_string());
+            i += 1;
+            continue;
+        }
+        let attrs = std::mem::take(&mut pending);
+        if let Some((level, text)) = section_title(trimmed) {
+            let id = attrs
+                .id
+                .unwrap_or_else(|| unique_slug(slugify(text), "section", ids));
+            out.push_str(&format!(
+                "<h{level} id=\"{}\">{}</h{level}>\n",
+                escape_html(&id),
+                inline(text)
+            ));
+            i += 1;
+            continue;
+        }
+        push_title(&attrs, out);
+        if trimmed == "'''" {
+            out.push_str("<hr />\n");
+            i += 1;
+            continue;
+        }
+        if trimmed == "<<<" {
+            i += 1;
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("image::") {
+            let (target, alt) = macro_parts(rest);
+            out.push_str(&format!("<p>{}</p>\n", image(target, alt)));
+            i += 1;
+            continue;
+        }
+        if list_item(trimmed).is_some() {
+            let end = list_end(lines, i);
+            out.push_str(&render_list(&lines[i..end]));
+            i = end;
+            continue;
+        }
+        let end = lines[i..]
+            .iter()
+            .position(|l| l.trim().is_empty() || starts_block(l.trim()))
+            .map_or(lines.len(), |n| i + n.max(1));
+        let text = lines[i..end]
+            .iter()
+            .map(|l| l.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let paragraph = |text: &str| format!("<p>{}</p>\n", hard_breaks(&inline(text)));
+        let labelled = ADMONITIONS.iter().find_map(|label| {
+            text.strip_prefix(label)
+                .and_then(|t| t.strip_prefix(": "))
+                .map(|t| (*label, t))
+        });
+        match labelled.or_else(|| admonition(&attrs).map(|label| (label, text.as_str()))) {
+            Some((label, text)) => push_admonition(label, &paragraph(text), out),
+            None => out.push_str(&paragraph(&text)),
+        }
+        i = end;
+    }
+}
+
+/// Whether a line starts a new block, ending the paragraph before it.
+fn starts_block(line: &str) -> bool {
+    delimiter(line).is_some()
+        || section_title(line).is_some()
+        || list_item(line).is_some()
+        || block_attributes(line).is_some()
+        || line.starts_with("image::")
+        || line.starts_with("//")
+        || line == "'''"
+}
+
+/// `== Title` → `(2, "Title")`, down to `======` (h6). A lone `= Title` in
+/// the body is an h1.
+fn section_title(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '=').count();
+    let text = line[level..].strip_prefix(' ')?.trim();
+    ((1..=6).contains(&level) && !text.is_empty()).then_some((level, text))
+}
+
+fn render_delimited(
+    delim: &str,
+    inner: &[&str],
+    attrs: BlockAttributes,
+    ids: &mut HashSet<String>,
+    warnings: &mut Vec<String>,
+    out: &mut String,
+) {
+    let verbatim = || {
+        inner
+            .iter()
+            .map(|l| format!("{}\n", escape_html(l)))
+            .collect::<String>()
+    };
+    let nested = |ids: &mut HashSet<String>, warnings: &mut Vec<String>| {
+        let mut html = String::new();
+        render_blocks(inner, ids, warnings, &mut html);
+        html
+    };
+    if !delim.starts_with('/') {
+        push_title(&attrs, out);
+    }
+    match delim.as_bytes()[0] {
+        b'/' => {}
+        b'-' => match &attrs.lang {
+            Some(lang) => out.push_str(&format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>\n",
+                escape_html(lang),
+                verbatim()
+            )),
+            None => out.push_str(&format!("<pre><code>{}</code></pre>\n", verbatim())),
+        },
+        b'.' => out.push_str(&format!("<pre>{}</pre>\n", verbatim())),
+        b'+' => {
+            for line in inner {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        b'_' => out.push_str(&format!(
+            "<blockquote>\n{}</blockquote>\n",
+            nested(ids, warnings)
+        )),
+        b'*' => out.push_str(&format!(
+            "<aside class=\"sidebar\">\n{}</aside>\n",
+            nested(ids, warnings)
+        )),
+        _ => {
+            let html = nested(ids, warnings);
+            match admonition(&attrs) {
+                Some(label) => push_admonition(label, &html, out),
+                None => out.push_str(&format!("<div class=\"example\">\n{html}</div>\n")),
+            }
+        }
+    }
+}
+
+/// The admonition a block's style names, e.g. `[WARNING]`.
+fn admonition(attrs: &BlockAttributes) -> Option<&'static str> {
+    let style = attrs.style.as_deref()?;
+    ADMONITIONS.iter().copied().find(|label| *label == style)
+}
+
+/// An admonition, marked up like a `callout` shortcode:
+/// `<div class="callout note">`, led by its label.
+fn push_admonition(label: &str, html: &str, out: &mut String) {
+    let kind = label.to_ascii_lowercase();
+    let mut name = kind.clone();
+    name[..1].make_ascii_uppercase();
+    out.push_str(&format!(
+        "<div class=\"callout {kind}\">\n<p class=\"callout-title\">{name}</p>\n{html}</div>\n"
+    ));
+}
+
+/// A block title (`.Title`) as a `<div class="title">` before the block.
+fn push_title(attrs: &BlockAttributes, out: &mut String) {
+    if let Some(title) = &attrs.title {
+        out.push_str(&format!("<div class=\"title\">{}</div>\n", inline(title)));
+    }
+}
+
+/// `* item` → `(false, 1, "item")`, `** item` nests one deeper, `- item` is
+/// a top-level bullet, and `. item` / `.. item` are numbered.
+fn list_item(line: &str) -> Option<(bool, usize, &str)> {
+    let marker = line.chars().next()?;
+    let depth = match marker {
+        '*' | '.' => line.chars().take_while(|&c| c == marker).count(),
+        '-' => 1,
+        _ => return None,
+    };
+    let text = line[depth..].strip_prefix(' ')?.trim();
+    (depth <= 5 && !text.is_empty()).then_some((marker == '.', depth, text))
+}
+
+/// Index just past the list starting at `start`: items, their continuation
+/// lines, and blank lines between items.
+fn list_end(lines: &[&str], start: usize) -> usize {
+    let mut end = start + 1;
+    let mut i = start + 1;
+    while i < lines.len() {
+        let line = lines[i].trim();
+        if line.is_empty() {
+            i += 1;
+            continue;
+        }
+        // A line right after the list that starts no block of its own
+        // continues the last item.
+        let continues = i == end && !starts_block(line);
+        if list_item(line).is_none() && !continues {
+            break;
+        }
+        end = i + 1;
+        i += 1;
+    }
+    end
+}
+
+/// Nested `<ul>` / `<ol>` for a run of list lines.
+fn render_list(lines: &[&str]) -> String {
+    let mut items: Vec<(bool, usize, String)> = Vec::new();
+    for line in lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        match list_item(line) {
+            Some((ordered, depth, text)) => items.push((ordered, depth, text.to_string())),
+            None => {
+                if let Some((_, _, text)) = items.last_mut() {
+                    text.push('\n');
+                    text.push_str(line);
+                }
+            }
+        }
+    }
+    let tag = |ordered: bool| if ordered { "ol" } else { "ul" };
+    let mut out = String::new();
+    let mut open: Vec<(bool, usize)> = Vec::new();
+    for (ordered, depth, text) in &items {
+        while let Some(&(o, d)) = open.last() {
+            if d > *depth || (d == *depth && o != *ordered) {
+                out.push_str(&format!("</li>\n</{}>\n", tag(o)));
+                open.pop();
+            } else {
+                break;
+            }
+        }
+        match open.last() {
+            Some(&(_, d)) if d == *depth => out.push_str("</li>\n"),
+            _ => {
+                if !open.is_empty() {
+                    out.push('\n');
+                }
+                out.push_str(&format!("<{}>\n", tag(*ordered)));
+                open.push((*ordered, *depth));
+            }
+        }
+        out.push_str(&format!("<li>{}", hard_breaks(&inline(text))));
+    }
+    while let Some((o, _)) = open.pop() {
+        out.push_str(&format!("</li>\n</{}>\n", tag(o)));
+    }
+    out
+}
+
+/// A line ending in ` +` breaks there.
+fn hard_breaks(html: &str) -> String {
+    let html = html.replace(" +\n", "<br />\n");
+    match html.strip_suffix(" +") {
+        Some(rest) => format!("{rest}<br />"),
+        None => html,
+    }
+}
+
+/// Inline markup in a run of text: `` `code` `` spans first, then macros and
+/// emphasis in the text between them.
+fn inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('`') {
+        let after = &rest[start + 1..];
+        match after.find('`').filter(|&end| end > 0) {
+            Some(end) => {
+                out.push_str(&inline_text(&rest[..start]));
+                out.push_str(&format!("<code>{}</code>", escape_html(&after[..end])));
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&inline_text(&rest[..=start]));
+                rest = after;
+            }
+        }
+    }
+    out.push_str(&inline_text(rest));
+    out
+}
+
+/// Links, inline images and cross references in `text`, with emphasis in the
+/// plain text around and inside them.
+fn inline_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut plain = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let at_boundary = text[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || "(<>[".contains(c));
+        let found = if let Some(xref) = rest.strip_prefix("<<") {
+            xref.find(">>").map(|end| {
+                let (id, label) = match xref[..end].split_once(',') {
+                    Some((id, label)) => (id.trim(), label.trim()),
+                    None => (xref[..end].trim(), xref[..end].trim()),
+                };
+                let html = format!("<a href=\"#{}\">{}</a>", escape_html(id), emphasis(label));
+                (html, 2 + end + 2)
+            })
+        } else if !at_boundary {
+            None
+        } else if let Some(macro_rest) = rest.strip_prefix("link:") {
+            let (target, label) = macro_parts(macro_rest);
+            let len = "link:".len() + target.len() + label.map_or(0, |l| l.len() + 2);
+            (!target.is_empty()).then(|| (link(target, label), len))
+        } else if let Some(macro_rest) = rest.strip_prefix("image:").filter(|r| !r.starts_with(':'))
+        {
+            let (target, alt) = macro_parts(macro_rest);
+            let len = "image:".len() + target.len() + alt.map_or(0, |a| a.len() + 2);
+            (!target.is_empty()).then(|| (image(target, alt), len))
+        } else if ["https://", "http://", "mailto:"]
+            .iter()
+            .any(|scheme| rest.starts_with(scheme))
+        {
+            let (target, label) = macro_parts(rest);
+            let target = match label {
+                Some(_) => target,
+                None => target.trim_end_matches(['.', ',', ';', ':', '!', '?', ')']),
+            };
+            let len = target.len() + label.map_or(0, |l| l.len() + 2);
+            Some((link(target, label), len))
+        } else {
+            None
+        };
+        match found {
+            Some((html, len)) => {
+                out.push_str(&emphasis(&text[plain..i]));
+                out.push_str(&html);
+                i += len;
+                plain = i;
+            }
+            None => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    out.push_str(&emphasis(&text[plain..]));
+    out
+}
+
+/// `target[text]` → `("target", Some("text"))`; without brackets the target
+/// runs to the next whitespace.
+fn macro_parts(rest: &str) -> (&str, Option<&str>) {
+    let end = rest
+        .find(|c: char| c.is_whitespace() || c == '[')
+        .unwrap_or(rest.len());
+    let target = &rest[..end];
+    let label = rest[end..]
+        .strip_prefix('[')
+        .and_then(|r| r.find(']').map(|close| &r[..close]));
+    (target, label)
+}
+
+fn link(target: &str, label: Option<&str>) -> String {
+    let text = label.filter(|l| !l.trim().is_empty()).map_or_else(
+        || escape_html(target.strip_prefix("mailto:").unwrap_or(target)),
+        emphasis,
+    );
+    format!("<a href=\"{}\">{text}</a>", escape_html(target))
+}
+
+fn image(target: &str, alt: Option<&str>) -> String {
+    let alt = alt
+        .and_then(|a| a.split(',').next())
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| {
+            Path::new(target)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+        });
+    format!(
+        "<img src=\"{}\" alt=\"{}\" />",
+        escape_html(target),
+        escape_html(alt)
+    )
+}
+
+/// Escape `text`, then mark up `*strong*` and `_emphasis_`.
+fn emphasis(text: &str) -> String {
+    let strong = constrained(&escape_html(text), '*', "strong");
+    constrained(&strong, '_', "em")
+}
+
+/// Wrap `mark`-delimited runs in `tag`. Like AsciiDoc's constrained quotes,
+/// the marks must sit at word edges, so `snake_case` and `2*3*4` stay text.
+fn constrained(text: &str, mark: char, tag: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let opens = chars[i] == mark
+            && (i == 0 || !chars[i - 1].is_alphanumeric())
+            && chars
+                .get(i + 1)
+                .is_some_and(|c| !c.is_whitespace() && *c != mark);
+        let close = opens
+            .then(|| {
+                (i + 1..chars.len()).find(|&j| {
+                    chars[j] == mark
+                        && !chars[j - 1].is_whitespace()
+                        && chars.get(j + 1).is_none_or(|c| !c.is_alphanumeric())
+                })
+            })
+            .flatten();
+        match close {
+            Some(j) => {
+                let inner: String = chars[i + 1..j].iter().collect();
+                out.push_str(&format!("<{tag}>{inner}</{tag}>"));
+                i = j + 1;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Parsed {
+        AsciidocParser.parse(source, Path::new("doc.adoc")).unwrap()
+    }
+
+    #[test]
+    fn header_becomes_frontmatter() {
+        let parsed = parse(
+            "= Getting Started\nJane Doe <jane@example.com>\n:date: 2024-01-15\n\
+             :tags: rust, web\n:toc:\n:toc_max_level: 3\n:draft!:\n\nBody.\n",
+        );
+        let fm = &parsed.frontmatter;
+        assert_eq!(fm.title, "Getting Started");
+        assert_eq!(fm.get_string("date").as_deref(), Some("2024-01-15"));
+        assert!(matches!(fm.get("tags"), Some(Pod::Array(tags)) if tags.len() == 2));
+        assert!(matches!(fm.get("toc"), Some(Pod::Boolean(true))));
+        assert!(matches!(fm.get("toc_max_level"), Some(Pod::Integer(3))));
+        assert!(fm.get("draft").is_none());
+        assert_eq!(parsed.content_html, "<p>Body.</p>\n");
+    }
+
+    #[test]
+    fn frontmatter_wins_over_header_attributes() {
+        let parsed = parse(
+            "---\ntitle: From YAML\ndate: 2024-02-01\n---\n:date: 1999-01-01\n:lang: fr\n\nHi.\n",
+        );
+        assert_eq!(parsed.frontmatter.title, "From YAML");
+        assert_eq!(
+            parsed.frontmatter.get_string("date").as_deref(),
+            Some("2024-02-01")
+        );
+        assert_eq!(parsed.frontmatter.get_string("lang").as_deref(), Some("fr"));
+        assert_eq!(parsed.content_html, "<p>Hi.</p>\n");
+    }
+
+    #[test]
+    fn missing_title_is_an_error() {
+        let err = AsciidocParser.parse("Just text.\n", Path::new("x.adoc"));
+        assert!(matches!(err, Err(ParserError::Message(_))));
+    }
+
+    #[test]
+    fn renders_sections_lists_and_blocks() {
+        let html = parse(
+            "= Doc\n\n== Setup & Use\n\nRun *this* with _care_ and `cargo *run*`.\n\
+             Second line +\nbroken.\n\n* one\n** nested\n* two\n\n. first\n. second\n\n\
+             [source,rust]\n----\nfn main() {}\n<tag>\n----\n\n[[custom]]\n=== Explicit\n\n\
+             NOTE: Mind the gap.\n\n'''\n\n== Setup & Use\n",
+        )
+        .content_html;
+        assert!(
+            html.contains("<h2 id=\"setup-use\">Setup &amp; Use</h2>"),
+            "{html}"
+        );
+        assert!(html.contains("<h2 id=\"setup-use-1\">"), "{html}");
+        assert!(html.contains("<h3 id=\"custom\">Explicit</h3>"), "{html}");
+        assert!(html.contains(
+            "<p>Run <strong>this</strong> with <em>care</em> and <code>cargo *run*</code>.\n\
+             Second line<br />\nbroken.</p>"
+        ));
+        assert!(
+            html.contains(
+                "<ul>\n<li>one\n<ul>\n<li>nested</li>\n</ul>\n</li>\n<li>two</li>\n</ul>"
+            ),
+            "{html}"
+        );
+        assert!(
+            html.contains("<ol>\n<li>first</li>\n<li>second</li>\n</ol>"),
+            "{html}"
+        );
+        assert!(html.contains(
+            "<pre><code class=\"language-rust\">fn main() {}\n&lt;tag&gt;\n</code></pre>"
+        ));
+        assert!(html.contains(
+            "<div class=\"callout note\">\n<p class=\"callout-title\">Note</p>\n<p>Mind the gap.</p>\n</div>"
+        ));
+        assert!(html.contains("<hr />"));
+    }
+
+    #[test]
+    fn renders_links_images_and_xrefs() {
+        assert_eq!(
+            inline("See https://example.com/a_b_c, link:/guide/[the *guide*] and <<setup,Setup>>."),
+            "See <a href=\"https://example.com/a_b_c\">https://example.com/a_b_c</a>, \
+             <a href=\"/guide/\">the <strong>guide</strong></a> and <a href=\"#setup\">Setup</a>."
+        );
+        assert_eq!(
+            inline("image:logo.png[Logo, 40] snake_case 2*3*4"),
+            "<img src=\"logo.png\" alt=\"Logo\" /> snake_case 2*3*4"
+        );
+    }
+
+    #[test]
+    fn excerpt_ends_at_more_comment() {
+        let parsed = parse("= Post\n\nIntro.\n\n// more\n\nRest.\n");
+        assert_eq!(parsed.excerpt_html.as_deref(), Some("<p>Intro.</p>\n"));
+        assert_eq!(parsed.content_html, "<p>Intro.</p>\n<p>Rest.</p>\n");
+    }
+
+    #[test]
+    fn unclosed_block_warns() {
+        let parsed = parse("= Doc\n\n----\ncode\n");
+        assert_eq!(parsed.content_html, "<pre><code>code\n</code></pre>\n");
+        assert_eq!(parsed.warnings.len(), 1);
+    }
+}
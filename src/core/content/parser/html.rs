@@ -0,0 +1,140 @@
+use super::{ContentParser, Parsed, ParserError, split_excerpt};
+use crate::core::config::SiteConfig;
+use crate::core::content::frontmatter::split_frontmatter;
+use std::ops::Range;
+use std::path::Path;
+
+/// File extensions [`HtmlParser`] claims.
+pub const HTML_EXTENSIONS: &[&str] = &["html", "htm"];
+
+/// Hand-written HTML pages, wrapped in the site template like any other
+/// page. Not registered by default: without it `.html` files in the content
+/// tree are copied verbatim.
+///
+/// Only files that start with YAML or TOML frontmatter become pages; the
+/// HTML after it is the page body, kept as written, and the site's
+/// [`SiteConfig::excerpt_separator`] (`<!-- more -->` by default) ends the
+/// excerpt (one inside a `<pre>` or `<code>` element doesn't). Any other
+/// `.html` file (a complete landing page, a host's verification file) is
+/// left alone and still copied verbatim, so bespoke pages and
+/// template-wrapped fragments can sit side by side:
+///
+/// ```
+/// use std::sync::Arc;
+/// use sherwood::{HtmlParser, ParserRegistry};
+///
+/// let mut registry = ParserRegistry::default();
+/// registry.register(Arc::new(HtmlParser::new()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HtmlParser;
+
+impl HtmlParser {
+    /// A parser with the default options.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ContentParser for HtmlParser {
+    fn extensions(&self) -> &[&str] {
+        HTML_EXTENSIONS
+    }
+
+    fn claims(&self, source: &str) -> bool {
+        matches!(source.lines().next().map(str::trim), Some("---" | "+++"))
+    }
+
+    fn parse(&self, source: &str, path: &Path) -> Result<Parsed, ParserError> {
+        self.parse_with_config(source, path, &SiteConfig::default())
+    }
+
+    fn parse_with_config(
+        &self,
+        source: &str,
+        _path: &Path,
+        config: &SiteConfig,
+    ) -> Result<Parsed, ParserError> {
+        let (frontmatter, body) = split_frontmatter(source)?;
+        let split = split_excerpt(&body, &config.excerpt_separator, &code_elements(&body));
+        let (excerpt_html, content_html) = match split {
+            Some((excerpt, rest)) => (
+                Some(excerpt.trim().to_string()),
+                format!("{}\n{}", excerpt.trim(), rest.trim()),
+            ),
+            None => (None, body.trim().to_string()),
+        };
+        Ok(Parsed {
+            frontmatter,
+            content_html,
+            excerpt_html,
+            warnings: Vec::new(),
+        })
+    }
+}
+
+/// Byte ranges of `<pre>` and `<code>` elements, from the opening tag to the
+/// matching close (or the end of the document when it's missing).
+fn code_elements(html: &str) -> Vec<Range<usize>> {
+    // ASCII lowercasing keeps byte offsets, so ranges index `html` too.
+    let lower = html.to_ascii_lowercase();
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find('<').map(|i| pos + i) {
+        pos = start + 1;
+        let Some(name) = ["pre", "code"].into_iter().find(|name| {
+            lower[pos..]
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.starts_with(|c: char| c == '>' || c.is_ascii_whitespace()))
+        }) else {
+            continue;
+        };
+        let close = format!("</{name}>");
+        let end = lower[pos..]
+            .find(&close)
+            .map_or(html.len(), |i| pos + i + close.len());
+        ranges.push(start..end);
+        pos = end;
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fragment_with_frontmatter_is_a_page() {
+        let source = "---\ntitle: Landing\n---\n<section class=\"hero\">Hi</section>\n";
+        assert!(HtmlParser.claims(source));
+        let parsed = HtmlParser.parse(source, Path::new("landing.html")).unwrap();
+        assert_eq!(parsed.frontmatter.title, "Landing");
+        assert_eq!(parsed.content_html, "<section class=\"hero\">Hi</section>");
+        assert!(parsed.excerpt_html.is_none());
+    }
+
+    #[test]
+    fn more_comment_ends_the_excerpt() {
+        let source = "---\ntitle: Post\n---\n<p>Intro</p>\n<!-- more -->\n<p>Rest</p>\n";
+        let parsed = HtmlParser.parse(source, Path::new("post.html")).unwrap();
+        assert_eq!(parsed.excerpt_html.as_deref(), Some("<p>Intro</p>"));
+        assert_eq!(parsed.content_html, "<p>Intro</p>\n<p>Rest</p>");
+    }
+
+    #[test]
+    fn separator_inside_code_does_not_end_the_excerpt() {
+        let source = "---\ntitle: Post\n---\n<pre><code>a\n<!-- more -->\nb</code></pre>\n\
+                      <p>Intro</p>\n<!-- more -->\n<p>Rest</p>\n";
+        let parsed = HtmlParser.parse(source, Path::new("post.html")).unwrap();
+        assert_eq!(
+            parsed.excerpt_html.as_deref(),
+            Some("<pre><code>a\n<!-- more -->\nb</code></pre>\n<p>Intro</p>")
+        );
+    }
+
+    #[test]
+    fn documents_without_frontmatter_are_not_claimed() {
+        assert!(!HtmlParser.claims("<!DOCTYPE html>\n<html><body>Hi</body></html>\n"));
+        assert!(!HtmlParser.claims(""));
+    }
+}
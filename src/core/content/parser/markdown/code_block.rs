@@ -0,0 +1,151 @@
+//! Fenced code block attributes: highlighted-line ranges and line numbers.
+//!
+//! An info string like ` ```rust {1,3-5} ` names the language and marks lines
+//! 1 and 3–5 as highlighted. Such blocks (and every block, when line numbers
+//! are on) are rendered with one `<span class="line">` per source line so
+//! stylesheets can target individual lines; plain blocks keep
+//! `pulldown-cmark`'s stock markup. With mermaid on, ` ```mermaid ` blocks
+//! become diagram sources instead (see [`render_mermaid`]).
+
+use super::escape_html;
+
+/// The parsed info string of a fenced code block.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct CodeBlockInfo {
+    /// Language token (`rust`), or empty when none was given.
+    pub(super) lang: String,
+    /// Inclusive, 1-based highlighted line ranges.
+    pub(super) highlight: Vec<(usize, usize)>,
+}
+
+impl CodeBlockInfo {
+    /// Parse an info string. The language is the first token before any
+    /// `{...}` attribute block; inside the braces, `N` and `N-M` entries
+    /// (comma- or space-separated) are highlight ranges. Anything else —
+    /// unknown attributes, inverted or zero ranges — is ignored.
+    pub(super) fn parse(info: &str) -> Self {
+        let (head, attrs) = match info.split_once('{') {
+            Some((head, rest)) => (head, rest.split('}').next().unwrap_or("")),
+            None => (info, ""),
+        };
+        let lang = head.split_whitespace().next().unwrap_or("").to_string();
+        let highlight = attrs
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter_map(parse_range)
+            .collect();
+        Self { lang, highlight }
+    }
+
+    fn is_highlighted(&self, line: usize) -> bool {
+        self.highlight
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&line))
+    }
+
+    /// Render the block's `code` as a `<pre><code>` with one span per line.
+    /// `line_numbers` adds the `line-numbers` class to the `<pre>`; every line
+    /// carries its number in `data-line` either way.
+    pub(super) fn render(&self, code: &str, line_numbers: bool) -> String {
+        let mut html = String::from("<pre");
+        if line_numbers {
+            html.push_str(" class=\"line-numbers\"");
+        }
+        html.push_str("><code");
+        if !self.lang.is_empty() {
+            html.push_str(&format!(" class=\"language-{}\"", escape_html(&self.lang)));
+        }
+        html.push('>');
+        for (i, line) in code.lines().enumerate() {
+            let n = i + 1;
+            let class = if self.is_highlighted(n) {
+                "line highlighted"
+            } else {
+                "line"
+            };
+            html.push_str(&format!(
+                "<span class=\"{class}\" data-line=\"{n}\">{}</span>\n",
+                escape_html(line)
+            ));
+        }
+        html.push_str("</code></pre>\n");
+        html
+    }
+}
+
+/// Language token of fenced blocks holding a mermaid diagram.
+pub(super) const MERMAID: &str = "mermaid";
+
+/// A mermaid diagram's source as `<pre class="mermaid">`, which the mermaid
+/// script finds and replaces with an SVG in the browser. No `<code>` or line
+/// spans: mermaid reads the element's text as the diagram.
+pub(super) fn render_mermaid(code: &str) -> String {
+    format!("<pre class=\"{MERMAID}\">{}</pre>\n", escape_html(code))
+}
+
+fn parse_range(token: &str) -> Option<(usize, usize)> {
+    let token = token.trim();
+    let (start, end) = match token.split_once('-') {
+        Some((a, b)) => (a.trim().parse().ok()?, b.trim().parse().ok()?),
+        None => {
+            let n = token.parse().ok()?;
+            (n, n)
+        }
+    };
+    (start >= 1 && start <= end).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lang_and_ranges() {
+        let info = CodeBlockInfo::parse("rust {1,3-5}");
+        assert_eq!(info.lang, "rust");
+        assert_eq!(info.highlight, vec![(1, 1), (3, 5)]);
+    }
+
+    #[test]
+    fn parses_ranges_without_lang() {
+        let info = CodeBlockInfo::parse("{2}");
+        assert_eq!(info.lang, "");
+        assert_eq!(info.highlight, vec![(2, 2)]);
+    }
+
+    #[test]
+    fn ignores_unrecognized_attributes() {
+        let info = CodeBlockInfo::parse("js {title=\"x\", 2, 5-3, 0, foo}");
+        assert_eq!(info.lang, "js");
+        assert_eq!(info.highlight, vec![(2, 2)]);
+    }
+
+    #[test]
+    fn plain_lang_has_no_ranges() {
+        assert_eq!(
+            CodeBlockInfo::parse("python"),
+            CodeBlockInfo {
+                lang: "python".to_string(),
+                highlight: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn render_marks_highlighted_lines() {
+        let info = CodeBlockInfo::parse("rust {2}");
+        let html = info.render("let a = 1;\nlet b = a < 2;\n", false);
+        assert!(html.starts_with("<pre><code class=\"language-rust\">"));
+        assert!(html.contains("<span class=\"line\" data-line=\"1\">let a = 1;</span>"));
+        assert!(
+            html.contains(
+                "<span class=\"line highlighted\" data-line=\"2\">let b = a &lt; 2;</span>"
+            )
+        );
+    }
+
+    #[test]
+    fn render_line_numbers_class() {
+        let html = CodeBlockInfo::default().render("x\n", true);
+        assert!(html.starts_with("<pre class=\"line-numbers\"><code>"));
+    }
+}
@@ -0,0 +1,125 @@
+//! `:shortcode:` emoji. With the feature on, text like `:smile:` becomes 😄,
+//! looked up in the gemoji names bundled by the `emojis` crate. Only text
+//! events are rewritten, so inline code, code blocks, raw HTML and link
+//! destinations keep their colons; autolinked URLs are skipped too. Unknown
+//! names stay as written.
+
+use pulldown_cmark::{CowStr, Event, LinkType, Tag, TagEnd};
+
+/// Replace known shortcodes in the text events of `events`. Adjacent text
+/// events are merged first, since `pulldown-cmark` may split a run of text
+/// at characters like `_` that could have opened emphasis.
+pub(super) fn expand_emoji(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut out: Vec<Event> = Vec::with_capacity(events.len());
+    // Inside a code block or an autolink, where text is left alone.
+    let mut verbatim = false;
+    // Index in `out` of the run of prose text being collected.
+    let mut run: Option<usize> = None;
+    for event in events {
+        match &event {
+            Event::Start(Tag::CodeBlock(_))
+            | Event::Start(Tag::Link {
+                link_type: LinkType::Autolink | LinkType::Email,
+                ..
+            }) => verbatim = true,
+            Event::End(TagEnd::CodeBlock | TagEnd::Link) => verbatim = false,
+            _ => {}
+        }
+        if let Event::Text(text) = &event
+            && !verbatim
+        {
+            if let Some(Event::Text(prev)) = run.and_then(|i| out.get_mut(i)) {
+                *prev = CowStr::from(format!("{prev}{text}"));
+                continue;
+            }
+            run = Some(out.len());
+        } else {
+            expand_run(&mut out, run.take());
+        }
+        out.push(event);
+    }
+    expand_run(&mut out, run);
+    out
+}
+
+/// Replace the shortcodes in the text event at `run`, once it is complete.
+fn expand_run(out: &mut [Event<'_>], run: Option<usize>) {
+    if let Some(Event::Text(text)) = run.and_then(|i| out.get_mut(i))
+        && let Some(replaced) = replace_shortcodes(text)
+    {
+        *text = replaced.into();
+    }
+}
+
+/// `text` with every known `:name:` replaced by its emoji, or `None` when
+/// there was nothing to replace. A shortcode must stand apart from words on
+/// both sides, so times and ratios (`10:30:45`, `a:b:c`) stay as written.
+fn replace_shortcodes(text: &str) -> Option<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut replaced = false;
+    while let Some(start) = rest.find(':') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let opens = !out.chars().next_back().is_some_and(char::is_alphanumeric);
+        let emoji = after
+            .find(':')
+            .map(|end| &after[..end])
+            .filter(|name| opens && is_shortcode(name))
+            .filter(|name| {
+                !after[name.len() + 1..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric)
+            })
+            .and_then(|name| emojis::get_by_shortcode(name).map(|e| (name, e)));
+        match emoji {
+            Some((name, emoji)) => {
+                out.push_str(emoji.as_str());
+                rest = &after[name.len() + 1..];
+                replaced = true;
+            }
+            // The closing colon may open the next shortcode.
+            None => {
+                out.push(':');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    replaced.then_some(out)
+}
+
+/// Gemoji names are lowercase ASCII letters, digits, `_`, `+` and `-`.
+fn is_shortcode(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_+-".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_known_names_and_keeps_the_rest() {
+        assert_eq!(
+            replace_shortcodes("hi :smile: and :+1:").as_deref(),
+            Some("hi 😄 and 👍")
+        );
+        assert_eq!(replace_shortcodes("a:b:c :nope: 10:30"), None);
+        assert_eq!(
+            replace_shortcodes("(:smile:) :smile::+1:").as_deref(),
+            Some("(😄) 😄👍")
+        );
+    }
+
+    #[test]
+    fn needs_word_boundaries_around_shortcodes() {
+        assert_eq!(replace_shortcodes("at 10:smile:"), None);
+        assert_eq!(replace_shortcodes("10:100:20"), None);
+        assert_eq!(replace_shortcodes(":smile:s"), None);
+        assert_eq!(replace_shortcodes("a:b:c"), None);
+    }
+}
@@ -0,0 +1,164 @@
+//! Which markdown extensions [`MarkdownParser`](super::MarkdownParser)
+//! recognizes. Everything but mermaid diagrams and emoji is on by default; turning a
+//! construct off makes its syntax plain text (a `|` table stays a paragraph
+//! of pipes).
+
+use pulldown_cmark::Options;
+
+/// Markdown constructs beyond CommonMark, each on by default except
+/// [`mermaid`](Self::mermaid) and [`emoji`](Self::emoji). Set with
+/// [`MarkdownParser::with_features`](super::MarkdownParser::with_features),
+/// or the `[markdown]` table of a config file
+/// ([`SiteConfig::markdown`](crate::SiteConfig::markdown)).
+///
+/// Autolinks aren't listed: `<https://…>` links are core CommonMark, and
+/// bare URLs are never linked.
+///
+/// ```
+/// use sherwood::{MarkdownFeatures, MarkdownParser};
+///
+/// let parser = MarkdownParser::new()
+///     .with_features(MarkdownFeatures::new().with_tables(false).with_math(false));
+/// assert_eq!(parser.render("$x$"), "<p>$x$</p>\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MarkdownFeatures {
+    /// GitHub-style `| a | b |` tables.
+    pub tables: bool,
+    /// `~~struck~~` text.
+    pub strikethrough: bool,
+    /// `[^1]` footnote references and definitions.
+    pub footnotes: bool,
+    /// `- [x] done` task list items.
+    pub task_lists: bool,
+    /// `$inline$` and `$$display$$` math, rendered as `<span class="math …">`
+    /// for a client-side renderer to typeset.
+    pub math: bool,
+    /// Curly quotes, `–` and `—` from `--` and `---`, and `…` from `...`.
+    pub smart_punctuation: bool,
+    /// ` ```mermaid ` blocks rendered as `<pre class="mermaid">` for the
+    /// mermaid script to draw, instead of as code. Off by default.
+    pub mermaid: bool,
+    /// `:smile:` shortcodes in text replaced by their emoji (😄), using the
+    /// gemoji names. Code and URLs are left alone, as are unknown names. Off
+    /// by default.
+    pub emoji: bool,
+}
+
+impl Default for MarkdownFeatures {
+    fn default() -> Self {
+        Self {
+            tables: true,
+            strikethrough: true,
+            footnotes: true,
+            task_lists: true,
+            math: true,
+            smart_punctuation: true,
+            mermaid: false,
+            emoji: false,
+        }
+    }
+}
+
+impl MarkdownFeatures {
+    /// Every construct on, except mermaid diagrams and emoji.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turn tables on or off.
+    pub fn with_tables(mut self, enabled: bool) -> Self {
+        self.tables = enabled;
+        self
+    }
+
+    /// Turn strikethrough on or off.
+    pub fn with_strikethrough(mut self, enabled: bool) -> Self {
+        self.strikethrough = enabled;
+        self
+    }
+
+    /// Turn footnotes on or off.
+    pub fn with_footnotes(mut self, enabled: bool) -> Self {
+        self.footnotes = enabled;
+        self
+    }
+
+    /// Turn task lists on or off.
+    pub fn with_task_lists(mut self, enabled: bool) -> Self {
+        self.task_lists = enabled;
+        self
+    }
+
+    /// Turn math on or off.
+    pub fn with_math(mut self, enabled: bool) -> Self {
+        self.math = enabled;
+        self
+    }
+
+    /// Turn smart punctuation on or off.
+    pub fn with_smart_punctuation(mut self, enabled: bool) -> Self {
+        self.smart_punctuation = enabled;
+        self
+    }
+
+    /// Turn mermaid diagram blocks on or off.
+    pub fn with_mermaid(mut self, enabled: bool) -> Self {
+        self.mermaid = enabled;
+        self
+    }
+
+    /// Turn emoji shortcodes on or off.
+    pub fn with_emoji(mut self, enabled: bool) -> Self {
+        self.emoji = enabled;
+        self
+    }
+
+    /// The `pulldown-cmark` options: every extension, minus the ones turned
+    /// off. Heading attributes, metadata blocks and the rest stay on.
+    pub(super) fn options(&self) -> Options {
+        let mut options = Options::all();
+        let toggles = [
+            (self.tables, Options::ENABLE_TABLES),
+            (self.strikethrough, Options::ENABLE_STRIKETHROUGH),
+            // Clears both footnote flags: `all()` includes the old-style
+            // footnote syntax, which implies the new one.
+            (self.footnotes, Options::ENABLE_OLD_FOOTNOTES),
+            (self.task_lists, Options::ENABLE_TASKLISTS),
+            (self.math, Options::ENABLE_MATH),
+            (self.smart_punctuation, Options::ENABLE_SMART_PUNCTUATION),
+        ];
+        for (enabled, flag) in toggles {
+            if !enabled {
+                options.remove(flag);
+            }
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keeps_every_extension() {
+        assert_eq!(MarkdownFeatures::new().options(), Options::all());
+        assert!(!MarkdownFeatures::new().mermaid);
+        assert!(!MarkdownFeatures::new().emoji);
+    }
+
+    #[test]
+    fn disabled_features_clear_their_flags() {
+        let options = MarkdownFeatures::new()
+            .with_footnotes(false)
+            .with_tables(false)
+            .options();
+        assert!(!options.contains(Options::ENABLE_TABLES));
+        assert!(!options.contains(Options::ENABLE_FOOTNOTES));
+        assert!(!options.contains(Options::ENABLE_OLD_FOOTNOTES));
+        assert!(options.contains(Options::ENABLE_STRIKETHROUGH));
+        assert!(options.contains(Options::ENABLE_HEADING_ATTRIBUTES));
+    }
+}
@@ -0,0 +1,166 @@
+//! Heading anchors: every heading gets an `id` slugged from its text, so
+//! pages can be deep-linked and tables of contents can point into them.
+//! Explicit ids (`## Setup {#install}`) are kept as written. Optionally each
+//! heading also gets a visible self-link ([`HeadingAnchors`]).
+
+use super::escape_html;
+use crate::core::content::slug::{slugify, unique_slug};
+use pulldown_cmark::{CowStr, Event, Tag, TagEnd};
+use std::collections::HashSet;
+
+/// Class on the self-links [`HeadingAnchors`] adds. Tables of contents drop
+/// elements with it from heading text.
+pub(crate) const ANCHOR_CLASS: &str = "heading-anchor";
+
+/// Which side of the heading text a self-link goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnchorPosition {
+    /// `<h2 id="setup"><a …>¶</a> Setup</h2>`
+    Before,
+    /// `<h2 id="setup">Setup <a …>¶</a></h2>`
+    #[default]
+    After,
+}
+
+/// A clickable self-link in every heading with an id, so readers can copy a
+/// section's URL. Enabled with
+/// [`MarkdownParser::with_heading_anchors`](super::MarkdownParser::with_heading_anchors).
+///
+/// The link is `<a class="heading-anchor" href="#id" aria-label="Link to this
+/// section">¶</a>`; the default template copies its URL to the clipboard on
+/// click.
+///
+/// ```
+/// use sherwood::{AnchorPosition, HeadingAnchors, MarkdownParser};
+///
+/// let parser = MarkdownParser::new().with_heading_anchors(
+///     HeadingAnchors::new().with_symbol("#").with_position(AnchorPosition::Before),
+/// );
+/// assert_eq!(
+///     parser.render("## Setup"),
+///     "<h2 id=\"setup\"><a class=\"heading-anchor\" href=\"#setup\" \
+///      aria-label=\"Link to this section\">#</a> Setup</h2>\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HeadingAnchors {
+    /// Link text, `¶` by default.
+    pub symbol: String,
+    /// Before or after the heading text (after by default).
+    pub position: AnchorPosition,
+}
+
+impl Default for HeadingAnchors {
+    fn default() -> Self {
+        Self {
+            symbol: "¶".to_string(),
+            position: AnchorPosition::default(),
+        }
+    }
+}
+
+impl HeadingAnchors {
+    /// A `¶` link after the heading text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the link text.
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = symbol.into();
+        self
+    }
+
+    /// Put the link before or after the heading text.
+    pub fn with_position(mut self, position: AnchorPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    fn link(&self, id: &str) -> String {
+        let link = format!(
+            "<a class=\"{ANCHOR_CLASS}\" href=\"#{}\" aria-label=\"Link to this section\">{}</a>",
+            escape_html(id),
+            escape_html(&self.symbol)
+        );
+        match self.position {
+            AnchorPosition::Before => format!("{link} "),
+            AnchorPosition::After => format!(" {link}"),
+        }
+    }
+}
+
+/// Give each heading without an explicit id a unique slug id. Repeats get a
+/// numeric suffix (`setup`, `setup-1`, …); a heading with no sluggable text
+/// becomes `section`.
+pub(super) fn assign_heading_ids(events: &mut [Event<'_>]) {
+    let mut used: HashSet<String> = events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Start(Tag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+            _ => None,
+        })
+        .collect();
+    let unnamed: Vec<usize> = events
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| matches!(e, Event::Start(Tag::Heading { id: None, .. })))
+        .map(|(i, _)| i)
+        .collect();
+    for i in unnamed {
+        let slug = unique_slug(
+            slugify(&heading_text(&events[i + 1..])),
+            "section",
+            &mut used,
+        );
+        if let Event::Start(Tag::Heading { id, .. }) = &mut events[i] {
+            *id = Some(slug.into());
+        }
+    }
+}
+
+/// Add `anchors`' self-link to every heading that has an id. Runs after
+/// [`assign_heading_ids`], so the ids are final.
+pub(super) fn add_heading_anchors<'a>(
+    events: Vec<Event<'a>>,
+    anchors: &HeadingAnchors,
+) -> Vec<Event<'a>> {
+    let mut out = Vec::with_capacity(events.len());
+    // An `After` link waiting for its heading's end tag.
+    let mut pending: Option<String> = None;
+    for event in events {
+        match &event {
+            Event::Start(Tag::Heading { id: Some(id), .. }) => {
+                let link = anchors.link(id);
+                out.push(event);
+                match anchors.position {
+                    AnchorPosition::Before => out.push(Event::InlineHtml(CowStr::from(link))),
+                    AnchorPosition::After => pending = Some(link),
+                }
+                continue;
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(link) = pending.take() {
+                    out.push(Event::InlineHtml(CowStr::from(link)));
+                }
+            }
+            _ => {}
+        }
+        out.push(event);
+    }
+    out
+}
+
+/// Plain text of a heading: text and inline code up to its end tag.
+fn heading_text(events: &[Event<'_>]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            Event::End(TagEnd::Heading(_)) => break,
+            Event::Text(t) | Event::Code(t) => text.push_str(t),
+            _ => {}
+        }
+    }
+    text
+}
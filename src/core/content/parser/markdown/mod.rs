@@ -0,0 +1,562 @@
+use super::{ContentParser, Parsed, ParserError, split_excerpt};
+use crate::core::config::SiteConfig;
+use crate::core::content::frontmatter::split_frontmatter;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd, html};
+use std::path::Path;
+
+mod code_block;
+mod emoji;
+mod features;
+mod headings;
+mod shortcode;
+
+use code_block::{CodeBlockInfo, MERMAID, render_mermaid};
+use emoji::expand_emoji;
+pub use features::MarkdownFeatures;
+pub(crate) use headings::ANCHOR_CLASS;
+pub use headings::{AnchorPosition, HeadingAnchors};
+use headings::{add_heading_anchors, assign_heading_ids};
+pub use shortcode::{Shortcode, ShortcodeCall, ShortcodeRegistry};
+
+/// File extensions [`MarkdownParser`] claims. `.mdx` is deliberately absent:
+/// it embeds JSX, which isn't markdown.
+pub const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd", "mkdn"];
+
+/// Default excerpt separator: everything before it (if present) becomes the
+/// page's excerpt.
+pub const DEFAULT_EXCERPT_SEPARATOR: &str = "<!-- more -->";
+
+/// The built-in markdown parser. Handles [`MARKDOWN_EXTENSIONS`], splits YAML or
+/// TOML frontmatter via [`split_frontmatter`], renders the body with
+/// `pulldown-cmark`, and extracts an optional excerpt — everything before the
+/// excerpt separator (the site's
+/// [`SiteConfig::excerpt_separator`], `<!-- more -->` by default, unless
+/// changed with [`with_excerpt_separator`](Self::with_excerpt_separator)).
+///
+/// Headings get slugged `id`s (`## Getting Started` →
+/// `<h2 id="getting-started">`) unless disabled with
+/// [`with_heading_ids`](Self::with_heading_ids), and a `¶` self-link with
+/// [`with_heading_anchors`](Self::with_heading_anchors). Shortcodes
+/// (`{{< youtube id >}}`) are expanded by the handlers in
+/// [`with_shortcodes`](Self::with_shortcodes); an unknown one is left as text
+/// and reported in [`Parsed::warnings`].
+///
+/// Tables, footnotes, math and the other extensions follow the site's
+/// [`SiteConfig::markdown`] (all recognized by default) unless the parser
+/// picks its own with [`with_features`](Self::with_features).
+///
+/// Rendering options are set with the `with_*` builder methods; register a
+/// configured parser in place of the default one:
+///
+/// ```
+/// use std::sync::Arc;
+/// use sherwood::{MarkdownParser, ParserRegistry};
+///
+/// let mut registry = ParserRegistry::default();
+/// registry.register(Arc::new(MarkdownParser::new().with_line_numbers(true)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownParser {
+    line_numbers: bool,
+    excerpt_separator: Option<String>,
+    heading_ids: bool,
+    heading_anchors: Option<HeadingAnchors>,
+    features: Option<MarkdownFeatures>,
+    shortcodes: ShortcodeRegistry,
+}
+
+impl Default for MarkdownParser {
+    fn default() -> Self {
+        Self {
+            line_numbers: false,
+            excerpt_separator: None,
+            heading_ids: true,
+            heading_anchors: None,
+            features: None,
+            shortcodes: ShortcodeRegistry::new(),
+        }
+    }
+}
+
+impl MarkdownParser {
+    /// A parser with the default rendering options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the marker that ends a page's excerpt, e.g. `<!--more-->`, in
+    /// place of the site's [`SiteConfig::excerpt_separator`]. The marker is
+    /// removed from the rendered body; a page without it has no excerpt. An
+    /// empty marker matches nothing.
+    pub fn with_excerpt_separator(mut self, separator: impl Into<String>) -> Self {
+        self.excerpt_separator = Some(separator.into());
+        self
+    }
+
+    /// Render every fenced code block line by line with a `line-numbers`
+    /// class on its `<pre>`, so stylesheets can show line numbers. Blocks
+    /// with a highlight range (` ```rust {1,3-5} `) are rendered line by line
+    /// regardless.
+    pub fn with_line_numbers(mut self, enabled: bool) -> Self {
+        self.line_numbers = enabled;
+        self
+    }
+
+    /// Give headings `id` attributes slugged from their text (on by
+    /// default). Explicit `{#id}` attributes are always kept. Tables of
+    /// contents link to these ids, so pages with `toc: true` need them.
+    pub fn with_heading_ids(mut self, enabled: bool) -> Self {
+        self.heading_ids = enabled;
+        self
+    }
+
+    /// Add a clickable self-link (`¶` unless configured otherwise) to every
+    /// heading with an id (off by default). Tables of contents leave it out
+    /// of the heading text.
+    pub fn with_heading_anchors(mut self, anchors: HeadingAnchors) -> Self {
+        self.heading_anchors = Some(anchors);
+        self
+    }
+
+    /// Choose which markdown extensions are recognized, in place of the
+    /// site's [`SiteConfig::markdown`] (all by default).
+    pub fn with_features(mut self, features: MarkdownFeatures) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// Expand shortcodes with the handlers in `shortcodes`. Tags inside code
+    /// blocks and inline code are left alone.
+    pub fn with_shortcodes(mut self, shortcodes: ShortcodeRegistry) -> Self {
+        self.shortcodes = shortcodes;
+        self
+    }
+
+    /// Render a markdown string to an HTML fragment with this parser's
+    /// options and [`MarkdownFeatures`] (the defaults unless set with
+    /// [`with_features`](Self::with_features)). Shortcode
+    /// warnings are discarded; [`parse`](ContentParser::parse) reports them.
+    pub fn render(&self, markdown: &str) -> String {
+        let expanded = shortcode::expand(markdown, &self.shortcodes, &mut Vec::new());
+        let features = self.features.unwrap_or_default();
+        expanded.restore(&self.render_markdown(&expanded.markdown, features))
+    }
+
+    /// Render markdown whose shortcodes have already been expanded.
+    fn render_markdown(&self, markdown: &str, features: MarkdownFeatures) -> String {
+        let parser = Parser::new_ext(markdown, features.options());
+        // Whether a code block is a mermaid diagram to render as such.
+        let is_mermaid = |info: &CodeBlockInfo| features.mermaid && info.lang == MERMAID;
+        let mut events = Vec::new();
+        // A code block being buffered for line-by-line rendering.
+        let mut open: Option<(CodeBlockInfo, String)> = None;
+        for event in parser {
+            match event {
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let info = match &kind {
+                        CodeBlockKind::Fenced(info) => CodeBlockInfo::parse(info),
+                        CodeBlockKind::Indented => CodeBlockInfo::default(),
+                    };
+                    if self.line_numbers || !info.highlight.is_empty() || is_mermaid(&info) {
+                        open = Some((info, String::new()));
+                    } else {
+                        events.push(Event::Start(Tag::CodeBlock(kind)));
+                    }
+                }
+                Event::Text(text) => match &mut open {
+                    Some((_, code)) => code.push_str(&text),
+                    None => events.push(Event::Text(text)),
+                },
+                Event::End(TagEnd::CodeBlock) => match open.take() {
+                    Some((info, code)) if is_mermaid(&info) => {
+                        events.push(Event::Html(render_mermaid(&code).into()))
+                    }
+                    Some((info, code)) => {
+                        events.push(Event::Html(info.render(&code, self.line_numbers).into()))
+                    }
+                    None => events.push(Event::End(TagEnd::CodeBlock)),
+                },
+                other => events.push(other),
+            }
+        }
+        if features.emoji {
+            events = expand_emoji(events);
+        }
+        if self.heading_ids {
+            assign_heading_ids(&mut events);
+        }
+        if let Some(anchors) = &self.heading_anchors {
+            events = add_heading_anchors(events, anchors);
+        }
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, events.into_iter());
+        html_output
+    }
+}
+
+impl ContentParser for MarkdownParser {
+    fn extensions(&self) -> &[&str] {
+        MARKDOWN_EXTENSIONS
+    }
+
+    fn parse(&self, source: &str, path: &Path) -> Result<Parsed, ParserError> {
+        self.parse_with_config(source, path, &SiteConfig::default())
+    }
+
+    fn parse_with_config(
+        &self,
+        source: &str,
+        _path: &Path,
+        config: &SiteConfig,
+    ) -> Result<Parsed, ParserError> {
+        let (frontmatter, body) = split_frontmatter(source)?;
+        let mut warnings = Vec::new();
+        let expanded = shortcode::expand(&body, &self.shortcodes, &mut warnings);
+        let features = self.features.unwrap_or(config.markdown);
+        let render = |markdown: &str| expanded.restore(&self.render_markdown(markdown, features));
+        let separator = self
+            .excerpt_separator
+            .as_deref()
+            .unwrap_or(&config.excerpt_separator);
+        let code = shortcode::code_ranges(&expanded.markdown);
+        let split = split_excerpt(&expanded.markdown, separator, &code);
+        let (excerpt_html, content_html) = match split {
+            // The separator is authoring markup, not content: render the
+            // body with it spliced out so it never reaches the full page.
+            Some((before, after)) => (Some(render(before)), render(&format!("{before}{after}"))),
+            None => (None, render(&expanded.markdown)),
+        };
+        Ok(Parsed {
+            frontmatter,
+            content_html,
+            excerpt_html,
+            warnings,
+        })
+    }
+}
+
+/// Render a markdown string to an HTML fragment with all `pulldown-cmark`
+/// extensions enabled and the default [`MarkdownParser`] options.
+pub fn markdown_to_html(markdown: &str) -> String {
+    MarkdownParser::default().render(markdown)
+}
+
+/// Escape text for safe inclusion in HTML element content or a quoted
+/// attribute value.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Parsed {
+        MarkdownParser::default()
+            .parse(source, Path::new("test.md"))
+            .expect("parse should succeed")
+    }
+
+    #[test]
+    fn renders_heading_and_body() {
+        let parsed = parse("---\ntitle: Post\n---\n\n# Hello\n\nBody.");
+        assert!(parsed.content_html.contains("<h1 id=\"hello\">Hello</h1>"));
+        assert!(parsed.content_html.contains("<p>Body.</p>"));
+        assert_eq!(parsed.frontmatter.title, "Post");
+    }
+
+    #[test]
+    fn shortcodes_expand_in_blocks_and_inline() {
+        let mut shortcodes = ShortcodeRegistry::new();
+        shortcodes.register("kbd", |call: &ShortcodeCall| {
+            Ok(format!("<kbd>{}</kbd>", call.arg(0).unwrap_or_default()))
+        });
+        let parsed = MarkdownParser::new()
+            .with_shortcodes(shortcodes)
+            .parse(
+                "---\ntitle: Keys\n---\n\nPress {{< kbd Esc >}}.\n\n<!-- more -->\n\n\
+                 {{< kbd Enter >}}\n\n{{< missing >}}\n",
+                Path::new("keys.md"),
+            )
+            .unwrap();
+        assert_eq!(
+            parsed.excerpt_html.as_deref(),
+            Some("<p>Press <kbd>Esc</kbd>.</p>\n")
+        );
+        assert!(
+            parsed.content_html.contains("<kbd>Enter</kbd>\n"),
+            "{}",
+            parsed.content_html
+        );
+        assert!(!parsed.content_html.contains("<p><kbd>Enter"));
+        assert!(parsed.content_html.contains("<p>{{&lt; missing &gt;}}</p>"));
+        assert_eq!(
+            parsed.warnings,
+            ["unknown shortcode `missing` left as text"]
+        );
+    }
+
+    #[test]
+    fn excerpt_extracted_when_delimiter_present() {
+        let parsed =
+            parse("---\ntitle: Post\n---\n\nIntro line.\n\n<!-- more -->\n\nRest of post.");
+        let excerpt = parsed.excerpt_html.expect("excerpt should be set");
+        assert!(excerpt.contains("Intro line."));
+        assert!(!excerpt.contains("Rest of post."));
+    }
+
+    #[test]
+    fn separator_stripped_from_body() {
+        let parsed = parse("---\ntitle: Post\n---\n\nIntro.\n\n<!-- more -->\n\nRest.");
+        assert!(
+            !parsed.content_html.contains("more"),
+            "{}",
+            parsed.content_html
+        );
+        assert!(parsed.content_html.contains("<p>Intro.</p>"));
+        assert!(parsed.content_html.contains("<p>Rest.</p>"));
+    }
+
+    #[test]
+    fn custom_excerpt_separator() {
+        let parsed = MarkdownParser::new()
+            .with_excerpt_separator("<!--more-->")
+            .parse(
+                "---\ntitle: Post\n---\n\nIntro.\n\n<!--more-->\n\nRest.",
+                Path::new("x.md"),
+            )
+            .unwrap();
+        let excerpt = parsed.excerpt_html.expect("excerpt should be set");
+        assert!(excerpt.contains("Intro."));
+        assert!(!excerpt.contains("Rest."));
+        assert!(!parsed.content_html.contains("<!--more-->"));
+    }
+
+    #[test]
+    fn site_excerpt_separator_unless_parser_sets_one() {
+        let source = "---\ntitle: Post\n---\n\nIntro.\n\n<!--cut-->\n\nRest.";
+        let config = SiteConfig::default().with_excerpt_separator("<!--cut-->");
+        let parsed = MarkdownParser::new()
+            .parse_with_config(source, Path::new("x.md"), &config)
+            .unwrap();
+        assert!(parsed.excerpt_html.unwrap().contains("Intro."));
+        let parsed = MarkdownParser::new()
+            .with_excerpt_separator("<!--more-->")
+            .parse_with_config(source, Path::new("x.md"), &config)
+            .unwrap();
+        assert!(parsed.excerpt_html.is_none());
+        let parsed = MarkdownParser::new()
+            .with_excerpt_separator("")
+            .parse(source, Path::new("x.md"))
+            .unwrap();
+        assert!(parsed.excerpt_html.is_none());
+    }
+
+    #[test]
+    fn separator_in_code_is_left_alone() {
+        let parsed = parse(
+            "---\ntitle: Post\n---\n\nUse `<!-- more -->` to cut.\n\n\
+             ```html\n<p>Intro</p>\n<!-- more -->\n```\n\nTail.\n\n<!-- more -->\n\nRest.",
+        );
+        let excerpt = parsed.excerpt_html.expect("excerpt should be set");
+        assert!(excerpt.contains("Tail."), "{excerpt}");
+        assert!(
+            excerpt.contains("&lt;!-- more --&gt;\n</code></pre>"),
+            "{excerpt}"
+        );
+        assert!(!excerpt.contains("Rest."));
+        assert_eq!(
+            parsed.content_html.matches("&lt;!-- more --&gt;").count(),
+            2,
+            "{}",
+            parsed.content_html
+        );
+    }
+
+    #[test]
+    fn no_excerpt_when_delimiter_absent() {
+        let parsed = parse("---\ntitle: Post\n---\n\nJust a body.");
+        assert!(parsed.excerpt_html.is_none());
+    }
+
+    #[test]
+    fn heading_ids_are_unique_and_respect_explicit_ids() {
+        let html =
+            markdown_to_html("## Setup\n\n## Setup\n\n## Other {#custom}\n\n## `code` here\n");
+        assert!(html.contains("<h2 id=\"setup\">Setup</h2>"), "{html}");
+        assert!(html.contains("<h2 id=\"setup-1\">Setup</h2>"), "{html}");
+        assert!(html.contains("<h2 id=\"custom\">Other</h2>"), "{html}");
+        assert!(html.contains("<h2 id=\"code-here\">"), "{html}");
+    }
+
+    #[test]
+    fn heading_ids_can_be_disabled() {
+        let html = MarkdownParser::new()
+            .with_heading_ids(false)
+            .render("## Plain\n");
+        assert_eq!(html, "<h2>Plain</h2>\n");
+    }
+
+    #[test]
+    fn heading_anchors_link_each_heading() {
+        let html = MarkdownParser::new()
+            .with_heading_anchors(HeadingAnchors::new())
+            .render("# Title\n\n## A & B {#ab}\n\ntext\n");
+        assert!(
+            html.contains(
+                "<h1 id=\"title\">Title <a class=\"heading-anchor\" href=\"#title\" \
+                 aria-label=\"Link to this section\">¶</a></h1>"
+            ),
+            "{html}"
+        );
+        assert!(html.contains("href=\"#ab\""), "{html}");
+        assert!(html.contains("<p>text</p>"), "{html}");
+
+        let html = MarkdownParser::new()
+            .with_heading_ids(false)
+            .with_heading_anchors(HeadingAnchors::new().with_symbol("<#>"))
+            .render("## Plain\n\n## Named {#named}\n");
+        assert!(html.contains("<h2>Plain</h2>"), "{html}");
+        assert!(html.contains(">&lt;#&gt;</a></h2>"), "{html}");
+    }
+
+    #[test]
+    fn disabled_features_render_as_text() {
+        let source = "| a | b |\n|---|---|\n| 1 | 2 |\n\n~~gone~~ and a note[^1]\n\n[^1]: Here.\n";
+        let html = markdown_to_html(source);
+        assert!(html.contains("<table>"), "{html}");
+        assert!(html.contains("<del>gone</del>"), "{html}");
+        assert!(html.contains("footnote"), "{html}");
+
+        let html = MarkdownParser::new()
+            .with_features(
+                MarkdownFeatures::new()
+                    .with_tables(false)
+                    .with_strikethrough(false)
+                    .with_footnotes(false),
+            )
+            .render(source);
+        assert!(!html.contains("<table>"), "{html}");
+        assert!(html.contains("~~gone~~"), "{html}");
+        assert!(!html.contains("footnote"), "{html}");
+    }
+
+    #[test]
+    fn site_markdown_features_unless_parser_sets_them() {
+        let source = "---\ntitle: Post\n---\n\n~~gone~~\n";
+        let config =
+            SiteConfig::default().with_markdown(MarkdownFeatures::new().with_strikethrough(false));
+        let parsed = MarkdownParser::new()
+            .parse_with_config(source, Path::new("x.md"), &config)
+            .unwrap();
+        assert!(
+            parsed.content_html.contains("~~gone~~"),
+            "{}",
+            parsed.content_html
+        );
+        let parsed = MarkdownParser::new()
+            .with_features(MarkdownFeatures::new())
+            .parse_with_config(source, Path::new("x.md"), &config)
+            .unwrap();
+        assert!(
+            parsed.content_html.contains("<del>gone</del>"),
+            "{}",
+            parsed.content_html
+        );
+    }
+
+    #[test]
+    fn markdown_bold_converts_to_strong() {
+        assert!(markdown_to_html("**bold**").contains("<strong>bold</strong>"));
+    }
+
+    #[test]
+    fn missing_frontmatter_is_a_parser_error() {
+        let err = MarkdownParser::default()
+            .parse("# No frontmatter", Path::new("x.md"))
+            .unwrap_err();
+        assert!(matches!(err, ParserError::Frontmatter(_)));
+    }
+
+    #[test]
+    fn plain_code_block_keeps_stock_markup() {
+        let html = markdown_to_html("```rust\nfn main() {}\n```\n");
+        assert_eq!(
+            html,
+            "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn highlight_range_renders_line_spans() {
+        let html = markdown_to_html("```rust {2}\nlet a = 1;\nlet b = 2;\n```\n");
+        assert!(html.contains("<code class=\"language-rust\">"), "{html}");
+        assert!(
+            html.contains("<span class=\"line highlighted\" data-line=\"2\">let b = 2;</span>")
+        );
+        assert!(!html.contains("{2}"), "{html}");
+    }
+
+    #[test]
+    fn line_numbers_option_applies_to_every_block() {
+        let html = MarkdownParser::new()
+            .with_line_numbers(true)
+            .render("```\nplain\n```\n");
+        assert!(html.contains("<pre class=\"line-numbers\">"), "{html}");
+        assert!(html.contains("data-line=\"1\">plain</span>"), "{html}");
+    }
+
+    #[test]
+    fn mermaid_blocks_become_diagrams_only_when_enabled() {
+        let source = "```mermaid\ngraph TD\n  A --> B\n```\n\n```rust\nlet a = 1 > 0;\n```\n";
+        let html = markdown_to_html(source);
+        assert!(html.contains("<code class=\"language-mermaid\">"), "{html}");
+
+        let html = MarkdownParser::new()
+            .with_features(MarkdownFeatures::new().with_mermaid(true))
+            .render(source);
+        assert!(
+            html.contains("<pre class=\"mermaid\">graph TD\n  A --&gt; B\n</pre>"),
+            "{html}"
+        );
+        assert!(
+            html.contains("<pre><code class=\"language-rust\">let a = 1 &gt; 0;\n</code></pre>"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn emoji_expand_in_text_but_not_code_or_urls() {
+        let source =
+            ":smile: `a:b :smile:` <https://x.test/:smile:> :not_an_emoji:\n\n```\n:smile:\n```\n";
+        assert!(markdown_to_html(source).starts_with("<p>:smile: "));
+        let html = MarkdownParser::new()
+            .with_features(MarkdownFeatures::new().with_emoji(true))
+            .render(source);
+        assert_eq!(
+            html,
+            "<p>😄 <code>a:b :smile:</code> \
+             <a href=\"https://x.test/:smile:\">https://x.test/:smile:</a> \
+             :not_an_emoji:</p>\n<pre><code>:smile:\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn escape_html_escapes_specials() {
+        assert_eq!(
+            escape_html("<a href=\"x\">&'"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+}
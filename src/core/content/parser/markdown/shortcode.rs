@@ -0,0 +1,483 @@
+//! Shortcodes: `{{< name args >}}` tags in markdown expanded by registered
+//! handlers, Hugo-style. A tag may wrap content —
+//! `{{< callout type="warning" >}}Careful.{{< /callout >}}` — and wrapped
+//! content may contain further shortcodes, which are expanded first.
+//!
+//! Expansion runs on the markdown source, before `pulldown-cmark`. Each
+//! top-level shortcode is swapped for an opaque placeholder and its HTML is
+//! spliced back into the rendered output, so the markdown parser never sees
+//! (or mangles) handler output. Fenced code blocks and inline code spans are
+//! left alone, and `{{</* name */>}}` writes a literal `{{< name >}}`.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Renders one shortcode to HTML. An `Err` message is reported as a page
+/// warning and the tag is left in the page as written.
+///
+/// Handlers must be `Send + Sync`: the dev server shares the parser across
+/// threads when rebuilding on file changes.
+pub type Shortcode = dyn Fn(&ShortcodeCall) -> Result<String, String> + Send + Sync;
+
+/// One shortcode tag as written in the page: `{{< youtube dQw4w9WgXcQ >}}`
+/// has the positional argument `dQw4w9WgXcQ`; `{{< callout type="warning" >}}`
+/// the named argument `type`. Values may be bare words or double-quoted
+/// strings (with `\"` escapes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ShortcodeCall {
+    pub name: String,
+    /// Arguments without a `key=`, in order.
+    pub positional: Vec<String>,
+    /// `key=value` arguments, in order.
+    pub named: Vec<(String, String)>,
+    /// The markdown between the opening and closing tags, with nested
+    /// shortcodes already expanded to HTML. `None` for a tag without a
+    /// closing tag (or written self-closing, `{{< name />}}`).
+    pub inner: Option<String>,
+}
+
+impl ShortcodeCall {
+    /// The `n`th positional argument.
+    pub fn arg(&self, n: usize) -> Option<&str> {
+        self.positional.get(n).map(String::as_str)
+    }
+
+    /// The named argument `key`; the last one wins if it's repeated.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.named
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Maps shortcode names to their handlers. Empty by default; give it to
+/// [`MarkdownParser::with_shortcodes`](super::MarkdownParser::with_shortcodes).
+///
+/// ```
+/// use sherwood::{MarkdownParser, ShortcodeCall, ShortcodeRegistry, markdown_to_html};
+///
+/// let mut shortcodes = ShortcodeRegistry::new();
+/// shortcodes.register("callout", |call: &ShortcodeCall| {
+///     let kind = call.get("type").unwrap_or("note");
+///     let inner = markdown_to_html(call.inner.as_deref().unwrap_or_default());
+///     Ok(format!("<div class=\"callout {kind}\">{inner}</div>"))
+/// });
+/// let parser = MarkdownParser::new().with_shortcodes(shortcodes);
+/// let html = parser.render("{{< callout type=\"warning\" >}}Back up first.{{< /callout >}}");
+/// assert!(html.contains("<div class=\"callout warning\"><p>Back up first.</p>\n</div>"));
+/// ```
+#[derive(Clone, Default)]
+pub struct ShortcodeRegistry {
+    by_name: HashMap<String, Arc<Shortcode>>,
+}
+
+impl ShortcodeRegistry {
+    /// A registry with no shortcodes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `render` as the handler for `name`. A later registration
+    /// for the same name wins.
+    pub fn register<F>(&mut self, name: impl Into<String>, render: F) -> &mut Self
+    where
+        F: Fn(&ShortcodeCall) -> Result<String, String> + Send + Sync + 'static,
+    {
+        self.by_name.insert(name.into(), Arc::new(render));
+        self
+    }
+
+    /// The handler registered for `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&Arc<Shortcode>> {
+        self.by_name.get(name)
+    }
+}
+
+impl std::fmt::Debug for ShortcodeRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&str> = self.by_name.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        f.debug_struct("ShortcodeRegistry")
+            .field("names", &names)
+            .finish()
+    }
+}
+
+/// Markdown with its top-level shortcodes replaced by placeholders, plus the
+/// HTML each placeholder stands for.
+pub(super) struct Expanded {
+    pub(super) markdown: String,
+    outputs: Vec<String>,
+}
+
+impl Expanded {
+    /// Put shortcode output back into HTML rendered from
+    /// [`markdown`](Self::markdown). A shortcode alone on its line was
+    /// wrapped in a paragraph by the markdown renderer; that wrapper goes.
+    pub(super) fn restore(&self, html: &str) -> String {
+        let mut html = html.to_string();
+        for (n, output) in self.outputs.iter().enumerate() {
+            let token = placeholder(n);
+            html = html
+                .replace(&format!("<p>{token}</p>"), output)
+                .replace(&token, output);
+        }
+        html
+    }
+}
+
+/// Expand every shortcode in `markdown`, pushing a warning for each unknown
+/// or failing one (those stay as written).
+pub(super) fn expand(
+    markdown: &str,
+    registry: &ShortcodeRegistry,
+    warnings: &mut Vec<String>,
+) -> Expanded {
+    let mut outputs = Vec::new();
+    let markdown = expand_into(markdown, registry, warnings, Some(&mut outputs));
+    Expanded { markdown, outputs }
+}
+
+/// Private-use code points: they pass through `pulldown-cmark` untouched
+/// and never occur in real content.
+fn placeholder(n: usize) -> String {
+    format!("\u{E000}{n}\u{E001}")
+}
+
+/// A parsed `{{< … >}}` tag.
+enum Tag {
+    Open { call: ShortcodeCall, closed: bool },
+    Close(String),
+}
+
+/// With `outputs`, shortcode HTML is parked there and a placeholder written
+/// in its place; without, the HTML is written inline (for wrapped content,
+/// which is handed to a handler rather than to the markdown renderer).
+fn expand_into(
+    src: &str,
+    registry: &ShortcodeRegistry,
+    warnings: &mut Vec<String>,
+    mut outputs: Option<&mut Vec<String>>,
+) -> String {
+    let code = code_ranges(src);
+    let mut out = String::with_capacity(src.len());
+    let mut pos = 0;
+    while let Some((start, end, body)) = next_tag(src, pos, &code) {
+        out.push_str(&src[pos..start]);
+        pos = end;
+        if let Some(literal) = body.strip_prefix("/*").and_then(|b| b.strip_suffix("*/")) {
+            out.push_str(&format!("{{{{<{literal}>}}}}"));
+            continue;
+        }
+        let Some(Tag::Open { mut call, closed }) = parse_tag(body) else {
+            out.push_str(&src[start..end]);
+            continue;
+        };
+        let Some(handler) = registry.get(&call.name) else {
+            warnings.push(format!("unknown shortcode `{}` left as text", call.name));
+            out.push_str(&src[start..end]);
+            continue;
+        };
+        if !closed && let Some((inner_end, close_end)) = find_close(src, end, &call.name, &code) {
+            call.inner = Some(expand_into(&src[end..inner_end], registry, warnings, None));
+            pos = close_end;
+        }
+        match handler(&call) {
+            Ok(html) => match outputs.as_deref_mut() {
+                Some(outputs) => {
+                    out.push_str(&placeholder(outputs.len()));
+                    outputs.push(html);
+                }
+                None => out.push_str(&html),
+            },
+            Err(message) => {
+                warnings.push(format!("shortcode `{}`: {message}", call.name));
+                out.push_str(&src[start..pos]);
+            }
+        }
+    }
+    out.push_str(&src[pos..]);
+    out
+}
+
+/// The next `{{< … >}}` at or after `from` outside code: its byte range and
+/// the text between the delimiters.
+fn next_tag<'a>(
+    src: &'a str,
+    mut from: usize,
+    code: &[Range<usize>],
+) -> Option<(usize, usize, &'a str)> {
+    loop {
+        let start = from + src[from..].find("{{<")?;
+        if let Some(range) = code.iter().find(|r| r.contains(&start)) {
+            from = range.end;
+            continue;
+        }
+        let len = src[start..].find(">}}")?;
+        return Some((start, start + len + 3, &src[start + 3..start + len]));
+    }
+}
+
+/// The closing tag for `name` after `from`, skipping nested pairs of the
+/// same name: the byte offsets where it starts and ends.
+fn find_close(
+    src: &str,
+    mut from: usize,
+    name: &str,
+    code: &[Range<usize>],
+) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    while let Some((start, end, body)) = next_tag(src, from, code) {
+        match parse_tag(body) {
+            Some(Tag::Open {
+                call,
+                closed: false,
+            }) if call.name == name => depth += 1,
+            Some(Tag::Close(close)) if close == name => {
+                if depth == 0 {
+                    return Some((start, end));
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+        from = end;
+    }
+    None
+}
+
+/// Parse the inside of a tag: `name args…`, `name args… /`, or `/name`.
+fn parse_tag(body: &str) -> Option<Tag> {
+    let body = body.trim();
+    if let Some(name) = body.strip_prefix('/') {
+        let name = name.trim();
+        return is_name(name).then(|| Tag::Close(name.to_string()));
+    }
+    let (body, closed) = match body.strip_suffix('/') {
+        Some(body) => (body, true),
+        None => (body, false),
+    };
+    let mut args = parse_args(body)?.into_iter();
+    let name = match args.next()? {
+        (None, name) if is_name(&name) => name,
+        _ => return None,
+    };
+    let mut call = ShortcodeCall {
+        name,
+        positional: Vec::new(),
+        named: Vec::new(),
+        inner: None,
+    };
+    for (key, value) in args {
+        match key {
+            Some(key) => call.named.push((key, value)),
+            None => call.positional.push(value),
+        }
+    }
+    Some(Tag::Open { call, closed })
+}
+
+fn is_name(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Whitespace-separated `value` / `key=value` arguments.
+fn parse_args(s: &str) -> Option<Vec<(Option<String>, String)>> {
+    let mut args = Vec::new();
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let (first, after) = take_value(rest)?;
+        let arg = match after.strip_prefix('=') {
+            Some(after) => {
+                let (value, after) = take_value(after)?;
+                rest = after;
+                (Some(first), value)
+            }
+            None => {
+                rest = after;
+                (None, first)
+            }
+        };
+        args.push(arg);
+        rest = rest.trim_start();
+    }
+    Some(args)
+}
+
+/// A bare word or a `"quoted string"` from the front of `s`, and the rest.
+fn take_value(s: &str) -> Option<(String, &str)> {
+    let Some(quoted) = s.strip_prefix('"') else {
+        let end = s
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '"')
+            .unwrap_or(s.len());
+        return (end > 0).then(|| (s[..end].to_string(), &s[end..]));
+    };
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &quoted[i + 1..])),
+            '\\' => value.push(chars.next()?.1),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Byte ranges of fenced code blocks and inline code spans, where tags (and
+/// excerpt separators) are left alone. Inline spans are only matched within a line.
+pub(super) fn code_ranges(src: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    // Fence character, fence length, and start offset of an open block.
+    let mut fence: Option<(char, usize, usize)> = None;
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let indented = line.len() - trimmed.len() >= 4;
+        let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'));
+        let run = marker.map_or(0, |m| trimmed.chars().take_while(|&c| c == m).count());
+        match fence {
+            Some((c, len, start)) => {
+                let closes = marker == Some(c) && run >= len && trimmed[run..].trim().is_empty();
+                if !indented && closes {
+                    ranges.push(start..offset + line.len());
+                    fence = None;
+                }
+            }
+            None if !indented && run >= 3 => fence = Some((marker.unwrap_or('`'), run, offset)),
+            None => inline_code(line, offset, &mut ranges),
+        }
+        offset += line.len();
+    }
+    if let Some((_, _, start)) = fence {
+        ranges.push(start..src.len());
+    }
+    ranges
+}
+
+/// Code spans in one line: a backtick run up to the next run of the same
+/// length.
+fn inline_code(line: &str, offset: usize, ranges: &mut Vec<Range<usize>>) {
+    let bytes = line.as_bytes();
+    let mut open: Option<(usize, usize)> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && bytes[i] == b'`' {
+            i += 1;
+        }
+        match open {
+            Some((open_start, len)) if len == i - start => {
+                ranges.push(offset + open_start..offset + i);
+                open = None;
+            }
+            Some(_) => {}
+            None => open = Some((start, i - start)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> ShortcodeRegistry {
+        let mut registry = ShortcodeRegistry::new();
+        registry
+            .register("youtube", |call: &ShortcodeCall| {
+                let id = call.arg(0).ok_or("missing video id")?;
+                Ok(format!(
+                    "<iframe src=\"https://www.youtube.com/embed/{id}\"></iframe>"
+                ))
+            })
+            .register("box", |call: &ShortcodeCall| {
+                Ok(format!(
+                    "<div class=\"{}\">{}</div>",
+                    call.get("class").unwrap_or("box"),
+                    call.inner.as_deref().unwrap_or_default().trim()
+                ))
+            });
+        registry
+    }
+
+    fn expand_html(src: &str) -> (String, Vec<String>) {
+        let mut warnings = Vec::new();
+        let expanded = expand(src, &registry(), &mut warnings);
+        (expanded.restore(&expanded.markdown), warnings)
+    }
+
+    #[test]
+    fn parses_positional_and_named_args() {
+        let Some(Tag::Open { call, closed }) =
+            parse_tag(r#" callout warn type="a \"b\"" level=2 / "#)
+        else {
+            panic!("expected an opening tag");
+        };
+        assert!(closed);
+        assert_eq!(call.name, "callout");
+        assert_eq!(call.arg(0), Some("warn"));
+        assert_eq!(call.get("type"), Some("a \"b\""));
+        assert_eq!(call.get("level"), Some("2"));
+        assert!(parse_tag("bad!name").is_none());
+        assert!(matches!(parse_tag("/box"), Some(Tag::Close(n)) if n == "box"));
+    }
+
+    #[test]
+    fn expands_self_closing_and_paired_tags() {
+        let (html, warnings) = expand_html("{{< youtube abc >}}");
+        assert_eq!(
+            html,
+            "<iframe src=\"https://www.youtube.com/embed/abc\"></iframe>"
+        );
+        assert!(warnings.is_empty());
+
+        let (html, _) = expand_html("{{< box class=\"a b\" >}} hi {{< /box >}}");
+        assert_eq!(html, "<div class=\"a b\">hi</div>");
+    }
+
+    #[test]
+    fn nested_shortcodes_expand_inside_out() {
+        let (html, _) = expand_html(
+            "{{< box >}}{{< box class=inner >}}x{{< /box >}}{{< youtube v >}}{{< /box >}}",
+        );
+        assert_eq!(
+            html,
+            "<div class=\"box\"><div class=\"inner\">x</div>\
+             <iframe src=\"https://www.youtube.com/embed/v\"></iframe></div>"
+        );
+    }
+
+    #[test]
+    fn unknown_and_failing_shortcodes_stay_literal_with_a_warning() {
+        let (html, warnings) = expand_html("a {{< nope x >}} b {{< youtube >}}");
+        assert_eq!(html, "a {{< nope x >}} b {{< youtube >}}");
+        assert_eq!(
+            warnings,
+            [
+                "unknown shortcode `nope` left as text",
+                "shortcode `youtube`: missing video id"
+            ]
+        );
+    }
+
+    #[test]
+    fn code_and_escaped_tags_are_left_alone() {
+        let src = "`{{< youtube a >}}`\n\n```\n{{< youtube b >}}\n```\n\n{{</* youtube c */>}}\n";
+        let (html, warnings) = expand_html(src);
+        assert_eq!(
+            html,
+            "`{{< youtube a >}}`\n\n```\n{{< youtube b >}}\n```\n\n{{< youtube c >}}\n"
+        );
+        assert!(warnings.is_empty());
+    }
+}
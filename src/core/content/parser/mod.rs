@@ -5,17 +5,34 @@
 //! extension in a [`ParserRegistry`]; the build pipeline looks up the parser
 //! for each file's extension and skips files with no registered parser.
 //!
-//! Markdown ships built in ([`MarkdownParser`]). Downstream crates implement
+//! Markdown ships built in ([`MarkdownParser`]), AsciiDoc behind the
+//! `asciidoc` feature (`AsciidocParser`), and an opt-in [`HtmlParser`] for
+//! hand-written HTML pages. Downstream crates implement
 //! [`ContentParser`] for other formats and `register` them — reusing
 //! [`crate::split_frontmatter`] for frontmatter handling if their format uses
-//! the same `---` / `+++` convention.
+//! the same `---` / `+++` convention. A parser whose output depends on
+//! site-wide settings (the base path or site URL, for links) overrides
+//! [`ContentParser::parse_with_config`] to see the resolved [`SiteConfig`].
 
+#[cfg(feature = "asciidoc")]
+mod asciidoc;
+mod html;
 mod markdown;
 
-pub use markdown::{MarkdownParser, markdown_to_html};
+#[cfg(feature = "asciidoc")]
+pub use asciidoc::{ASCIIDOC_EXTENSIONS, AsciidocParser};
+pub use html::{HTML_EXTENSIONS, HtmlParser};
+pub(crate) use markdown::{ANCHOR_CLASS, escape_html};
+pub use markdown::{
+    AnchorPosition, DEFAULT_EXCERPT_SEPARATOR, HeadingAnchors, MARKDOWN_EXTENSIONS,
+    MarkdownFeatures, MarkdownParser, Shortcode, ShortcodeCall, ShortcodeRegistry,
+    markdown_to_html,
+};
 
+use crate::core::config::SiteConfig;
 use crate::core::content::frontmatter::{FrontMatter, FrontmatterError};
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
 use thiserror::Error;
@@ -31,6 +48,9 @@ pub struct Parsed {
     /// Optional pre-rendered excerpt HTML, when the format supports one (e.g.
     /// markdown's `<!-- more -->` delimiter). `None` otherwise.
     pub excerpt_html: Option<String>,
+    /// Non-fatal problems met while parsing (e.g. an unknown shortcode).
+    /// They end up in [`Page::warnings`](crate::Page::warnings).
+    pub warnings: Vec<String>,
 }
 
 /// Turns the raw source of a single content file into a [`Parsed`] payload.
@@ -42,9 +62,31 @@ pub trait ContentParser: Send + Sync {
     /// dot — e.g. `["md", "markdown"]`.
     fn extensions(&self) -> &[&str];
 
+    /// Whether this file is content at all. A source the parser declines is
+    /// treated as if no parser claimed its extension: copied verbatim as a
+    /// static asset. The default claims everything.
+    fn claims(&self, source: &str) -> bool {
+        let _ = source;
+        true
+    }
+
     /// Parse `source` (the full file contents) into a [`Parsed`] payload.
     /// `path` is provided for diagnostics only.
     fn parse(&self, source: &str, path: &Path) -> Result<Parsed, ParserError>;
+
+    /// [`parse`](Self::parse), with read-only access to the site's resolved
+    /// config. This is what the build calls. The default ignores `config`
+    /// and delegates to `parse`, so only parsers that need site settings
+    /// override it.
+    fn parse_with_config(
+        &self,
+        source: &str,
+        path: &Path,
+        config: &SiteConfig,
+    ) -> Result<Parsed, ParserError> {
+        let _ = config;
+        self.parse(source, path)
+    }
 }
 
 /// Errors a [`ContentParser`] may return. Open enough that third-party parsers
@@ -67,11 +109,14 @@ pub struct ParserRegistry {
 }
 
 impl Default for ParserRegistry {
-    /// Registers the built-in [`MarkdownParser`] (`.md`, `.markdown`). Use
-    /// [`ParserRegistry::empty`] for a registry with no formats.
+    /// Registers the built-in [`MarkdownParser`] ([`MARKDOWN_EXTENSIONS`]), and
+    /// `AsciidocParser` (`ASCIIDOC_EXTENSIONS`) with the `asciidoc` feature.
+    /// Use [`ParserRegistry::empty`] for a registry with no formats.
     fn default() -> Self {
         let mut registry = Self::empty();
-        registry.register(Arc::new(MarkdownParser));
+        registry.register(Arc::new(MarkdownParser::default()));
+        #[cfg(feature = "asciidoc")]
+        registry.register(Arc::new(AsciidocParser::new()));
         registry
     }
 }
@@ -88,23 +133,47 @@ impl ParserRegistry {
     /// for an already-claimed extension wins.
     pub fn register(&mut self, parser: Arc<dyn ContentParser>) -> &mut Self {
         for ext in parser.extensions() {
-            self.by_ext.insert(ext.to_string(), parser.clone());
+            self.by_ext.insert(ext.to_ascii_lowercase(), parser.clone());
         }
         self
     }
 
-    /// The parser registered for `ext` (no leading dot), if any.
+    /// The parser registered for `ext` (no leading dot), if any. Matching
+    /// is case-insensitive, so `About.MD` is markdown too.
     pub fn get(&self, ext: &str) -> Option<&Arc<dyn ContentParser>> {
-        self.by_ext.get(ext)
+        self.by_ext.get(&ext.to_ascii_lowercase())
     }
+
+    /// Every registered extension, lowercase and sorted, so listings built
+    /// from it don't change from run to run.
+    pub fn extensions(&self) -> Vec<&str> {
+        let mut exts: Vec<&str> = self.by_ext.keys().map(String::as_str).collect();
+        exts.sort_unstable();
+        exts
+    }
+}
+
+/// Split `source` around the first excerpt `separator` that doesn't start
+/// inside one of the `code` ranges, so a separator quoted in a code sample
+/// stays part of it. `None` when there is no such separator or it's empty.
+fn split_excerpt<'a>(
+    source: &'a str,
+    separator: &str,
+    code: &[Range<usize>],
+) -> Option<(&'a str, &'a str)> {
+    if separator.is_empty() {
+        return None;
+    }
+    let (at, _) = source
+        .match_indices(separator)
+        .find(|(at, _)| !code.iter().any(|range| range.contains(at)))?;
+    Some((&source[..at], &source[at + separator.len()..]))
 }
 
 impl std::fmt::Debug for ParserRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut exts: Vec<&str> = self.by_ext.keys().map(String::as_str).collect();
-        exts.sort_unstable();
         f.debug_struct("ParserRegistry")
-            .field("extensions", &exts)
+            .field("extensions", &self.extensions())
             .finish()
     }
 }
@@ -126,15 +195,59 @@ mod tests {
                 },
                 content_html: source.to_string(),
                 excerpt_html: None,
+                warnings: Vec::new(),
             })
         }
     }
 
+    /// Links its body under the site's base path, like a data-driven parser
+    /// rendering URLs would.
+    struct LinkParser;
+    impl ContentParser for LinkParser {
+        fn extensions(&self) -> &[&str] {
+            &["link"]
+        }
+        fn parse(&self, source: &str, path: &Path) -> Result<Parsed, ParserError> {
+            self.parse_with_config(source, path, &SiteConfig::default())
+        }
+        fn parse_with_config(
+            &self,
+            source: &str,
+            _path: &Path,
+            config: &SiteConfig,
+        ) -> Result<Parsed, ParserError> {
+            let mut parsed = FakeParser.parse("", Path::new(""))?;
+            parsed.content_html = format!("<a href=\"{}/{source}/\">", config.base_path);
+            Ok(parsed)
+        }
+    }
+
+    #[test]
+    fn parse_with_config_defaults_to_parse() {
+        let config = SiteConfig::default().with_base_path("/docs");
+        let parsed = FakeParser
+            .parse_with_config("body", Path::new("x.txt"), &config)
+            .unwrap();
+        assert_eq!(parsed.content_html, "body");
+    }
+
+    #[test]
+    fn parse_with_config_sees_site_settings() {
+        let config = SiteConfig::default().with_base_path("/docs");
+        let parsed = LinkParser
+            .parse_with_config("guide", Path::new("x.link"), &config)
+            .unwrap();
+        assert_eq!(parsed.content_html, "<a href=\"/docs/guide/\">");
+    }
+
     #[test]
     fn default_registry_has_markdown() {
         let registry = ParserRegistry::default();
-        assert!(registry.get("md").is_some());
-        assert!(registry.get("markdown").is_some());
+        for ext in MARKDOWN_EXTENSIONS {
+            assert!(registry.get(ext).is_some(), "{ext}");
+        }
+        assert!(registry.get("MD").is_some());
+        assert!(registry.get("mdx").is_none());
         assert!(registry.get("rst").is_none());
     }
 
@@ -150,6 +263,7 @@ mod tests {
         registry.register(Arc::new(FakeParser));
         assert!(registry.get("txt").is_some());
         assert!(registry.get("text").is_some());
+        assert_eq!(registry.extensions(), ["text", "txt"]);
     }
 
     #[test]
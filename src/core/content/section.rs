@@ -0,0 +1,175 @@
+//! `_section.toml`: frontmatter defaults for a directory of content.
+//!
+//! A `_section.toml` in any content directory (the root included) holds
+//! frontmatter fields that every page in that directory and beneath it
+//! inherits, so a docs tree needn't repeat `template = "doc"` or `toc = true`
+//! on every page. Defaults cascade: a nearer file wins key by key over one
+//! further up, and the page's own frontmatter wins over both. `sort_by` /
+//! `sort_order` reach the section's index page like any other key, so a
+//! section can set its listing order here.
+//!
+//! `title` names the section rather than each page (pages keep their own
+//! titles); it is inherited as `section_title`.
+//!
+//! ```toml
+//! title = "Guide"
+//! template = "doc"
+//! sort_by = "title"
+//! ```
+
+use gray_matter::Pod;
+use gray_matter::engine::{Engine, TOML};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of a section's defaults. The file itself is never treated as
+/// content.
+pub(crate) const SECTION_FILE: &str = "_section.toml";
+
+/// The frontmatter key a section's `title` is inherited under.
+pub const SECTION_TITLE: &str = "section_title";
+
+#[derive(Debug, Error)]
+pub enum SectionError {
+    #[error("reading {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// Not valid TOML, or not a table of fields.
+    #[error("parsing {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+}
+
+/// Every `_section.toml` read so far, by directory, so each file is parsed
+/// once per build however many pages sit beneath it.
+#[derive(Debug, Default)]
+pub(crate) struct SectionDefaults {
+    dirs: HashMap<PathBuf, HashMap<String, Pod>>,
+}
+
+impl SectionDefaults {
+    /// The merged defaults for the page at `source` under the content `root`:
+    /// nearest directory first, each key taken from the first file that
+    /// sets it.
+    pub(crate) fn for_page(
+        &mut self,
+        root: &Path,
+        source: &Path,
+    ) -> Result<HashMap<String, Pod>, SectionError> {
+        let mut merged = HashMap::new();
+        let Some(parent) = source.parent() else {
+            return Ok(merged);
+        };
+        for dir in parent.ancestors().take_while(|dir| dir.starts_with(root)) {
+            for (key, value) in self.dir(dir)? {
+                merged.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        Ok(merged)
+    }
+
+    fn dir(&mut self, dir: &Path) -> Result<&HashMap<String, Pod>, SectionError> {
+        if !self.dirs.contains_key(dir) {
+            let defaults = load(&dir.join(SECTION_FILE))?;
+            self.dirs.insert(dir.to_owned(), defaults);
+        }
+        Ok(&self.dirs[dir])
+    }
+}
+
+/// Read one `_section.toml`. A missing file sets no defaults.
+fn load(path: &Path) -> Result<HashMap<String, Pod>, SectionError> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(source) => {
+            return Err(SectionError::Read {
+                path: path.to_owned(),
+                source,
+            });
+        }
+    };
+    let parse_error = |message: String| SectionError::Parse {
+        path: path.to_owned(),
+        message,
+    };
+    let mut map = match TOML::parse(&text).map_err(|e| parse_error(e.to_string()))? {
+        Pod::Hash(map) => map,
+        Pod::Null => HashMap::new(),
+        _ => return Err(parse_error("expected a table of fields".to_string())),
+    };
+    if let Some(title) = map.remove("title") {
+        map.insert(SECTION_TITLE.to_string(), title);
+    }
+    Ok(map)
+}
+
+/// Fill the fields `data` doesn't set from `defaults`.
+pub(crate) fn apply_defaults(data: &mut Pod, defaults: &HashMap<String, Pod>) {
+    if let Pod::Hash(map) = data {
+        for (key, value) in defaults {
+            map.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn nearer_sections_win_key_by_key() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("docs/api")).unwrap();
+        fs::write(root.join(SECTION_FILE), "toc = true\ntemplate = \"page\"\n").unwrap();
+        fs::write(
+            root.join("docs").join(SECTION_FILE),
+            "title = \"Docs\"\ntemplate = \"doc\"\n",
+        )
+        .unwrap();
+        let mut sections = SectionDefaults::default();
+        let defaults = sections
+            .for_page(root, &root.join("docs/api/intro.md"))
+            .unwrap();
+        assert!(matches!(&defaults["template"], Pod::String(s) if s == "doc"));
+        assert!(matches!(&defaults[SECTION_TITLE], Pod::String(s) if s == "Docs"));
+        assert!(matches!(defaults["toc"], Pod::Boolean(true)));
+        assert!(!defaults.contains_key("title"));
+
+        let top = sections.for_page(root, &root.join("about.md")).unwrap();
+        assert!(matches!(&top["template"], Pod::String(s) if s == "page"));
+        assert!(!top.contains_key(SECTION_TITLE));
+    }
+
+    #[test]
+    fn page_frontmatter_overrides_defaults() {
+        let mut data = Pod::Hash(HashMap::from([(
+            "template".to_string(),
+            Pod::String("custom".to_string()),
+        )]));
+        let defaults = HashMap::from([
+            ("template".to_string(), Pod::String("doc".to_string())),
+            ("toc".to_string(), Pod::Boolean(true)),
+        ]);
+        apply_defaults(&mut data, &defaults);
+        let Pod::Hash(map) = data else { unreachable!() };
+        assert!(matches!(&map["template"], Pod::String(s) if s == "custom"));
+        assert!(matches!(map["toc"], Pod::Boolean(true)));
+    }
+
+    #[test]
+    fn invalid_section_file_names_the_path() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(SECTION_FILE), "title = [").unwrap();
+        let err = SectionDefaults::default()
+            .for_page(tmp.path(), &tmp.path().join("a.md"))
+            .unwrap_err();
+        assert!(err.to_string().contains(SECTION_FILE), "{err}");
+    }
+}
@@ -0,0 +1,87 @@
+//! Slugs: the one text → id algorithm behind heading ids (which tables of
+//! contents link to), series landing URLs, and single-file export anchors.
+//! Keeping it in one place means a TOC entry and the heading it points at
+//! can't disagree.
+
+use std::collections::HashSet;
+
+/// Slug for a piece of text: lowercase, letters and digits kept (any script,
+/// so `Über` and `你好` survive), runs of whitespace, `-`, and `_` collapsed
+/// to one `-`, everything else — punctuation, emoji — dropped.
+/// `"Hello, World!"` → `hello-world`. A leading digit is kept: HTML ids
+/// and URL fragments may start with one. `/` is punctuation too, so a URL
+/// path is slugged with its separators swapped for spaces.
+pub(crate) fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_dash = false;
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.extend(c.to_lowercase());
+        } else if c.is_whitespace() || c == '-' || c == '_' {
+            pending_dash = true;
+        }
+    }
+    slug
+}
+
+/// `slug`, or `fallback` when it is empty, made unique against `used` with
+/// a numeric suffix (`setup`, `setup-1`, `setup-2`, …) and recorded there.
+pub(crate) fn unique_slug(slug: String, fallback: &str, used: &mut HashSet<String>) -> String {
+    let base = if slug.is_empty() {
+        fallback.to_string()
+    } else {
+        slug
+    };
+    let mut candidate = base.clone();
+    let mut n = 1;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{base}-{n}");
+        n += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_separators_and_drops_punctuation() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(
+            slugify("  snake_case -- and  spaces "),
+            "snake-case-and-spaces"
+        );
+        assert_eq!(slugify("What's new? (v2.0)"), "whats-new-v20");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn slugify_keeps_unicode_letters_and_leading_digits() {
+        assert_eq!(slugify("Über Café"), "über-café");
+        assert_eq!(slugify("你好 世界"), "你好-世界");
+        assert_eq!(slugify("🚀 Launch 🎉 day"), "launch-day");
+        assert_eq!(slugify("1. Getting started"), "1-getting-started");
+    }
+
+    #[test]
+    fn slugify_drops_path_separators() {
+        assert_eq!(slugify("/guide/setup/"), "guidesetup");
+    }
+
+    #[test]
+    fn unique_slug_suffixes_repeats_and_falls_back() {
+        let mut used = HashSet::from(["setup-1".to_string()]);
+        assert_eq!(unique_slug("setup".into(), "section", &mut used), "setup");
+        assert_eq!(unique_slug("setup".into(), "section", &mut used), "setup-2");
+        assert_eq!(unique_slug(String::new(), "section", &mut used), "section");
+        assert_eq!(
+            unique_slug(slugify("🎉"), "section", &mut used),
+            "section-1"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Opt-in wrappers around content tables, so a theme can scroll wide ones
+//! sideways on small screens instead of letting them overflow the page:
+//! each top-level `<table>` is placed inside a
+//! `<div class="table-wrapper">`, which the default stylesheet gives
+//! `overflow-x: auto`.
+//!
+//! Works on rendered HTML, so tables from any parser (or raw HTML in
+//! markdown) are covered. Nested tables stay inside their parent's wrapper.
+
+/// Class of the `<div>` wrapped around each table.
+pub(crate) const TABLE_WRAPPER_CLASS: &str = "table-wrapper";
+
+const CLOSE: &str = "</table>";
+
+/// `html` with every top-level `<table>…</table>` wrapped in a
+/// `<div class="table-wrapper">`. A table missing its closing tag (and
+/// everything after it) is left as is.
+pub(crate) fn wrap_tables(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = find_open(rest) {
+        let Some(len) = table_len(&rest[start..]) else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&format!("<div class=\"{TABLE_WRAPPER_CLASS}\">"));
+        out.push_str(&rest[start..start + len]);
+        out.push_str("</div>");
+        rest = &rest[start + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Offset of the next `<table` tag — not a longer name like `<tablet>`.
+fn find_open(html: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(at) = html[from..].find("<table") {
+        let at = from + at;
+        let after = &html[at + "<table".len()..];
+        if after.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            return Some(at);
+        }
+        from = at + "<table".len();
+    }
+    None
+}
+
+/// Length of the table `html` starts with, through its matching
+/// `</table>` (nested tables counted).
+fn table_len(html: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut pos = 0;
+    loop {
+        let close = pos + html[pos..].find(CLOSE)?;
+        match find_open(&html[pos..]).map(|at| pos + at) {
+            Some(open) if open < close => {
+                depth += 1;
+                pos = open + "<table".len();
+            }
+            _ => {
+                depth = depth.saturating_sub(1);
+                pos = close + CLOSE.len();
+                if depth == 0 {
+                    return Some(pos);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_each_table() {
+        let html = "<p>a</p>\n<table>\n<tr><td>1</td></tr>\n</table>\n<table class=\"x\"></table>";
+        assert_eq!(
+            wrap_tables(html),
+            concat!(
+                "<p>a</p>\n",
+                "<div class=\"table-wrapper\"><table>\n<tr><td>1</td></tr>\n</table></div>\n",
+                "<div class=\"table-wrapper\"><table class=\"x\"></table></div>",
+            )
+        );
+    }
+
+    #[test]
+    fn nested_tables_share_the_outer_wrapper() {
+        let html = "<table><tr><td><table><tr><td>in</td></tr></table></td></tr></table>";
+        assert_eq!(
+            wrap_tables(html),
+            format!("<div class=\"table-wrapper\">{html}</div>")
+        );
+    }
+
+    #[test]
+    fn leaves_other_tags_and_unclosed_tables_alone() {
+        assert_eq!(wrap_tables("<tablet>x</tablet>"), "<tablet>x</tablet>");
+        assert_eq!(wrap_tables("<table><tr>"), "<table><tr>");
+    }
+}
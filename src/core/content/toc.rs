@@ -0,0 +1,274 @@
+//! Tables of contents for pages that opt in with `toc: true` frontmatter.
+//!
+//! The TOC is read back from the rendered HTML — every `<hN id="…">` in the
+//! range — rather than from markdown, so it works for any
+//! [`ContentParser`](crate::ContentParser) that emits heading ids, and its
+//! links always match the anchors actually on the page. Heading self-links
+//! ([`HeadingAnchors`](crate::HeadingAnchors)) are left out of the text.
+//!
+//! Which levels are included comes from
+//! [`SiteConfig::with_toc_levels`](crate::SiteConfig::with_toc_levels)
+//! (default `<h2>`–`<h3>`), overridable per page with `toc_min_level` /
+//! `toc_max_level` frontmatter.
+
+use crate::core::content::parser::ANCHOR_CLASS;
+use std::ops::RangeInclusive;
+
+/// Default heading levels included in a TOC: `<h2>` through `<h3>`.
+pub(crate) const DEFAULT_TOC_LEVELS: (u8, u8) = (2, 3);
+
+/// Normalize a requested `(min, max)` heading-level range: each bound is
+/// clamped to 1–6 and an inverted range is swapped. Returns the usable range
+/// plus a warning describing any adjustment.
+pub(crate) fn toc_levels(min: i64, max: i64) -> (RangeInclusive<u8>, Option<String>) {
+    let clamp = |n: i64| n.clamp(1, 6) as u8;
+    let (mut lo, mut hi) = (clamp(min), clamp(max));
+    if lo > hi {
+        std::mem::swap(&mut lo, &mut hi);
+    }
+    let warning = (i64::from(lo) != min || i64::from(hi) != max)
+        .then(|| format!("TOC levels {min}–{max} are out of range or inverted; using {lo}–{hi}"));
+    (lo..=hi, warning)
+}
+
+/// One heading found in rendered HTML.
+#[derive(Debug, PartialEq)]
+struct Heading {
+    level: u8,
+    id: String,
+    /// Inner HTML with tags stripped; still entity-escaped.
+    text: String,
+}
+
+/// Build a nested `<ul>` TOC from the headings in `html` whose level is in
+/// `levels`, or `None` when there are none.
+pub(crate) fn toc_html(html: &str, levels: RangeInclusive<u8>) -> Option<String> {
+    let headings: Vec<Heading> = headings(html)
+        .into_iter()
+        .filter(|h| levels.contains(&h.level))
+        .collect();
+    if headings.is_empty() {
+        return None;
+    }
+    Some(render(&headings))
+}
+
+/// Nest by level: a deeper heading opens a sub-list under the previous item,
+/// a shallower one closes lists back to its level.
+fn render(headings: &[Heading]) -> String {
+    let mut out = String::new();
+    let mut open: Vec<u8> = Vec::new();
+    for h in headings {
+        match open.last() {
+            None => {
+                out.push_str("<ul>");
+                open.push(h.level);
+            }
+            Some(&last) if h.level > last => {
+                out.push_str("<ul>");
+                open.push(h.level);
+            }
+            Some(_) => {
+                while open.len() > 1 && open.last().is_some_and(|&last| h.level < last) {
+                    out.push_str("</li></ul>");
+                    open.pop();
+                }
+                out.push_str("</li>");
+            }
+        }
+        out.push_str(&format!("<li><a href=\"#{}\">{}</a>", h.id, h.text));
+    }
+    for _ in open {
+        out.push_str("</li></ul>");
+    }
+    out
+}
+
+/// Scan rendered HTML for `<h1>`–`<h6>` elements carrying an `id`.
+fn headings(html: &str) -> Vec<Heading> {
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<h") {
+        rest = &rest[start + 2..];
+        let Some(level) = rest
+            .chars()
+            .next()
+            .and_then(|c| c.to_digit(10))
+            .filter(|d| (1..=6).contains(d))
+        else {
+            continue;
+        };
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let attrs = &rest[1..tag_end];
+        let close = format!("</h{level}>");
+        let Some(inner_end) = rest.find(&close) else {
+            break;
+        };
+        if let Some(id) = attr_value(attrs, "id")
+            && tag_end < inner_end
+        {
+            found.push(Heading {
+                level: level as u8,
+                id: id.to_string(),
+                text: strip_tags(&strip_anchors(&rest[tag_end + 1..inner_end]))
+                    .trim()
+                    .to_string(),
+            });
+        }
+        rest = &rest[inner_end + close.len()..];
+    }
+    found
+}
+
+/// Value of a double-quoted attribute in a tag's attribute string.
+fn attr_value<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{name}=\"");
+    let mut search = attrs;
+    loop {
+        let at = search.find(&needle)?;
+        // Require a boundary so `data-id="…"` doesn't match `id`.
+        let boundary = at == 0 || search[..at].ends_with(char::is_whitespace);
+        let value = &search[at + needle.len()..];
+        if boundary {
+            return value.find('"').map(|end| &value[..end]);
+        }
+        search = value;
+    }
+}
+
+/// `html` without heading self-links (`<a class="heading-anchor" …>…</a>`).
+fn strip_anchors(html: &str) -> String {
+    let open = format!("<a class=\"{ANCHOR_CLASS}\"");
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(&open) {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("</a>") {
+            Some(len) => rest = &rest[start + len + "</a>".len()..],
+            None => {
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_headings_with_ids() {
+        let html =
+            "<h1 id=\"t\">Title</h1><p>x</p><h2 id=\"a\">A <code>b</code></h2><h3>No id</h3>";
+        assert_eq!(
+            headings(html),
+            vec![
+                Heading {
+                    level: 1,
+                    id: "t".into(),
+                    text: "Title".into()
+                },
+                Heading {
+                    level: 2,
+                    id: "a".into(),
+                    text: "A b".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn nests_by_level() {
+        let html =
+            "<h2 id=\"a\">A</h2><h3 id=\"a1\">A1</h3><h3 id=\"a2\">A2</h3><h2 id=\"b\">B</h2>";
+        assert_eq!(
+            toc_html(html, 2..=3).unwrap(),
+            "<ul><li><a href=\"#a\">A</a><ul><li><a href=\"#a1\">A1</a></li>\
+             <li><a href=\"#a2\">A2</a></li></ul></li><li><a href=\"#b\">B</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn out_of_range_levels_are_skipped() {
+        let html = "<h1 id=\"t\">T</h1><h4 id=\"d\">D</h4>";
+        assert_eq!(toc_html(html, 2..=3), None);
+        assert_eq!(
+            toc_html(html, 2..=4).unwrap(),
+            "<ul><li><a href=\"#d\">D</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn skipped_level_nests_once() {
+        let html = "<h2 id=\"a\">A</h2><h4 id=\"b\">B</h4><h2 id=\"c\">C</h2>";
+        assert_eq!(
+            toc_html(html, 2..=4).unwrap(),
+            "<ul><li><a href=\"#a\">A</a><ul><li><a href=\"#b\">B</a></li></ul></li>\
+             <li><a href=\"#c\">C</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn levels_are_clamped_with_a_warning() {
+        assert_eq!(toc_levels(2, 4), (2..=4, None));
+        let (range, warning) = toc_levels(0, 9);
+        assert_eq!(range, 1..=6);
+        assert!(warning.is_some());
+        let (range, warning) = toc_levels(4, 2);
+        assert_eq!(range, 2..=4);
+        assert!(warning.is_some());
+    }
+
+    #[test]
+    fn heading_anchors_are_left_out_of_the_text() {
+        let html = "<h2 id=\"a\"><a class=\"heading-anchor\" href=\"#a\">¶</a> A</h2>\
+                    <h2 id=\"b\">B <a class=\"heading-anchor\" href=\"#b\">¶</a></h2>";
+        assert_eq!(
+            toc_html(html, 2..=2).unwrap(),
+            "<ul><li><a href=\"#a\">A</a></li><li><a href=\"#b\">B</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn links_match_the_rendered_heading_ids() {
+        let html = crate::core::content::parser::markdown_to_html(
+            "## Setup\n\n## Setup\n\n## 🚀 Launch\n\n## 你好 世界\n\n## 1. Intro\n\n## !!!\n",
+        );
+        let toc = toc_html(&html, 2..=2).unwrap();
+        for id in [
+            "setup",
+            "setup-1",
+            "launch",
+            "你好-世界",
+            "1-intro",
+            "section",
+        ] {
+            assert!(html.contains(&format!("id=\"{id}\"")), "{html}");
+            assert!(toc.contains(&format!("href=\"#{id}\"")), "{toc}");
+        }
+    }
+
+    #[test]
+    fn attr_value_requires_boundary() {
+        assert_eq!(attr_value(" data-id=\"x\" id=\"y\"", "id"), Some("y"));
+        assert_eq!(attr_value(" class=\"c\"", "id"), None);
+    }
+}
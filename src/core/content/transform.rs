@@ -0,0 +1,72 @@
+//! Post-processing hooks for library embedders. [`HtmlTransform`]s on
+//! [`SiteConfig`] rewrite each page's rendered HTML after its parser (and
+//! the built-in image hints) and before the render closure wraps it in a
+//! template, so every consumer — templates, feeds, export — sees the result.
+
+use crate::core::config::SiteConfig;
+use std::fmt;
+
+/// Rewrites a page's rendered HTML, e.g. opening external links in a new
+/// tab or wrapping tables in scroll containers.
+///
+/// Transforms run in the order they were added with
+/// [`SiteConfig::with_html_transform`], each receiving the previous one's
+/// output. They see `content_html` and, separately, `excerpt_html`; the
+/// table of contents is read before they run, from the parser's headings.
+///
+/// ```
+/// use sherwood::{HtmlTransform, SiteConfig};
+///
+/// /// Let wide tables scroll instead of stretching the page.
+/// #[derive(Debug)]
+/// struct ScrollTables;
+///
+/// impl HtmlTransform for ScrollTables {
+///     fn transform(&self, html: String, _url: &str) -> String {
+///         html.replace("<table>", "<div class=\"table-scroll\"><table>")
+///             .replace("</table>", "</table></div>")
+///     }
+/// }
+///
+/// let config = SiteConfig::new().with_html_transform(ScrollTables);
+/// ```
+pub trait HtmlTransform: Send + Sync + fmt::Debug {
+    /// Return `html` rewritten. `url` is the page's canonical URL (without
+    /// the base path), for transforms that treat sections differently.
+    fn transform(&self, html: String, url: &str) -> String;
+}
+
+/// `html` passed through every configured transform in order.
+pub(crate) fn apply_transforms(html: String, url: &str, config: &SiteConfig) -> String {
+    config
+        .html_transforms
+        .iter()
+        .fold(html, |html, transform| transform.transform(html, url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::test_config;
+
+    #[derive(Debug)]
+    struct Append(&'static str);
+
+    impl HtmlTransform for Append {
+        fn transform(&self, html: String, url: &str) -> String {
+            format!("{html}{}{url}", self.0)
+        }
+    }
+
+    #[test]
+    fn transforms_run_in_registration_order() {
+        let config = test_config()
+            .with_html_transform(Append("[a]"))
+            .with_html_transform(Append("[b]"));
+        assert_eq!(
+            apply_transforms("<p>x</p>".to_string(), "/p/", &config),
+            "<p>x</p>[a]/p/[b]/p/"
+        );
+        assert_eq!(apply_transforms("y".to_string(), "/", &test_config()), "y");
+    }
+}
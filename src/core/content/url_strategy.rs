@@ -0,0 +1,164 @@
+//! Custom page URLs for library embedders. A [`UrlStrategy`] on
+//! [`SiteConfig`] picks each page's canonical URL and the output path follows
+//! from it, so every link Sherwood generates — nav, breadcrumbs, prev/next,
+//! feeds — points where the file is actually written.
+
+use crate::core::config::SiteConfig;
+use crate::core::content::frontmatter::FrontMatter;
+use crate::core::content::page::{Page, output_path_for};
+use crate::core::nav::{href_for, normalize_segments};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Chooses the public URL of each page, e.g. date-prefixed permalinks
+/// (`/2024/01/hello/`) in place of the path-mirroring default.
+///
+/// URLs are pretty: whatever is returned is treated as a directory, and the
+/// page is written to its `index.html` (`/2024/01/hello` →
+/// `_site/2024/01/hello/index.html`, served as `/2024/01/hello/`). Two pages
+/// resolving to the same URL fail the build, as colliding sources always do.
+///
+/// ```
+/// use std::path::Path;
+/// use sherwood::{FrontMatter, SiteConfig, UrlStrategy};
+///
+/// /// `content/blog/hello.md` dated 2024-01-15 → `/2024/01/hello/`.
+/// #[derive(Debug)]
+/// struct DatedPosts;
+///
+/// impl UrlStrategy for DatedPosts {
+///     fn url(&self, relative: &Path, frontmatter: &FrontMatter) -> Option<String> {
+///         let date = frontmatter.get_string("date")?;
+///         let slug = relative.file_stem()?.to_str()?;
+///         Some(format!("/{}/{}/{slug}/", date.get(..4)?, date.get(5..7)?))
+///     }
+/// }
+///
+/// let config = SiteConfig::new().with_url_strategy(DatedPosts);
+/// ```
+pub trait UrlStrategy: Send + Sync + fmt::Debug {
+    /// The canonical URL (root-relative, without the base path) for the page
+    /// whose source is `relative` to the content directory. Return `None` to
+    /// keep the default URL for this page.
+    fn url(&self, relative: &Path, frontmatter: &FrontMatter) -> Option<String>;
+}
+
+/// URL of the not-found page, rendered from a top-level `404.md` (any
+/// parser's extension) to `404.html` at the output root, where static hosts
+/// look for it.
+pub(crate) const NOT_FOUND_URL: &str = "/404.html";
+
+/// Whether `page` is the site's not-found page. It is rendered but kept out
+/// of nav, section listings, prev/next, related pages and feeds.
+pub(crate) fn is_not_found(page: &Page) -> bool {
+    page.url == NOT_FOUND_URL
+}
+
+/// Output path and canonical URL of a content file: `404.html` for the
+/// not-found page, else from the configured [`UrlStrategy`] when it has an
+/// opinion, otherwise mirroring the source path (`about.md` →
+/// `about/index.html`, `/about/`).
+pub(crate) fn page_location(
+    source: &Path,
+    frontmatter: &FrontMatter,
+    config: &SiteConfig,
+) -> (PathBuf, String) {
+    let relative = config.content_relative(source).unwrap_or(source);
+    if relative.parent() == Some(Path::new("")) && relative.file_stem() == Some("404".as_ref()) {
+        return (
+            config.output_dir.join("404.html"),
+            NOT_FOUND_URL.to_string(),
+        );
+    }
+    let custom = config
+        .url_strategy
+        .as_ref()
+        .and_then(|strategy| strategy.url(relative, frontmatter));
+    let output_path = match custom {
+        Some(url) => output_path_for_url(&url, config),
+        None => output_path_for(source, config),
+    };
+    let url = href_for(&output_path, config);
+    (output_path, url)
+}
+
+/// `/2024/01/hello/` → `<output_dir>/2024/01/hello/index.html`. `.` and
+/// `..` segments are resolved so a URL can't escape the output directory.
+fn output_path_for_url(url: &str, config: &SiteConfig) -> PathBuf {
+    let segments = normalize_segments(url);
+    let mut path = config.output_dir.clone();
+    path.extend(segments);
+    path.join("index.html")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::{pod_hash, test_config};
+    use gray_matter::Pod;
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct Flat;
+
+    impl UrlStrategy for Flat {
+        fn url(&self, relative: &Path, frontmatter: &FrontMatter) -> Option<String> {
+            frontmatter.get_string("keep")?;
+            Some(format!("/../x/{}", relative.file_stem()?.to_str()?))
+        }
+    }
+
+    fn frontmatter(keep: bool) -> FrontMatter {
+        let data = if keep {
+            pod_hash(&[("keep", Pod::String("y".to_string()))])
+        } else {
+            Pod::Null
+        };
+        FrontMatter {
+            title: "T".to_string(),
+            data,
+        }
+    }
+
+    #[test]
+    fn strategy_url_decides_output_path() {
+        let mut config = test_config();
+        config.url_strategy = Some(Arc::new(Flat));
+        let (output, url) = page_location(
+            Path::new("content/blog/deep/post.md"),
+            &frontmatter(true),
+            &config,
+        );
+        assert_eq!(output, PathBuf::from("_site/x/post/index.html"));
+        assert_eq!(url, "/x/post/");
+    }
+
+    #[test]
+    fn none_keeps_default_location() {
+        let mut config = test_config();
+        config.url_strategy = Some(Arc::new(Flat));
+        let (output, url) = page_location(
+            Path::new("content/blog/post.md"),
+            &frontmatter(false),
+            &config,
+        );
+        assert_eq!(output, PathBuf::from("_site/blog/post/index.html"));
+        assert_eq!(url, "/blog/post/");
+    }
+
+    #[test]
+    fn top_level_404_renders_to_root_404_html() {
+        let mut config = test_config();
+        config.url_strategy = Some(Arc::new(Flat));
+        let (output, url) = page_location(Path::new("content/404.md"), &frontmatter(true), &config);
+        assert_eq!(output, PathBuf::from("_site/404.html"));
+        assert_eq!(url, NOT_FOUND_URL);
+
+        let (output, _) = page_location(
+            Path::new("content/blog/404.md"),
+            &frontmatter(false),
+            &config,
+        );
+        assert_eq!(output, PathBuf::from("_site/blog/404/index.html"));
+    }
+}
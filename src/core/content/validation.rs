@@ -0,0 +1,124 @@
+//! Required frontmatter per part of the content tree, e.g. "every blog post
+//! has a `date`". Rules are checked as each page loads, so `build`, `serve`,
+//! `export` and `check` all reject a page that breaks one.
+
+use crate::core::content::frontmatter::FrontMatter;
+use crate::core::content::ignore::path_matches;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Frontmatter fields every page matching a path glob must set, configured
+/// with [`SiteConfig::with_validation`](crate::SiteConfig::with_validation).
+///
+/// `pattern` uses `.sherwoodignore` glob syntax against the source path
+/// relative to the content directory: `blog/**` is everything under `blog/`,
+/// `docs/*.md` only its direct children, and a pattern without a `/`
+/// (`*.md`) matches at any depth. A page matching several rules must satisfy
+/// all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValidationRule {
+    pub pattern: String,
+    /// Frontmatter keys that must be present (and not null).
+    pub required: Vec<String>,
+}
+
+impl ValidationRule {
+    pub fn new<S: Into<String>>(
+        pattern: impl Into<String>,
+        required: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            pattern: pattern.into(),
+            required: required.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Parses `glob=field,field`, the CLI's `--require` format.
+impl FromStr for ValidationRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern, fields) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected `glob=field,field`, got `{s}`"))?;
+        let required: Vec<&str> = fields
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .collect();
+        if pattern.trim().is_empty() || required.is_empty() {
+            return Err(format!("expected a glob and at least one field, got `{s}`"));
+        }
+        Ok(Self::new(pattern.trim(), required))
+    }
+}
+
+/// The fields `rules` require of the page at `relative` that its frontmatter
+/// doesn't set, in rule order without repeats.
+pub(crate) fn missing_fields(
+    rules: &[ValidationRule],
+    relative: &Path,
+    frontmatter: &FrontMatter,
+) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for rule in rules {
+        if !path_matches(&rule.pattern, relative) {
+            continue;
+        }
+        for field in &rule.required {
+            let set = !matches!(frontmatter.get(field), None | Some(gray_matter::Pod::Null));
+            if !set && !missing.contains(field) {
+                missing.push(field.clone());
+            }
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::pod_hash;
+    use gray_matter::Pod;
+
+    fn frontmatter(pairs: &[(&str, Pod)]) -> FrontMatter {
+        FrontMatter {
+            title: "T".to_string(),
+            data: pod_hash(pairs),
+        }
+    }
+
+    #[test]
+    fn reports_fields_missing_under_matching_globs() {
+        let rules = [
+            ValidationRule::new("blog/**", ["date", "author"]),
+            ValidationRule::new("*.md", ["title", "date"]),
+            ValidationRule::new("docs/*.md", ["weight"]),
+        ];
+        let fm = frontmatter(&[
+            ("title", Pod::String("T".to_string())),
+            ("author", Pod::Null),
+        ]);
+        assert_eq!(
+            missing_fields(&rules, Path::new("blog/2024/post.md"), &fm),
+            ["date", "author"]
+        );
+        assert_eq!(
+            missing_fields(&rules, Path::new("docs/a.md"), &fm),
+            ["date", "weight"]
+        );
+        assert!(missing_fields(&rules, Path::new("docs/deep/a.txt"), &fm).is_empty());
+    }
+
+    #[test]
+    fn parses_cli_format() {
+        assert_eq!(
+            "blog/** = date, author".parse::<ValidationRule>(),
+            Ok(ValidationRule::new("blog/**", ["date", "author"]))
+        );
+        assert!("blog/**".parse::<ValidationRule>().is_err());
+        assert!("blog/**=".parse::<ValidationRule>().is_err());
+    }
+}
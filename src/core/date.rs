@@ -0,0 +1,621 @@
+//! Minimal calendar-date handling for frontmatter `date` fields, feeds, and
+//! timestamps. Sherwood only needs to parse `YYYY-MM-DD`, optionally followed
+//! by an RFC 3339 time and offset (which only feeds use, via [`Timestamp`]),
+//! and format it a few ways, so this is hand-rolled rather than pulling in a
+//! date crate.
+//!
+//! [`PageDate`] is the public face: a page's `date` formatted for display
+//! with [`SiteConfig::date_format`](crate::SiteConfig::date_format).
+
+use crate::core::content::parser::escape_html;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// The display format used unless [`SiteConfig::with_date_format`] says
+/// otherwise: `January 15, 2024`.
+///
+/// [`SiteConfig::with_date_format`]: crate::SiteConfig::with_date_format
+pub const DEFAULT_DATE_FORMAT: &str = "%B %-d, %Y";
+
+/// The special [`SiteConfig::date_format`](crate::SiteConfig::date_format)
+/// value that renders dates relative to the build day: `today`, `yesterday`,
+/// `3 days ago`, `2 months ago`, `in 5 days`.
+pub const RELATIVE_DATE_FORMAT: &str = "relative";
+
+/// A page's frontmatter `date`, formatted for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PageDate {
+    /// Machine-readable `YYYY-MM-DD`, for `<time datetime="…">`. `None` when
+    /// the frontmatter value isn't a recognizable date.
+    pub iso: Option<String>,
+    /// Human-readable date per the site's date format. An unparseable
+    /// frontmatter value passes through here unchanged.
+    pub display: String,
+}
+
+impl PageDate {
+    /// Format a raw frontmatter value with `format` (a strftime-style pattern
+    /// or [`RELATIVE_DATE_FORMAT`]), taking relative dates from `today`.
+    pub(crate) fn new(raw: &str, format: &str, today: Date) -> Self {
+        match Date::parse(raw) {
+            Some(date) => PageDate {
+                iso: Some(date.iso()),
+                display: if format == RELATIVE_DATE_FORMAT {
+                    date.relative_to(today)
+                } else {
+                    date.format(format)
+                },
+            },
+            None => PageDate {
+                iso: None,
+                display: raw.to_string(),
+            },
+        }
+    }
+
+    /// The date as HTML: `<time datetime="2024-01-15">January 15, 2024</time>`,
+    /// or just the escaped display text when there's no ISO value, so an
+    /// unparseable date never yields an invalid `datetime`.
+    pub fn to_html(&self) -> String {
+        let display = escape_html(&self.display);
+        match &self.iso {
+            Some(iso) => format!("<time datetime=\"{iso}\">{display}</time>"),
+            None => display,
+        }
+    }
+}
+
+/// A proleptic Gregorian calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Date {
+    pub(crate) year: i64,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+}
+
+impl Date {
+    /// Parse `YYYY-MM-DD`, allowing a trailing time component (`T…` or a
+    /// space-separated time) as in `2026-05-31T09:30:00Z`. Returns `None`
+    /// for anything else, including out-of-range months and days and a time
+    /// or offset that doesn't parse.
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        Self::parse_with_time(raw).map(|(date, _)| date)
+    }
+
+    /// [`Date::parse`], also returning the time of day (seconds since
+    /// midnight) and offset, if any. A bare date is midnight with no offset.
+    fn parse_with_time(raw: &str) -> Option<(Self, (u32, Option<UtcOffset>))> {
+        let raw = raw.trim();
+        let date_part = raw.get(..10)?;
+        let time = match raw.get(10..) {
+            None | Some("") => (0, None),
+            Some(rest) => parse_time(rest.strip_prefix(['T', 't', ' '])?)?,
+        };
+        let mut parts = date_part.split('-');
+        let year: i64 = parse_digits(parts.next()?, 4)?;
+        let month: u32 = parse_digits(parts.next()?, 2)?;
+        let day: u32 = parse_digits(parts.next()?, 2)?;
+        if parts.next().is_some() || !(1..=12).contains(&month) {
+            return None;
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some((Self { year, month, day }, time))
+    }
+
+    /// Today's date in UTC.
+    pub(crate) fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01.
+    pub(crate) fn days_since_epoch(self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+
+    /// `YYYY-MM-DD`.
+    pub(crate) fn iso(self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Format with a strftime-style pattern. Supported: `%Y` (2024), `%y`
+    /// (24), `%m` / `%-m` (01 / 1), `%d` / `%-d` (05 / 5), `%B` (January),
+    /// `%b` (Jan), `%A` (Monday), `%a` (Mon), and `%%`. Anything else is
+    /// copied through as written.
+    pub(crate) fn format(self, pattern: &str) -> String {
+        let weekday = (self.days_since_epoch() + 4).rem_euclid(7) as usize;
+        let month = (self.month - 1) as usize;
+        let mut out = String::with_capacity(pattern.len() + 8);
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let unpadded = chars.next_if_eq(&'-').is_some();
+            let spec = chars.next();
+            match (unpadded, spec) {
+                (false, Some('Y')) => out.push_str(&format!("{:04}", self.year)),
+                (false, Some('y')) => out.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                (false, Some('m')) => out.push_str(&format!("{:02}", self.month)),
+                (true, Some('m')) => out.push_str(&self.month.to_string()),
+                (false, Some('d')) => out.push_str(&format!("{:02}", self.day)),
+                (true, Some('d')) => out.push_str(&self.day.to_string()),
+                (false, Some('B')) => out.push_str(MONTH_NAMES[month]),
+                (false, Some('b')) => out.push_str(MONTHS[month]),
+                (false, Some('A')) => out.push_str(WEEKDAY_NAMES[weekday]),
+                (false, Some('a')) => out.push_str(WEEKDAYS[weekday]),
+                (false, Some('%')) => out.push('%'),
+                (unpadded, spec) => {
+                    out.push('%');
+                    if unpadded {
+                        out.push('-');
+                    }
+                    out.extend(spec);
+                }
+            }
+        }
+        out
+    }
+
+    /// Describe this date relative to `today`: `today`, `yesterday`,
+    /// `tomorrow`, `N days ago`, `N months ago`, `N years ago`, or the
+    /// `in …` equivalents for future dates. Months are 30 days and years
+    /// 365 — close enough for display.
+    pub(crate) fn relative_to(self, today: Date) -> String {
+        let diff = today.days_since_epoch() - self.days_since_epoch();
+        match diff {
+            0 => return "today".to_string(),
+            1 => return "yesterday".to_string(),
+            -1 => return "tomorrow".to_string(),
+            _ => {}
+        }
+        let days = diff.abs();
+        let (n, unit) = if days < 30 {
+            (days, "day")
+        } else if days < 365 {
+            (days / 30, "month")
+        } else {
+            (days / 365, "year")
+        };
+        let plural = if n == 1 { "" } else { "s" };
+        if diff > 0 {
+            format!("{n} {unit}{plural} ago")
+        } else {
+            format!("in {n} {unit}{plural}")
+        }
+    }
+}
+
+/// A fixed offset from UTC: the zone assumed for frontmatter dates that
+/// don't give one ([`SiteConfig::timezone`](crate::SiteConfig::timezone)).
+/// Parses from `Z` / `UTC`, `+02:00`, `-05:30` or `+0200`; displays as `Z`
+/// or `+02:00`.
+///
+/// ```
+/// use sherwood::UtcOffset;
+/// let offset: UtcOffset = "+05:30".parse().unwrap();
+/// assert_eq!(offset.minutes(), 330);
+/// assert_eq!(UtcOffset::UTC.to_string(), "Z");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UtcOffset {
+    minutes: i32,
+}
+
+impl UtcOffset {
+    /// UTC itself, the default.
+    pub const UTC: Self = Self { minutes: 0 };
+
+    /// An offset of `minutes` east of UTC; `None` beyond ±23:59.
+    pub fn from_minutes(minutes: i32) -> Option<Self> {
+        (minutes.abs() < 24 * 60).then_some(Self { minutes })
+    }
+
+    /// Minutes east of UTC (negative to the west).
+    pub fn minutes(self) -> i32 {
+        self.minutes
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        if raw.eq_ignore_ascii_case("z") || raw.eq_ignore_ascii_case("utc") {
+            return Some(Self::UTC);
+        }
+        let sign = match raw.as_bytes().first()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        let rest = &raw[1..];
+        let (hours, minutes) = match rest.split_once(':') {
+            Some(parts) => parts,
+            None if rest.len() == 4 => rest.split_at_checked(2)?,
+            None => return None,
+        };
+        let hours: i32 = parse_digits(hours, 2)?;
+        let minutes: i32 = parse_digits(minutes, 2)?;
+        if minutes >= 60 {
+            return None;
+        }
+        Self::from_minutes(sign * (hours * 60 + minutes))
+    }
+
+    /// `+0200` style, as RFC 2822 writes zones.
+    fn rfc2822(self) -> String {
+        let sign = if self.minutes < 0 { '-' } else { '+' };
+        let minutes = self.minutes.abs();
+        format!("{sign}{:02}{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl FromStr for UtcOffset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| {
+            format!("invalid UTC offset `{s}` (expected Z, UTC or ±HH:MM, e.g. +02:00)")
+        })
+    }
+}
+
+impl fmt::Display for UtcOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.minutes == 0 {
+            return f.write_str("Z");
+        }
+        let sign = if self.minutes < 0 { '-' } else { '+' };
+        let minutes = self.minutes.abs();
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+/// A frontmatter date with its time of day and UTC offset, for feed
+/// timestamps. A bare date is midnight; a date without an offset is in the
+/// site's default zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Timestamp {
+    date: Date,
+    /// Seconds since local midnight.
+    seconds: u32,
+    offset: UtcOffset,
+}
+
+impl Timestamp {
+    /// Parse `2024-01-15T09:30:00+02:00` (the seconds, fraction and offset
+    /// are optional; a space may stand in for the `T`) or a bare
+    /// `2024-01-15`, taking `default_offset` when no offset is given. Only a
+    /// bare date means midnight: as in [`PageDate`], a time or offset that
+    /// doesn't parse makes the whole value invalid.
+    pub(crate) fn parse(raw: &str, default_offset: UtcOffset) -> Option<Self> {
+        let (date, (seconds, offset)) = Date::parse_with_time(raw)?;
+        Some(Self {
+            date,
+            seconds,
+            offset: offset.unwrap_or(default_offset),
+        })
+    }
+
+    /// The calendar date as written, `YYYY-MM-DD`.
+    pub(crate) fn iso(self) -> String {
+        self.date.iso()
+    }
+
+    /// Seconds since the Unix epoch — the instant, for ordering timestamps
+    /// written in different zones.
+    pub(crate) fn epoch_seconds(self) -> i64 {
+        self.date.days_since_epoch() * 86_400 + i64::from(self.seconds)
+            - i64::from(self.offset.minutes) * 60
+    }
+
+    /// RFC 3339 (Atom `<updated>`, JSON Feed), e.g.
+    /// `2026-05-31T09:30:00+02:00`.
+    pub(crate) fn rfc3339(self) -> String {
+        format!("{}T{}{}", self.iso(), self.clock(), self.offset)
+    }
+
+    /// RFC 2822 (RSS `pubDate`), e.g. `Sun, 31 May 2026 09:30:00 +0200`.
+    pub(crate) fn rfc2822(self) -> String {
+        let date = self.date;
+        let weekday = WEEKDAYS[(date.days_since_epoch() + 4).rem_euclid(7) as usize];
+        format!(
+            "{weekday}, {:02} {} {:04} {} {}",
+            date.day,
+            MONTHS[(date.month - 1) as usize],
+            date.year,
+            self.clock(),
+            self.offset.rfc2822()
+        )
+    }
+
+    /// `HH:MM:SS`.
+    fn clock(self) -> String {
+        let s = self.seconds;
+        format!("{:02}:{:02}:{:02}", s / 3600, s % 3600 / 60, s % 60)
+    }
+}
+
+/// `HH:MM[:SS[.fraction]][offset]` → seconds since midnight and the offset,
+/// if one is given. A leap second reads as `:59`.
+fn parse_time(raw: &str) -> Option<(u32, Option<UtcOffset>)> {
+    let raw = raw.trim();
+    let zone_at = raw.find(['Z', 'z', '+', '-']).unwrap_or(raw.len());
+    let (clock, zone) = raw.split_at(zone_at);
+    let mut parts = clock.trim_end().split(':');
+    let hours: u32 = parse_digits(parts.next()?, 2)?;
+    let minutes: u32 = parse_digits(parts.next()?, 2)?;
+    let seconds: u32 = match parts.next() {
+        Some(s) => parse_digits(s.split('.').next()?, 2)?,
+        None => 0,
+    };
+    if parts.next().is_some() || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    let offset = match zone {
+        "" => None,
+        zone => Some(UtcOffset::parse(zone)?),
+    };
+    Some((hours * 3600 + minutes * 60 + seconds.min(59), offset))
+}
+
+fn parse_digits<T: std::str::FromStr>(s: &str, len: usize) -> Option<T> {
+    if s.len() != len || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Proleptic Gregorian `(year, month, day)` → days since 1970-01-01. Howard
+/// Hinnant's `days_from_civil`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Days since 1970-01-01 → proleptic Gregorian `(year, month, day)`. Howard
+/// Hinnant's `civil_from_days`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Format a `SystemTime` as a UTC RFC 3339 timestamp (`YYYY-MM-DDTHH:MM:SSZ`).
+/// Times before the Unix epoch clamp to it.
+pub(crate) fn format_system_time(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_plain_date() {
+        let d = Date::parse("2026-05-31").unwrap();
+        assert_eq!((d.year, d.month, d.day), (2026, 5, 31));
+    }
+
+    #[test]
+    fn parses_date_with_time_suffix() {
+        assert!(Date::parse("2026-05-31T09:30:00+02:00").is_some());
+        assert!(Date::parse("2026-05-31 09:30").is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for raw in [
+            "",
+            "2026-13-01",
+            "2026-02-30",
+            "2026-5-31",
+            "31/05/2026",
+            "2026-05-31x",
+        ] {
+            assert!(Date::parse(raw).is_none(), "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn accepts_leap_day() {
+        assert!(Date::parse("2024-02-29").is_some());
+        assert!(Date::parse("2023-02-29").is_none());
+    }
+
+    #[test]
+    fn days_round_trip() {
+        for days in [-1, 0, 19_782, 20_604] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn formats_rfc2822() {
+        let utc = |raw| Timestamp::parse(raw, UtcOffset::UTC).unwrap().rfc2822();
+        assert_eq!(utc("2026-05-31"), "Sun, 31 May 2026 00:00:00 +0000");
+        assert_eq!(
+            utc("2026-05-31T09:30:00-05:30"),
+            "Sun, 31 May 2026 09:30:00 -0530"
+        );
+    }
+
+    #[test]
+    fn formats_rfc3339() {
+        let utc = |raw| Timestamp::parse(raw, UtcOffset::UTC).unwrap().rfc3339();
+        assert_eq!(utc("2026-05-31"), "2026-05-31T00:00:00Z");
+        assert_eq!(
+            utc("2026-05-31T09:30:15.250+02:00"),
+            "2026-05-31T09:30:15+02:00"
+        );
+        assert_eq!(utc("2026-05-31 09:30"), "2026-05-31T09:30:00Z");
+    }
+
+    #[test]
+    fn timestamps_take_the_default_offset_and_order_by_instant() {
+        let berlin: UtcOffset = "+02:00".parse().unwrap();
+        let ts = |raw| Timestamp::parse(raw, berlin).unwrap();
+        assert_eq!(ts("2026-05-31").rfc3339(), "2026-05-31T00:00:00+02:00");
+        assert_eq!(ts("2026-05-31T08:00Z").rfc3339(), "2026-05-31T08:00:00Z");
+        // 09:00 in Berlin is before 08:00 UTC.
+        assert!(ts("2026-05-31T09:00").epoch_seconds() < ts("2026-05-31T08:00Z").epoch_seconds());
+    }
+
+    #[test]
+    fn rejects_unreadable_times_and_offsets() {
+        for raw in [
+            "2026-05-31Tlunch",
+            "2026-05-31T",
+            "2024-01-15T25:99+99:00",
+            "2024-01-15T09:30+99:00",
+            "2024-01-15 09:30 CET",
+        ] {
+            assert!(Timestamp::parse(raw, UtcOffset::UTC).is_none(), "{raw:?}");
+            assert!(Date::parse(raw).is_none(), "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn parses_utc_offsets() {
+        for (raw, minutes) in [("Z", 0), ("utc", 0), ("+02:00", 120), ("-0530", -330)] {
+            assert_eq!(
+                raw.parse::<UtcOffset>().unwrap().minutes(),
+                minutes,
+                "{raw}"
+            );
+        }
+        for raw in ["", "02:00", "+2", "+24:00", "+01:60", "CET"] {
+            assert!(raw.parse::<UtcOffset>().is_err(), "{raw:?}");
+        }
+        assert_eq!(UtcOffset::from_minutes(-90).unwrap().to_string(), "-01:30");
+    }
+
+    #[test]
+    fn formats_strftime_patterns() {
+        let d = Date::parse("2024-01-05").unwrap();
+        assert_eq!(d.format(DEFAULT_DATE_FORMAT), "January 5, 2024");
+        assert_eq!(d.format("%d/%m/%y"), "05/01/24");
+        assert_eq!(d.format("%a %-d %b"), "Fri 5 Jan");
+        assert_eq!(d.format("%A, 100%% %Q"), "Friday, 100% %Q");
+    }
+
+    #[test]
+    fn formats_relative_dates() {
+        let today = Date::parse("2026-05-31").unwrap();
+        let rel = |raw| Date::parse(raw).unwrap().relative_to(today);
+        assert_eq!(rel("2026-05-31"), "today");
+        assert_eq!(rel("2026-05-30"), "yesterday");
+        assert_eq!(rel("2026-06-01"), "tomorrow");
+        assert_eq!(rel("2026-05-28"), "3 days ago");
+        assert_eq!(rel("2026-03-31"), "2 months ago");
+        assert_eq!(rel("2025-05-31"), "1 year ago");
+        assert_eq!(rel("2026-06-05"), "in 5 days");
+    }
+
+    #[test]
+    fn page_date_passes_unparseable_values_through() {
+        let today = Date::parse("2026-05-31").unwrap();
+        let date = PageDate::new("sometime in spring", DEFAULT_DATE_FORMAT, today);
+        assert_eq!(date.iso, None);
+        assert_eq!(date.display, "sometime in spring");
+
+        let date = PageDate::new("2024-01-15", DEFAULT_DATE_FORMAT, today);
+        assert_eq!(date.iso.as_deref(), Some("2024-01-15"));
+        assert_eq!(date.display, "January 15, 2024");
+    }
+
+    #[test]
+    fn page_date_html_marks_up_only_parsed_dates() {
+        let today = Date::parse("2026-05-31").unwrap();
+        assert_eq!(
+            PageDate::new("2024-01-15", DEFAULT_DATE_FORMAT, today).to_html(),
+            "<time datetime=\"2024-01-15\">January 15, 2024</time>"
+        );
+        assert_eq!(
+            PageDate::new("<soon>", DEFAULT_DATE_FORMAT, today).to_html(),
+            "&lt;soon&gt;"
+        );
+    }
+
+    #[test]
+    fn formats_system_time() {
+        assert_eq!(format_system_time(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        // 2026-05-31T07:30:15Z
+        let t = UNIX_EPOCH + Duration::from_secs(1_780_212_615);
+        assert_eq!(format_system_time(t), "2026-05-31T07:30:15Z");
+    }
+}
@@ -0,0 +1,255 @@
+//! Single-file export: every page, in nav order, concatenated into one
+//! self-contained HTML document with the stylesheet inlined and a table of
+//! contents up front — for "download the manual" links and offline reading.
+//!
+//! Each page becomes a `<section>` whose id is slugged from its URL
+//! (`/guide/setup/` → `guide-setup`, the root → `index`). Ids inside a page
+//! are prefixed with that slug (`guide-setup--install`) so headings repeated
+//! across pages stay unique, and links between pages are rewritten to the
+//! in-document anchors. Links that don't point at a page (external URLs,
+//! images, other assets) are left as they are; assets are not embedded.
+
+use crate::core::build::{BuildError, load_site};
+use crate::core::config::SiteConfig;
+use crate::core::content::page::Page;
+use crate::core::content::parser::{ParserRegistry, escape_html};
+use crate::core::content::slug::{slugify, unique_slug};
+use crate::core::content::toc::toc_html;
+use crate::core::nav::{is_external, is_root_index, normalize_segments, strip_base_path};
+use std::collections::{HashMap, HashSet};
+
+/// Load the site and render it as one HTML document, with `stylesheet` (the
+/// CSS text, not a path) inlined in a `<style>` element. Nothing is written
+/// to the output directory.
+///
+/// The document title is the root index page's title. The TOC lists every
+/// page, with each page's headings (levels per
+/// [`SiteConfig::toc_min_level`] / [`SiteConfig::toc_max_level`]) nested
+/// beneath it.
+pub fn build_single_file(
+    config: &SiteConfig,
+    registry: &ParserRegistry,
+    stylesheet: &str,
+) -> Result<String, BuildError> {
+    let (pages, _assets) = load_site(config, registry)?;
+    let anchors = page_anchors(&pages);
+    let levels = config.toc_min_level..=config.toc_max_level;
+
+    let mut toc = String::new();
+    let mut body = String::new();
+    for page in &pages {
+        let anchor = &anchors[&page.url];
+        let content = rewrite_page(page, anchor, &anchors, &config.base_path);
+        toc.push_str(&format!(
+            "<li><a href=\"#{anchor}\">{}</a>",
+            escape_html(&page.frontmatter.title)
+        ));
+        if let Some(headings) = toc_html(&content, levels.clone()) {
+            toc.push_str(&headings);
+        }
+        toc.push_str("</li>");
+        body.push_str(&format!(
+            "<section id=\"{anchor}\">\n{content}\n</section>\n"
+        ));
+    }
+
+    let title = pages
+        .iter()
+        .find(|p| is_root_index(p, config))
+        .map(|p| p.frontmatter.title.as_str())
+        .unwrap_or_default();
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"UTF-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n\
+         <title>{}</title>\n<style>\n{stylesheet}\n</style>\n</head>\n<body>\n\
+         <nav class=\"toc\" aria-label=\"Table of contents\"><ul>{toc}</ul></nav>\n\
+         <main>\n{body}</main>\n</body>\n</html>\n",
+        escape_html(&config.language),
+        escape_html(title)
+    ))
+}
+
+/// Canonical URL → section id. Page slugs never contain `--` (slugify
+/// collapses dash runs), so they can't collide with prefixed heading ids; two
+/// URLs slugging alike (`/a-b/`, `/a/b/`) get a numeric suffix.
+fn page_anchors(pages: &[Page]) -> HashMap<String, String> {
+    let mut used = HashSet::new();
+    let mut anchors = HashMap::new();
+    for page in pages {
+        // Path separators would be dropped as punctuation; make them word
+        // breaks so `/guide/setup/` becomes `guide-setup`, not `guidesetup`.
+        let anchor = unique_slug(slugify(&page.url.replace('/', " ")), "index", &mut used);
+        anchors.insert(page.url.clone(), anchor);
+    }
+    anchors
+}
+
+/// Prefix the page's ids with its anchor and point links at other pages (or
+/// fragments within them) to their in-document anchors.
+fn rewrite_page(
+    page: &Page,
+    anchor: &str,
+    anchors: &HashMap<String, String>,
+    base_path: &str,
+) -> String {
+    rewrite_attrs(&page.content_html, |name, value| match name {
+        "id" => Some(format!("{anchor}--{value}")),
+        "href" => {
+            let (url, fragment) = link_target(value, &page.url, base_path)?;
+            let target = anchors.get(&url)?;
+            Some(match fragment {
+                Some(f) if !f.is_empty() => format!("#{target}--{f}"),
+                _ => format!("#{target}"),
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Replace double-quoted attribute values: `f(name, value)` returns the new
+/// value, or `None` to keep it. Text content can't contain a bare `="` —
+/// rendered markdown escapes quotes — so every match is an attribute.
+fn rewrite_attrs(html: &str, mut f: impl FnMut(&str, &str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(at) = rest.find("=\"") {
+        let before = &rest[..at];
+        let name = &before[before.rfind(char::is_whitespace).map_or(0, |i| i + 1)..];
+        let value_start = at + 2;
+        let Some(len) = rest[value_start..].find('"') else {
+            break;
+        };
+        let value = &rest[value_start..value_start + len];
+        out.push_str(&rest[..value_start]);
+        match f(name, value) {
+            Some(new) => out.push_str(&new),
+            None => out.push_str(value),
+        }
+        out.push('"');
+        rest = &rest[value_start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The canonical page URL and fragment an `href` points at, resolved
+/// against the linking page's URL; `None` for external links. Root-relative
+/// links may include the base path. `/guide/index.html` and `/guide` both
+/// name `/guide/`.
+fn link_target<'a>(
+    href: &'a str,
+    page_url: &str,
+    base_path: &str,
+) -> Option<(String, Option<&'a str>)> {
+    let (path, fragment) = match href.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (href, None),
+    };
+    let path = path.split('?').next().unwrap_or_default();
+    if path.is_empty() {
+        return Some((page_url.to_string(), fragment));
+    }
+    if is_external(path) {
+        return None;
+    }
+    let joined = match path.strip_prefix('/') {
+        Some(_) => strip_base_path(path, base_path).to_string(),
+        // Pretty URLs are directories, so relative links resolve from the
+        // page's own URL.
+        None => format!("{page_url}{path}"),
+    };
+    let mut segments = normalize_segments(&joined);
+    if segments.last() == Some(&"index.html") {
+        segments.pop();
+    }
+    let mut url = format!("/{}", segments.join("/"));
+    if !segments.is_empty() && !segments.last().is_some_and(|s| s.contains('.')) {
+        url.push('/');
+    }
+    Some((url, fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn link_target_resolves_page_links() {
+        assert_eq!(
+            link_target("/guide/#setup", "/", ""),
+            Some(("/guide/".to_string(), Some("setup")))
+        );
+        assert_eq!(
+            link_target("../about", "/guide/intro/", ""),
+            Some(("/guide/about/".to_string(), None))
+        );
+        assert_eq!(
+            link_target("/docs/index.html", "/a/", "/docs"),
+            Some(("/".to_string(), None))
+        );
+        assert_eq!(
+            link_target("#top", "/a/", ""),
+            Some(("/a/".to_string(), Some("top")))
+        );
+        assert_eq!(link_target("https://example.com/", "/", ""), None);
+        assert_eq!(link_target("//cdn.example/x.js", "/", ""), None);
+    }
+
+    #[test]
+    fn rewrite_attrs_touches_only_attribute_values() {
+        let html = "<h2 id=\"a\">x=&quot;y&quot;</h2><a href=\"#a\">a</a>";
+        assert_eq!(
+            rewrite_attrs(html, |name, value| Some(format!("{name}:{value}"))),
+            "<h2 id=\"id:a\">x=&quot;y&quot;</h2><a href=\"href:#a\">a</a>"
+        );
+    }
+
+    #[test]
+    fn page_anchors_are_unique() {
+        let mut a = crate::core::nav::test_support::make_page("a-b", "A");
+        a.url = "/a-b/".into();
+        let mut b = a.clone();
+        b.url = "/a/b/".into();
+        let mut root = a.clone();
+        root.url = "/".into();
+        let anchors = page_anchors(&[root, a, b]);
+        assert_eq!(anchors["/"], "index");
+        assert_eq!(anchors["/a-b/"], "a-b");
+        assert_eq!(anchors["/a/b/"], "a-b-1");
+    }
+
+    #[test]
+    fn single_file_inlines_css_and_links_pages() {
+        let tmp = TempDir::new().unwrap();
+        let content_dir = tmp.path().join("content");
+        fs::create_dir_all(content_dir.join("guide")).unwrap();
+        fs::write(
+            content_dir.join("index.md"),
+            "---\ntitle: Manual\n---\n\nSee [setup](/guide/setup/#install).\n",
+        )
+        .unwrap();
+        fs::write(
+            content_dir.join("guide/setup.md"),
+            "---\ntitle: Setup\n---\n\n## Install\n\n[Back](../../)\n",
+        )
+        .unwrap();
+        let config = SiteConfig::new()
+            .with_content_dir(&content_dir)
+            .with_output_dir(tmp.path().join("_site"))
+            .with_language("de");
+
+        let html = build_single_file(&config, &ParserRegistry::default(), "body{}").unwrap();
+        assert!(html.contains("<html lang=\"de\">"), "{html}");
+        assert!(html.contains("<title>Manual</title>"), "{html}");
+        assert!(html.contains("<style>\nbody{}\n</style>"), "{html}");
+        assert!(html.contains("<section id=\"index\">"), "{html}");
+        assert!(html.contains("<section id=\"guide-setup\">"), "{html}");
+        assert!(html.contains("<h2 id=\"guide-setup--install\">"), "{html}");
+        assert!(html.contains("href=\"#guide-setup--install\""), "{html}");
+        assert!(html.contains("href=\"#index\""), "{html}");
+        assert!(html.find("id=\"index\"") < html.find("id=\"guide-setup\""));
+        assert!(!config.output_dir.exists());
+    }
+}
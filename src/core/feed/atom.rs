@@ -0,0 +1,166 @@
+//! Atom 1.0 serialization (RFC 4287).
+
+use super::{FeedItem, FeedMeta};
+use crate::core::content::parser::escape_html as escape;
+
+pub(super) fn render(meta: &FeedMeta, items: &[FeedItem], self_url: &str) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", escape(&meta.title)));
+    xml.push_str(&format!(
+        "  <link rel=\"alternate\" href=\"{}\"/>\n",
+        escape(&meta.site_url)
+    ));
+    xml.push_str(&format!(
+        "  <link rel=\"self\" href=\"{}\"/>\n",
+        escape(self_url)
+    ));
+    xml.push_str(&format!("  <id>{}</id>\n", escape(&meta.site_url)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", meta.updated));
+    // RFC 4287 §4.1.1: every entry needs an author, its own or the feed's.
+    xml.push_str(&format!(
+        "  <author><name>{}</name></author>\n",
+        escape(&meta.author)
+    ));
+    for item in items {
+        let page = item.page;
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape(&page.frontmatter.title)
+        ));
+        xml.push_str(&format!(
+            "    <link rel=\"alternate\" href=\"{}\"/>\n",
+            escape(&item.url)
+        ));
+        xml.push_str(&format!("    <id>{}</id>\n", escape(&entry_id(item))));
+        xml.push_str(&format!("    <updated>{}</updated>\n", item.date.rfc3339()));
+        if let Some(author) = page.frontmatter.get_string("author") {
+            xml.push_str(&format!(
+                "    <author><name>{}</name></author>\n",
+                escape(&author)
+            ));
+        }
+        if let Some(excerpt) = &page.excerpt_html {
+            xml.push_str(&format!(
+                "    <summary type=\"html\">{}</summary>\n",
+                escape(excerpt)
+            ));
+        }
+        if let Some(content) = &item.content {
+            xml.push_str(&format!(
+                "    <content type=\"html\">{}</content>\n",
+                escape(content)
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// A stable tag URI (RFC 4151) for an entry: `tag:<host>,<date>:<path>`, e.g.
+/// `tag:example.com,2026-05-31:/blog/first/`. Falls back to the entry URL when
+/// no site URL is configured (there is no authority to mint a tag from).
+fn entry_id(item: &FeedItem) -> String {
+    let Some(rest) = item.url.split_once("://").map(|(_, rest)| rest) else {
+        return item.url.clone();
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let host = authority.split(':').next().unwrap_or(authority);
+    format!("tag:{host},{}:{path}", item.date.iso())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::{Timestamp, UtcOffset};
+    use crate::core::nav::test_support::{make_page, make_page_with_data, pod_hash};
+    use gray_matter::Pod;
+
+    fn meta() -> FeedMeta {
+        FeedMeta {
+            title: "My <Site>".to_string(),
+            author: "Jane Doe".to_string(),
+            site_url: "https://example.com/".to_string(),
+            updated: "2026-05-31T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn entry_id_is_tag_uri() {
+        let page = make_page("blog/first", "First");
+        let item = FeedItem {
+            page: &page,
+            date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+            url: "https://example.com:8080/docs/blog/first/".to_string(),
+            content: None,
+        };
+        assert_eq!(
+            entry_id(&item),
+            "tag:example.com,2026-05-31:/docs/blog/first/"
+        );
+    }
+
+    #[test]
+    fn renders_feed_and_entries() {
+        let mut page = make_page("blog/first", "First & Best");
+        page.excerpt_html = Some("<p>Intro</p>".to_string());
+        let items = vec![FeedItem {
+            page: &page,
+            date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+            url: "https://example.com/blog/first/".to_string(),
+            content: None,
+        }];
+        let xml = render(&meta(), &items, "https://example.com/atom.xml");
+        assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(xml.contains("<title>My &lt;Site&gt;</title>"));
+        assert!(xml.contains("<link rel=\"self\" href=\"https://example.com/atom.xml\"/>"));
+        assert!(xml.contains("<title>First &amp; Best</title>"));
+        assert!(xml.contains("<id>tag:example.com,2026-05-31:/blog/first/</id>"));
+        assert!(xml.contains("<updated>2026-05-31T00:00:00Z</updated>"));
+        assert!(xml.contains("<summary type=\"html\">&lt;p&gt;Intro&lt;/p&gt;</summary>"));
+        assert!(!xml.contains("<content"));
+    }
+
+    #[test]
+    fn entries_without_an_author_fall_back_to_the_feed_author() {
+        let page = make_page("blog/first", "First");
+        let authored = make_page_with_data(
+            "blog/second",
+            "Second",
+            pod_hash(&[("author", Pod::String("Ada".to_string()))]),
+        );
+        let items: Vec<FeedItem> = [&page, &authored]
+            .into_iter()
+            .map(|page| FeedItem {
+                page,
+                date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+                url: format!("https://example.com{}", page.url),
+                content: None,
+            })
+            .collect();
+        let xml = render(&meta(), &items, "https://example.com/atom.xml");
+        let (head, entries) = xml.split_once("<entry>").unwrap();
+        assert!(head.contains("  <author><name>Jane Doe</name></author>\n"));
+        let (unauthored, _) = entries.split_once("</entry>").unwrap();
+        assert!(!unauthored.contains("<author>"));
+        assert!(entries.contains("<author><name>Ada</name></author>"));
+    }
+
+    #[test]
+    fn full_content_adds_content_element() {
+        let page = make_page("blog/first", "First");
+        let items = vec![FeedItem {
+            page: &page,
+            date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+            url: "https://example.com/blog/first/".to_string(),
+            content: Some("<p>Body</p>".to_string()),
+        }];
+        let xml = render(&meta(), &items, "https://example.com/atom.xml");
+        assert!(xml.contains("<content type=\"html\">&lt;p&gt;Body&lt;/p&gt;</content>"));
+    }
+}
@@ -0,0 +1,131 @@
+//! JSON Feed 1.1 serialization (<https://jsonfeed.org/version/1.1>).
+
+use super::{FeedItem, FeedMeta};
+use std::fmt::Write as _;
+
+pub(super) fn render(meta: &FeedMeta, items: &[FeedItem], self_url: &str) -> String {
+    let mut json = String::from("{\n");
+    json.push_str("  \"version\": \"https://jsonfeed.org/version/1.1\",\n");
+    json.push_str(&format!("  \"title\": {},\n", string(&meta.title)));
+    json.push_str(&format!(
+        "  \"home_page_url\": {},\n",
+        string(&meta.site_url)
+    ));
+    json.push_str(&format!("  \"feed_url\": {},\n", string(self_url)));
+    json.push_str("  \"items\": [");
+    for (i, item) in items.iter().enumerate() {
+        let page = item.page;
+        json.push_str(if i == 0 { "\n" } else { ",\n" });
+        json.push_str("    {\n");
+        json.push_str(&format!("      \"id\": {},\n", string(&item.url)));
+        json.push_str(&format!("      \"url\": {},\n", string(&item.url)));
+        json.push_str(&format!(
+            "      \"title\": {},\n",
+            string(&page.frontmatter.title)
+        ));
+        if let Some(author) = page.frontmatter.get_string("author") {
+            json.push_str(&format!(
+                "      \"authors\": [{{ \"name\": {} }}],\n",
+                string(&author)
+            ));
+        }
+        json.push_str(&format!(
+            "      \"content_html\": {},\n",
+            string(item.content.as_deref().unwrap_or(&page.content_html))
+        ));
+        json.push_str(&format!(
+            "      \"date_published\": {}\n",
+            string(&item.date.rfc3339())
+        ));
+        json.push_str("    }");
+    }
+    json.push_str(if items.is_empty() { "]\n" } else { "\n  ]\n" });
+    json.push_str("}\n");
+    json
+}
+
+/// `s` as a quoted JSON string.
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::{Timestamp, UtcOffset};
+    use crate::core::nav::test_support::{make_page_with_data, pod_hash};
+    use gray_matter::Pod;
+
+    fn meta() -> FeedMeta {
+        FeedMeta {
+            title: "My \"Site\"".to_string(),
+            author: "Site".to_string(),
+            site_url: "https://example.com/".to_string(),
+            updated: "2026-05-31T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_feed_and_items() {
+        let mut page = make_page_with_data(
+            "blog/first",
+            "First",
+            pod_hash(&[("author", Pod::String("Ada".to_string()))]),
+        );
+        page.content_html = "<p>Hi</p>\n".to_string();
+        let items = vec![FeedItem {
+            page: &page,
+            date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+            url: "https://example.com/blog/first/".to_string(),
+            content: None,
+        }];
+        let json = render(&meta(), &items, "https://example.com/feed.json");
+        assert_eq!(
+            json,
+            r#"{
+  "version": "https://jsonfeed.org/version/1.1",
+  "title": "My \"Site\"",
+  "home_page_url": "https://example.com/",
+  "feed_url": "https://example.com/feed.json",
+  "items": [
+    {
+      "id": "https://example.com/blog/first/",
+      "url": "https://example.com/blog/first/",
+      "title": "First",
+      "authors": [{ "name": "Ada" }],
+      "content_html": "<p>Hi</p>\n",
+      "date_published": "2026-05-31T00:00:00Z"
+    }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn empty_feed_has_empty_items() {
+        let json = render(&meta(), &[], "https://example.com/feed.json");
+        assert!(json.ends_with("  \"items\": []\n}\n"), "{json}");
+    }
+
+    #[test]
+    fn escapes_control_characters() {
+        assert_eq!(string("a\u{1}b\\"), "\"a\\u0001b\\\\\"");
+    }
+}
@@ -0,0 +1,443 @@
+//! Syndication feeds (Atom 1.0, RSS 2.0, JSON Feed 1.1) for dated pages.
+//!
+//! Every page whose frontmatter carries a parseable `date` is a feed item,
+//! except the not-found page (`404.md`).
+//! [`collect_items`] gathers and orders them once (newest first, ties by URL)
+//! and each format module only serializes that shared list, so the formats
+//! never disagree on ordering or content — or on how many items they carry,
+//! since [`FeedConfig::limit`] is applied there too.
+//!
+//! Atom and RSS carry only the page excerpt unless
+//! [`FeedConfig::full_content`] is set; then every item embeds the rendered
+//! body, with relative links and image sources made absolute so they still
+//! resolve inside a feed reader.
+
+mod atom;
+mod json;
+mod rss;
+
+use crate::core::config::SiteConfig;
+use crate::core::content::images::tag_attr;
+use crate::core::content::page::Page;
+use crate::core::content::url_strategy::is_not_found;
+use crate::core::date::{Timestamp, format_system_time};
+use crate::core::nav::{
+    absolute_href, absolute_url, is_external, normalize_segments, strip_base_path,
+};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A feed format Sherwood can write. Each lands at the output root under
+/// [`FeedFormat::file_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FeedFormat {
+    /// Atom 1.0, written to `atom.xml`. Needs [`SiteConfig::site_url`]:
+    /// without it the feed and entry ids fall back to root-relative URLs.
+    Atom,
+    /// RSS 2.0, written to `rss.xml`.
+    Rss,
+    /// JSON Feed 1.1, written to `feed.json`. Items carry the full
+    /// `content_html`.
+    Json,
+}
+
+impl FeedFormat {
+    /// Output file name, relative to the output directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Rss => "rss.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+
+    /// Parse a comma-separated format list — `"atom"`, `"rss,json"`, or
+    /// `"both"` (RSS and Atom). Duplicates are dropped.
+    pub fn parse_list(raw: &str) -> Result<Vec<FeedFormat>, String> {
+        let mut formats = Vec::new();
+        for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let parsed = match name {
+                "both" => vec![FeedFormat::Rss, FeedFormat::Atom],
+                other => vec![other.parse()?],
+            };
+            for format in parsed {
+                if !formats.contains(&format) {
+                    formats.push(format);
+                }
+            }
+        }
+        Ok(formats)
+    }
+}
+
+impl FromStr for FeedFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "atom" => Ok(FeedFormat::Atom),
+            "rss" => Ok(FeedFormat::Rss),
+            "json" => Ok(FeedFormat::Json),
+            other => Err(format!(
+                "unknown feed format `{other}` (expected rss, atom or json)"
+            )),
+        }
+    }
+}
+
+/// Feed settings. Feeds are off until at least one format is selected.
+///
+/// ```
+/// use sherwood::{FeedConfig, FeedFormat, SiteConfig};
+/// let config = SiteConfig::new()
+///     .with_site_url("https://example.com")
+///     .with_feed(FeedConfig::new().with_formats([FeedFormat::Atom, FeedFormat::Rss]));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct FeedConfig {
+    /// Formats to write. Empty (the default) disables feeds.
+    pub formats: Vec<FeedFormat>,
+    /// Feed title. Defaults to the root index page's title.
+    pub title: Option<String>,
+    /// Feed author, named in Atom's feed-level `<author>` (pages with an
+    /// `author` frontmatter field also name their own). Defaults to the
+    /// footer owner, then the feed title.
+    pub author: Option<String>,
+    /// Embed each page's full rendered body in its item instead of just the
+    /// excerpt. Off by default.
+    pub full_content: bool,
+    /// Most items per feed: the newest `limit` dated pages. `None` (the
+    /// default) includes them all.
+    pub limit: Option<usize>,
+}
+
+impl FeedConfig {
+    /// Feeds disabled; add formats with [`with_formats`](Self::with_formats).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the formats to write.
+    pub fn with_formats(mut self, formats: impl IntoIterator<Item = FeedFormat>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// Set the feed title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the feed author.
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Embed full page bodies rather than excerpts.
+    pub fn with_full_content(mut self, full_content: bool) -> Self {
+        self.full_content = full_content;
+        self
+    }
+
+    /// Cap each feed at the `limit` most recent items.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// One dated page, ready for any feed format.
+pub(crate) struct FeedItem<'a> {
+    pub(crate) page: &'a Page,
+    pub(crate) date: Timestamp,
+    /// Absolute URL (site URL + base path + page URL).
+    pub(crate) url: String,
+    /// The page body with absolute links, when
+    /// [`FeedConfig::full_content`] is set.
+    pub(crate) content: Option<String>,
+}
+
+/// Channel-level data shared by every format.
+pub(crate) struct FeedMeta {
+    pub(crate) title: String,
+    /// Feed-level author name.
+    pub(crate) author: String,
+    /// Absolute URL of the site root.
+    pub(crate) site_url: String,
+    /// Newest item date (RFC 3339), or the build time for an empty feed.
+    pub(crate) updated: String,
+}
+
+/// Dated pages, newest first by instant (dates without an offset are in
+/// [`SiteConfig::timezone`]); equal times order by URL so output is stable.
+/// At most [`FeedConfig::limit`] items are kept.
+pub(crate) fn collect_items<'a>(pages: &'a [Page], config: &SiteConfig) -> Vec<FeedItem<'a>> {
+    let mut items: Vec<FeedItem<'a>> = pages
+        .iter()
+        .filter(|page| !is_not_found(page))
+        .filter_map(|page| {
+            let raw = page.frontmatter.get_string("date")?;
+            let date = Timestamp::parse(&raw, config.timezone)?;
+            Some(FeedItem {
+                page,
+                date,
+                url: absolute_href(config, &page.url),
+                content: config
+                    .feed
+                    .full_content
+                    .then(|| absolutize(&page.content_html, &page.url, config)),
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.date
+            .epoch_seconds()
+            .cmp(&a.date.epoch_seconds())
+            .then_with(|| a.url.cmp(&b.url))
+    });
+    if let Some(limit) = config.feed.limit {
+        items.truncate(limit);
+    }
+    items
+}
+
+/// Write every configured feed format into the output directory. Returns the
+/// paths written and their sizes. With [`SiteConfig::dry_run`] nothing is
+/// written.
+pub(crate) fn write_feeds(
+    pages: &[Page],
+    config: &SiteConfig,
+) -> Result<Vec<(std::path::PathBuf, u64)>, std::io::Error> {
+    let mut written = Vec::new();
+    if config.feed.formats.is_empty() {
+        return Ok(written);
+    }
+    let items = collect_items(pages, config);
+    let title = config
+        .feed
+        .title
+        .clone()
+        .or_else(|| {
+            pages
+                .iter()
+                .find(|p| p.url == "/")
+                .map(|p| p.frontmatter.title.clone())
+        })
+        .unwrap_or_else(|| "Feed".to_string());
+    let meta = FeedMeta {
+        author: config
+            .feed
+            .author
+            .clone()
+            .or_else(|| config.footer.owner.clone())
+            .unwrap_or_else(|| title.clone()),
+        title,
+        site_url: absolute_href(config, "/"),
+        updated: items
+            .first()
+            .map(|i| i.date.rfc3339())
+            .unwrap_or_else(|| format_system_time(std::time::SystemTime::now())),
+    };
+    for format in &config.feed.formats {
+        let self_url = absolute_url(config, &format!("/{}", format.file_name()));
+        let body = match format {
+            FeedFormat::Atom => atom::render(&meta, &items, &self_url),
+            FeedFormat::Rss => rss::render(&meta, &items, &self_url),
+            FeedFormat::Json => json::render(&meta, &items, &self_url),
+        };
+        let dest = config.output_dir.join(format.file_name());
+        if !config.dry_run {
+            write_file(&dest, &body)?;
+        }
+        written.push((dest, body.len() as u64));
+    }
+    Ok(written)
+}
+
+/// `html` with every relative `href` and `src` made absolute. A feed reader
+/// has no notion of where the page lives, so `/about/` or `cat.png` would
+/// otherwise point nowhere.
+fn absolutize(html: &str, page_url: &str, config: &SiteConfig) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(at) = rest.find('<') {
+        let after = &rest[at + 1..];
+        let end = after.find('>').unwrap_or(after.len());
+        let mut tag = after[..end].to_string();
+        for name in ["href", "src"] {
+            let Some((start, len, absolute)) = tag_attr(&tag, name).and_then(|value| {
+                let absolute = absolute_link(value, page_url, config)?;
+                // `value` borrows from `tag`, so its address gives its offset.
+                let start = value.as_ptr() as usize - tag.as_ptr() as usize;
+                Some((start, value.len(), absolute))
+            }) else {
+                continue;
+            };
+            tag.replace_range(start..start + len, &absolute);
+        }
+        out.push_str(&rest[..=at]);
+        out.push_str(&tag);
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The absolute form of a link found on the page at `page_url`, or `None`
+/// when it already is absolute (or names another scheme, like `mailto:`).
+/// Root-relative links get the site URL, and the base path unless they
+/// already start with it; fragments and page-relative links resolve against
+/// the page first.
+fn absolute_link(link: &str, page_url: &str, config: &SiteConfig) -> Option<String> {
+    let (path, suffix) = link.split_at(link.find(['?', '#']).unwrap_or(link.len()));
+    if link.is_empty() || is_external(path) {
+        return None;
+    }
+    let joined = match path.strip_prefix('/') {
+        Some(_) => strip_base_path(path, &config.base_path).to_string(),
+        None => {
+            let dir = &page_url[..page_url.rfind('/').map_or(0, |i| i + 1)];
+            format!("{dir}{path}")
+        }
+    };
+    let segments = normalize_segments(&joined);
+    let mut url = format!("/{}", segments.join("/"));
+    if !segments.is_empty() && matches!(path.rsplit('/').next(), Some("" | "." | "..")) {
+        url.push('/');
+    }
+    Some(format!("{}{suffix}", absolute_url(config, &url)))
+}
+
+fn write_file(dest: &Path, contents: &str) -> Result<(), std::io::Error> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(dest, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::{
+        make_page, make_page_with_data, pod_hash, test_config, test_config_with_base,
+    };
+    use gray_matter::Pod;
+
+    fn dated(rel: &str, title: &str, date: &str) -> Page {
+        make_page_with_data(
+            rel,
+            title,
+            pod_hash(&[("date", Pod::String(date.to_string()))]),
+        )
+    }
+
+    #[test]
+    fn parse_list_accepts_both_and_dedups() {
+        assert_eq!(
+            FeedFormat::parse_list("both").unwrap(),
+            vec![FeedFormat::Rss, FeedFormat::Atom]
+        );
+        assert_eq!(
+            FeedFormat::parse_list("atom, atom,rss").unwrap(),
+            vec![FeedFormat::Atom, FeedFormat::Rss]
+        );
+        assert_eq!(
+            FeedFormat::parse_list("json,rss").unwrap(),
+            vec![FeedFormat::Json, FeedFormat::Rss]
+        );
+        assert!(FeedFormat::parse_list("gopher").is_err());
+    }
+
+    #[test]
+    fn collect_items_skips_undated_and_sorts_newest_first() {
+        let config = test_config().with_site_url("https://example.com");
+        let pages = vec![
+            make_page("index", "Home"),
+            dated("blog/old", "Old", "2026-01-01"),
+            dated("blog/new", "New", "2026-05-31"),
+            dated("blog/bad", "Bad", "not a date"),
+            dated("404", "Not Found", "2026-06-01"),
+        ];
+        let items = collect_items(&pages, &config);
+        let titles: Vec<_> = items
+            .iter()
+            .map(|i| i.page.frontmatter.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["New", "Old"]);
+        assert_eq!(items[0].url, "https://example.com/blog/new/");
+        assert!(items[0].content.is_none());
+    }
+
+    #[test]
+    fn collect_items_orders_datetimes_by_instant() {
+        let config = test_config().with_timezone("+02:00".parse().unwrap());
+        let pages = vec![
+            dated("blog/morning", "Morning", "2026-05-31T09:00"),
+            dated("blog/utc", "UTC", "2026-05-31T08:00:00Z"),
+            dated("blog/midnight", "Midnight", "2026-05-31"),
+        ];
+        let items = collect_items(&pages, &config);
+        let stamps: Vec<_> = items.iter().map(|i| i.date.rfc3339()).collect();
+        assert_eq!(
+            stamps,
+            [
+                "2026-05-31T08:00:00Z",
+                "2026-05-31T09:00:00+02:00",
+                "2026-05-31T00:00:00+02:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn collect_items_keeps_the_newest_up_to_the_limit() {
+        let config = test_config().with_feed(FeedConfig::new().with_limit(2));
+        let pages = vec![
+            dated("blog/a", "A", "2026-01-01"),
+            dated("blog/c", "C", "2026-03-01"),
+            dated("blog/b", "B", "2026-02-01"),
+        ];
+        let titles: Vec<_> = collect_items(&pages, &config)
+            .iter()
+            .map(|i| i.page.frontmatter.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["C", "B"]);
+    }
+
+    #[test]
+    fn full_content_absolutizes_links_and_images() {
+        let config = test_config_with_base("/docs")
+            .with_site_url("https://example.com")
+            .with_feed(FeedConfig::new().with_full_content(true));
+        let mut page = dated("blog/post", "Post", "2026-05-31");
+        page.content_html = concat!(
+            "<p><a href=\"/about/\">About</a> <a href=\"/docs/about/\">Docs</a> ",
+            "<a href=\"/docs\">Root</a> <a href=\"/docsite/\">Other site</a> ",
+            "<a href=\"../other/#x\">Other</a> ",
+            "<a href=\"#notes\">Notes</a> <a href=\"https://elsewhere.org/\">Out</a> ",
+            "<a href=\"mailto:me@example.com\">Mail</a></p>\n",
+            "<p><img src=\"cat.png?v=2\" alt=\"Cat\"> <img src='/img/dog.png'></p>\n",
+        )
+        .to_string();
+        let pages = vec![page];
+        let items = collect_items(&pages, &config);
+        assert_eq!(
+            items[0].content.as_deref().unwrap(),
+            concat!(
+                "<p><a href=\"https://example.com/docs/about/\">About</a> ",
+                "<a href=\"https://example.com/docs/about/\">Docs</a> ",
+                "<a href=\"https://example.com/docs/\">Root</a> ",
+                "<a href=\"https://example.com/docs/docsite/\">Other site</a> ",
+                "<a href=\"https://example.com/docs/blog/other/#x\">Other</a> ",
+                "<a href=\"https://example.com/docs/blog/post/#notes\">Notes</a> ",
+                "<a href=\"https://elsewhere.org/\">Out</a> ",
+                "<a href=\"mailto:me@example.com\">Mail</a></p>\n",
+                "<p><img src=\"https://example.com/docs/blog/post/cat.png?v=2\" alt=\"Cat\"> ",
+                "<img src='https://example.com/docs/img/dog.png'></p>\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! RSS 2.0 serialization.
+
+use super::{FeedItem, FeedMeta};
+use crate::core::content::parser::escape_html as escape;
+
+pub(super) fn render(meta: &FeedMeta, items: &[FeedItem], self_url: &str) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str(concat!(
+        "<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" ",
+        "xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">\n",
+    ));
+    xml.push_str("  <channel>\n");
+    xml.push_str(&format!("    <title>{}</title>\n", escape(&meta.title)));
+    xml.push_str(&format!("    <link>{}</link>\n", escape(&meta.site_url)));
+    xml.push_str(&format!(
+        "    <description>{}</description>\n",
+        escape(&meta.title)
+    ));
+    xml.push_str(&format!(
+        "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape(self_url)
+    ));
+    if let Some(newest) = items.first() {
+        xml.push_str(&format!(
+            "    <lastBuildDate>{}</lastBuildDate>\n",
+            newest.date.rfc2822()
+        ));
+    }
+    for item in items {
+        let page = item.page;
+        xml.push_str("    <item>\n");
+        xml.push_str(&format!(
+            "      <title>{}</title>\n",
+            escape(&page.frontmatter.title)
+        ));
+        xml.push_str(&format!("      <link>{}</link>\n", escape(&item.url)));
+        xml.push_str(&format!(
+            "      <guid isPermaLink=\"true\">{}</guid>\n",
+            escape(&item.url)
+        ));
+        xml.push_str(&format!(
+            "      <pubDate>{}</pubDate>\n",
+            item.date.rfc2822()
+        ));
+        if let Some(excerpt) = &page.excerpt_html {
+            xml.push_str(&format!(
+                "      <description>{}</description>\n",
+                escape(excerpt)
+            ));
+        }
+        if let Some(content) = &item.content {
+            xml.push_str(&format!(
+                "      <content:encoded>{}</content:encoded>\n",
+                escape(content)
+            ));
+        }
+        xml.push_str("    </item>\n");
+    }
+    xml.push_str("  </channel>\n");
+    xml.push_str("</rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::{Timestamp, UtcOffset};
+    use crate::core::nav::test_support::make_page;
+
+    #[test]
+    fn renders_channel_and_items() {
+        let page = make_page("blog/first", "First");
+        let items = vec![FeedItem {
+            page: &page,
+            date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+            url: "https://example.com/blog/first/".to_string(),
+            content: None,
+        }];
+        let meta = FeedMeta {
+            title: "Site".to_string(),
+            author: "Site".to_string(),
+            site_url: "https://example.com/".to_string(),
+            updated: "2026-05-31T00:00:00Z".to_string(),
+        };
+        let xml = render(&meta, &items, "https://example.com/rss.xml");
+        assert!(xml.contains("<rss version=\"2.0\""));
+        assert!(xml.contains("<link>https://example.com/blog/first/</link>"));
+        assert!(xml.contains("<pubDate>Sun, 31 May 2026 00:00:00 +0000</pubDate>"));
+        assert!(xml.contains("<lastBuildDate>Sun, 31 May 2026 00:00:00 +0000</lastBuildDate>"));
+        // No excerpt → no description element for the item.
+        assert_eq!(xml.matches("<description>").count(), 1);
+        assert!(!xml.contains("<content:encoded>"));
+    }
+
+    #[test]
+    fn full_content_adds_encoded_content() {
+        let page = make_page("blog/first", "First");
+        let items = vec![FeedItem {
+            page: &page,
+            date: Timestamp::parse("2026-05-31", UtcOffset::UTC).unwrap(),
+            url: "https://example.com/blog/first/".to_string(),
+            content: Some("<p>Body</p>".to_string()),
+        }];
+        let meta = FeedMeta {
+            title: "Site".to_string(),
+            author: "Site".to_string(),
+            site_url: "https://example.com/".to_string(),
+            updated: "2026-05-31T00:00:00Z".to_string(),
+        };
+        let xml = render(&meta, &items, "https://example.com/rss.xml");
+        assert!(xml.contains("<content:encoded>&lt;p&gt;Body&lt;/p&gt;</content:encoded>"));
+    }
+}
@@ -0,0 +1,65 @@
+//! Content-hash file names for cache busting: the stylesheet
+//! (`--fingerprint-stylesheet`) and content images
+//! ([`SiteConfig::fingerprint_images`](crate::SiteConfig::fingerprint_images)).
+//!
+//! `style.css` becomes `style.<hash>.css`, where `<hash>` is 16 lowercase hex
+//! digits of a 64-bit FNV-1a hash of the file's bytes: any edit yields a new
+//! name, so the file can be cached forever. FNV is not collision-resistant,
+//! but cache busting only needs "changes when the content changes".
+
+/// Hex digits in a fingerprint.
+pub(crate) const HASH_LEN: usize = 16;
+
+/// Insert the content hash of `bytes` before the extension of `name`:
+/// `css/main.css` → `css/main.0123456789abcdef.css`.
+pub(crate) fn fingerprinted_name(name: &str, bytes: &[u8]) -> String {
+    let hash = format!("{:0width$x}", fnv1a(bytes), width = HASH_LEN);
+    let (dir, file) = match name.rsplit_once('/') {
+        Some((dir, file)) => (format!("{dir}/"), file),
+        None => (String::new(), name),
+    };
+    match file.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{dir}{stem}.{hash}.{ext}"),
+        _ => format!("{dir}{file}.{hash}"),
+    }
+}
+
+/// 64-bit FNV-1a.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_known_vectors() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn name_changes_with_content() {
+        let a = fingerprinted_name("style.css", b"body{}");
+        let b = fingerprinted_name("style.css", b"body{color:red}");
+        assert_ne!(a, b);
+        assert_eq!(a, fingerprinted_name("style.css", b"body{}"));
+        assert!(a.starts_with("style.") && a.ends_with(".css"), "{a}");
+        assert_eq!(a.len(), "style..css".len() + HASH_LEN);
+    }
+
+    #[test]
+    fn name_keeps_directory() {
+        let name = fingerprinted_name("css/main.css", b"x");
+        assert!(
+            name.starts_with("css/main.") && name.ends_with(".css"),
+            "{name}"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! The site footer: a copyright line whose year range runs to the year of
+//! the build, and an optional license link, configured once with
+//! [`SiteConfig::with_footer`](crate::SiteConfig::with_footer) and exposed
+//! pre-rendered as [`PageContext::footer`](crate::PageContext::footer).
+
+use crate::core::config::SiteConfig;
+use crate::core::content::parser::escape_html;
+use crate::core::date::Date;
+use crate::core::nav::resolve;
+
+/// Footer settings. Nothing is rendered until an owner or a license is set.
+///
+/// ```
+/// use sherwood::{FooterConfig, SiteConfig};
+/// let config = SiteConfig::new().with_footer(
+///     FooterConfig::new()
+///         .with_owner("Jane Doe")
+///         .with_since(2019)
+///         .with_license("CC BY 4.0")
+///         .with_license_url("https://creativecommons.org/licenses/by/4.0/"),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FooterConfig {
+    /// Copyright holder, e.g. `Jane Doe`. No copyright line without one.
+    pub owner: Option<String>,
+    /// First year of the copyright. A year before the build's gives a range
+    /// (`2019–2026`); otherwise only the current year is shown.
+    pub since: Option<i64>,
+    /// License name, e.g. `CC BY 4.0`.
+    pub license: Option<String>,
+    /// Where the license name links to. Root-relative URLs get the base path.
+    pub license_url: Option<String>,
+}
+
+impl FooterConfig {
+    /// An empty footer; set an owner or license to render one.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the copyright holder.
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Set the first copyright year.
+    pub fn with_since(mut self, year: i64) -> Self {
+        self.since = Some(year);
+        self
+    }
+
+    /// Set the license name.
+    pub fn with_license(mut self, name: impl Into<String>) -> Self {
+        self.license = Some(name.into());
+        self
+    }
+
+    /// Link the license name to `url`.
+    pub fn with_license_url(mut self, url: impl Into<String>) -> Self {
+        self.license_url = Some(url.into());
+        self
+    }
+
+    /// The footer as HTML for a build in `year`: a `copyright` and a
+    /// `license` paragraph, each present only when configured.
+    fn to_html(&self, year: i64, base_path: &str) -> String {
+        let mut lines = Vec::new();
+        if let Some(owner) = self.owner.as_deref().filter(|o| !o.trim().is_empty()) {
+            let years = match self.since {
+                Some(since) if since < year => format!("{since}–{year}"),
+                _ => year.to_string(),
+            };
+            lines.push(format!(
+                "<p class=\"copyright\">© {years} {}</p>",
+                escape_html(owner.trim())
+            ));
+        }
+        if let Some(name) = self.license.as_deref().filter(|n| !n.trim().is_empty()) {
+            let name = escape_html(name.trim());
+            let license = match self.license_url.as_deref() {
+                Some(url) => {
+                    let href = if url.starts_with('/') && !url.starts_with("//") {
+                        resolve(url, base_path)
+                    } else {
+                        url.to_string()
+                    };
+                    format!(
+                        "<a rel=\"license\" href=\"{}\">{name}</a>",
+                        escape_html(&href)
+                    )
+                }
+                None => name,
+            };
+            lines.push(format!("<p class=\"license\">Licensed under {license}</p>"));
+        }
+        lines.join("\n")
+    }
+}
+
+/// The configured footer, rendered for the current year. Empty when
+/// [`SiteConfig::footer`] sets neither an owner nor a license.
+pub(crate) fn footer_html(config: &SiteConfig) -> String {
+    config.footer.to_html(Date::today().year, &config.base_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_year_range_and_license() {
+        let footer = FooterConfig::new()
+            .with_owner("Jane & Co")
+            .with_since(2019)
+            .with_license("MIT")
+            .with_license_url("/license/");
+        assert_eq!(
+            footer.to_html(2026, "/docs"),
+            "<p class=\"copyright\">© 2019–2026 Jane &amp; Co</p>\n\
+             <p class=\"license\">Licensed under <a rel=\"license\" href=\"/docs/license/\">MIT</a></p>"
+        );
+        assert_eq!(
+            footer
+                .clone()
+                .with_since(2026)
+                .to_html(2026, "")
+                .lines()
+                .next(),
+            Some("<p class=\"copyright\">© 2026 Jane &amp; Co</p>")
+        );
+    }
+
+    #[test]
+    fn empty_without_owner_or_license() {
+        assert_eq!(FooterConfig::new().with_since(2019).to_html(2026, ""), "");
+        assert_eq!(
+            FooterConfig::new().with_license("MIT").to_html(2026, ""),
+            "<p class=\"license\">Licensed under MIT</p>"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Extra `<link>` / `<meta>` / `<script>` tags injected into every page's
+//! `<head>` — favicons, `theme-color`, preconnect hints, a diagram or math
+//! renderer — configured once with
+//! [`SiteConfig::with_head`](crate::SiteConfig::with_head) instead of forking
+//! the template.
+
+use crate::core::config::SiteConfig;
+use crate::core::content::parser::escape_html;
+use crate::core::nav::resolve;
+use std::str::FromStr;
+
+/// One `<link>`, `<meta>` or external `<script>` tag, as an element name
+/// plus attributes in declaration order.
+///
+/// ```
+/// use sherwood::HeadTag;
+/// let icon = HeadTag::link([("rel", "icon"), ("href", "/favicon.ico")]);
+/// let theme: HeadTag = "meta name=theme-color content=#336699".parse().unwrap();
+/// assert!(icon.validate().is_ok() && theme.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HeadTag {
+    /// `link`, `meta` or `script`.
+    pub element: String,
+    /// Attribute name/value pairs, emitted in this order.
+    pub attrs: Vec<(String, String)>,
+}
+
+impl HeadTag {
+    pub fn new<K: Into<String>, V: Into<String>>(
+        element: impl Into<String>,
+        attrs: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self {
+            element: element.into(),
+            attrs: attrs
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+
+    /// A `<link>` tag.
+    pub fn link<K: Into<String>, V: Into<String>>(attrs: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self::new("link", attrs)
+    }
+
+    /// A `<meta>` tag.
+    pub fn meta<K: Into<String>, V: Into<String>>(attrs: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self::new("meta", attrs)
+    }
+
+    /// A `<script>` tag loading an external file.
+    pub fn script<K: Into<String>, V: Into<String>>(
+        attrs: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self::new("script", attrs)
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Check the tag is well-formed: a `link` needs `rel` and `href`; a
+    /// `meta` needs `charset`, or `content` plus one of `name`, `property`,
+    /// or `http-equiv`; a `script` needs `src` (inline scripts aren't
+    /// supported). Invalid tags are skipped when pages are rendered.
+    pub fn validate(&self) -> Result<(), String> {
+        let missing = |attrs: &str| Err(format!("<{}> tag is missing {attrs}", self.element));
+        match self.element.as_str() {
+            "link" if self.attr("rel").is_none() || self.attr("href").is_none() => {
+                missing("`rel` or `href`")
+            }
+            "link" => Ok(()),
+            "meta" if self.attr("charset").is_some() => Ok(()),
+            "meta" if self.attr("content").is_none() => missing("`content`"),
+            "meta"
+                if ["name", "property", "http-equiv"]
+                    .iter()
+                    .all(|a| self.attr(a).is_none()) =>
+            {
+                missing("`name`, `property`, or `http-equiv`")
+            }
+            "meta" => Ok(()),
+            "script" if self.attr("src").is_none() => missing("`src`"),
+            "script" => Ok(()),
+            other => Err(format!(
+                "unsupported head element `{other}` (expected link, meta or script)"
+            )),
+        }
+    }
+
+    /// Serialize to HTML. Attribute values are escaped, and a root-relative
+    /// `href` or `src` (`/favicon.ico`) is resolved against `base_path`.
+    fn to_html(&self, base_path: &str) -> String {
+        let mut html = format!("<{}", self.element);
+        for (name, value) in &self.attrs {
+            let is_url = name == "href" || name == "src";
+            let value = if is_url && value.starts_with('/') && !value.starts_with("//") {
+                resolve(value, base_path)
+            } else {
+                value.clone()
+            };
+            html.push_str(&format!(
+                " {}=\"{}\"",
+                escape_html(name),
+                escape_html(&value)
+            ));
+        }
+        html.push('>');
+        if self.element == "script" {
+            html.push_str("</script>");
+        }
+        html
+    }
+}
+
+/// Parses the CLI's `--head` format: the element name, then
+/// whitespace-separated `name=value` attributes, with double quotes around
+/// values containing spaces — `meta name=viewport content="width=device-width,
+/// initial-scale=1"`.
+impl FromStr for HeadTag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = tokenize(s)?.into_iter();
+        let element = tokens.next().ok_or("empty head tag")?;
+        let attrs = tokens
+            .map(|token| {
+                token
+                    .split_once('=')
+                    .filter(|(name, _)| !name.is_empty())
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .ok_or_else(|| format!("expected `name=value`, got `{token}`"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HeadTag { element, attrs })
+    }
+}
+
+/// Split on whitespace outside double quotes, dropping the quotes.
+fn tokenize(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in s.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(format!("unterminated quote in `{s}`"));
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// The configured head tags as HTML, one per line in declaration order.
+/// Tags failing [`HeadTag::validate`] are skipped.
+pub(crate) fn head_html(config: &SiteConfig) -> String {
+    config
+        .head
+        .iter()
+        .filter(|tag| tag.validate().is_ok())
+        .map(|tag| tag.to_html(&config.base_path))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_format_with_quotes() {
+        let tag: HeadTag = r#"meta name=viewport content="width=device-width, initial-scale=1""#
+            .parse()
+            .unwrap();
+        assert_eq!(
+            tag,
+            HeadTag::meta([
+                ("name", "viewport"),
+                ("content", "width=device-width, initial-scale=1")
+            ])
+        );
+        assert!("link rel".parse::<HeadTag>().is_err());
+        assert!("link href=\"x".parse::<HeadTag>().is_err());
+        assert!("".parse::<HeadTag>().is_err());
+    }
+
+    #[test]
+    fn validates_required_attributes() {
+        assert!(HeadTag::link([("rel", "icon")]).validate().is_err());
+        assert!(HeadTag::meta([("name", "x")]).validate().is_err());
+        assert!(HeadTag::meta([("content", "x")]).validate().is_err());
+        assert!(HeadTag::meta([("charset", "utf-8")]).validate().is_ok());
+        assert!(HeadTag::script([("src", "x.js")]).validate().is_ok());
+        assert!(HeadTag::script([("type", "module")]).validate().is_err());
+        assert!(
+            HeadTag::new("style", [("media", "print")])
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn renders_valid_tags_in_order_and_skips_invalid() {
+        let config = SiteConfig::new().with_base_path("/docs").with_head([
+            HeadTag::link([("rel", "icon"), ("href", "/favicon.ico")]),
+            HeadTag::link([("rel", "preconnect")]),
+            HeadTag::meta([("name", "theme-color"), ("content", "#336699")]),
+            HeadTag::link([("rel", "preconnect"), ("href", "https://fonts.example")]),
+            HeadTag::script([("src", "/js/mermaid.min.js"), ("defer", "")]),
+        ]);
+        assert_eq!(
+            head_html(&config),
+            "<link rel=\"icon\" href=\"/docs/favicon.ico\">\n\
+             <meta name=\"theme-color\" content=\"#336699\">\n\
+             <link rel=\"preconnect\" href=\"https://fonts.example\">\n\
+             <script src=\"/docs/js/mermaid.min.js\" defer=\"\"></script>"
+        );
+    }
+}
@@ -1,11 +1,23 @@
 //! The always-on build pipeline — no cargo features required.
 //!
 //! Groups content loading + parsing ([`content`]), navigation/context
-//! computation ([`nav`]), shared [`config`], and the [`build`] orchestrator.
+//! computation ([`nav`]), feed output ([`feed`]), single-file [`export`],
+//! extra `<head>` tags ([`head`]), the site [`footer`], [`redirect`] stubs, content-hash
+//! [`fingerprint`] names, output precompression ([`compress`]), the accessibility lint ([`a11y`]), shared [`config`] and
+//! [`date`] handling, and the [`build`] orchestrator.
 //! Public types are re-exported from the crate root (`src/lib.rs`); this module
 //! tree is the internal organisation behind that facade.
 
+pub mod a11y;
 pub mod build;
+pub mod compress;
 pub mod config;
 pub mod content;
+pub mod date;
+pub mod export;
+pub mod feed;
+pub mod fingerprint;
+pub mod footer;
+pub mod head;
 pub mod nav;
+pub mod redirect;
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use super::href_for;
 use super::is_root_index;
-use super::resolve;
+use super::link;
 
 #[derive(Debug, Clone)]
 pub struct Breadcrumb {
@@ -36,7 +36,7 @@ pub(crate) fn breadcrumbs_for(
 
     crumbs.push(Breadcrumb {
         title: home_title,
-        href: Some(resolve("/", &config.base_path)),
+        href: Some(link("/", config)),
     });
 
     let components: Vec<_> = relative.components().collect();
@@ -57,11 +57,11 @@ pub(crate) fn breadcrumbs_for(
             .iter()
             .find(|p| p.output_path == index_output)
             .map(|p| p.frontmatter.title.clone())
-            .unwrap_or_else(|| capitalize_first(&dir_name));
+            .unwrap_or_else(|| humanize(&dir_name));
 
         crumbs.push(Breadcrumb {
             title: dir_title,
-            href: Some(resolve(&href_for(&index_output, config), &config.base_path)),
+            href: Some(link(&href_for(&index_output, config), config)),
         });
     }
 
@@ -85,12 +85,21 @@ pub(crate) fn breadcrumbs_for(
     crumbs
 }
 
-fn capitalize_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-    }
+/// A directory name as a title, for sections without an index page:
+/// `blog-posts` → `Blog Posts`.
+fn humanize(dir_name: &str) -> String {
+    dir_name
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 #[cfg(test)]
@@ -134,6 +143,17 @@ mod tests {
         assert!(ctx.breadcrumbs[2].href.is_none());
     }
 
+    #[test]
+    fn section_without_index_is_named_after_its_directory() {
+        let config = test_config();
+        let pages = vec![
+            make_page("blog-posts/first", "First"),
+            make_page("index", "Home"),
+        ];
+        let ctx = compute_context(&pages[0], &pages, &config);
+        assert_eq!(ctx.breadcrumbs[1].title, "Blog Posts");
+    }
+
     #[test]
     fn breadcrumbs_depth_3() {
         let config = test_config();
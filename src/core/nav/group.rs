@@ -0,0 +1,153 @@
+use crate::core::content::page::Page;
+use crate::core::date::Date;
+
+/// Section index frontmatter field that groups its listing for an archive:
+/// `year` or `month`. Absent (or anything else) lists in one group.
+pub(crate) const GROUP_BY: &str = "group_by";
+
+/// Label of the group holding pages without a recognizable date.
+pub const UNDATED_GROUP: &str = "Undated";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GroupKey {
+    Year,
+    Month,
+}
+
+/// One heading's worth of an archive listing: `2024` or `March 2024` and
+/// the pages dated in it.
+#[derive(Debug, Clone)]
+pub struct PageGroup<'a> {
+    /// `2024` by year, `March 2024` by month, [`UNDATED_GROUP`] for pages
+    /// without a date. Empty when the section doesn't group.
+    pub label: String,
+    /// The group's pages, in the section's listing order.
+    pub pages: Vec<&'a Page>,
+}
+
+/// Split an already sorted listing into groups per `index`'s `group_by`.
+/// Groups come in the order their first page appears, so a newest-first
+/// listing gives newest-first years; undated pages share one group.
+pub(crate) fn group_pages<'a>(index: Option<&Page>, pages: Vec<&'a Page>) -> Vec<PageGroup<'a>> {
+    let key = index
+        .and_then(|p| p.frontmatter.get_string(GROUP_BY))
+        .and_then(|by| match by.trim() {
+            "year" => Some(GroupKey::Year),
+            "month" => Some(GroupKey::Month),
+            _ => None,
+        });
+    let Some(key) = key else {
+        return if pages.is_empty() {
+            Vec::new()
+        } else {
+            vec![PageGroup {
+                label: String::new(),
+                pages,
+            }]
+        };
+    };
+    let mut groups: Vec<PageGroup<'a>> = Vec::new();
+    for page in pages {
+        let label = label_for(page, key);
+        match groups.iter_mut().find(|g| g.label == label) {
+            Some(group) => group.pages.push(page),
+            None => groups.push(PageGroup {
+                label,
+                pages: vec![page],
+            }),
+        }
+    }
+    groups
+}
+
+fn label_for(page: &Page, key: GroupKey) -> String {
+    let date = page
+        .date
+        .as_ref()
+        .and_then(|d| d.iso.as_deref())
+        .and_then(Date::parse);
+    match (date, key) {
+        (Some(date), GroupKey::Year) => date.format("%Y"),
+        (Some(date), GroupKey::Month) => date.format("%B %Y"),
+        (None, _) => UNDATED_GROUP.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::PageDate;
+    use crate::core::nav::test_support::{make_page, make_page_with_data, pod_hash};
+    use gray_matter::Pod;
+
+    fn post(rel: &str, date: Option<&str>) -> Page {
+        let mut page = make_page(rel, rel);
+        page.date = date.map(|d| PageDate {
+            iso: Some(d.to_string()),
+            display: d.to_string(),
+        });
+        page
+    }
+
+    fn index(group_by: &str) -> Page {
+        make_page_with_data(
+            "blog/index",
+            "Blog",
+            pod_hash(&[(GROUP_BY, Pod::String(group_by.to_string()))]),
+        )
+    }
+
+    fn labels(groups: &[PageGroup]) -> Vec<(String, usize)> {
+        groups
+            .iter()
+            .map(|g| (g.label.clone(), g.pages.len()))
+            .collect()
+    }
+
+    #[test]
+    fn groups_by_year_in_listing_order() {
+        let posts = [
+            post("blog/c", Some("2024-03-01")),
+            post("blog/b", Some("2024-01-01")),
+            post("blog/a", Some("2023-12-31")),
+            post("blog/x", None),
+        ];
+        let groups = group_pages(Some(&index("year")), posts.iter().collect());
+        assert_eq!(
+            labels(&groups),
+            [
+                ("2024".to_string(), 2),
+                ("2023".to_string(), 1),
+                (UNDATED_GROUP.to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_by_month() {
+        let posts = [
+            post("blog/b", Some("2024-03-20")),
+            post("blog/a", Some("2024-03-01")),
+            post("blog/c", Some("2024-02-01")),
+        ];
+        let groups = group_pages(Some(&index("month")), posts.iter().collect());
+        assert_eq!(
+            labels(&groups),
+            [
+                ("March 2024".to_string(), 2),
+                ("February 2024".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn without_group_by_lists_one_group() {
+        let posts = [post("blog/a", Some("2024-03-01")), post("blog/b", None)];
+        let groups = group_pages(
+            Some(&make_page("blog/index", "Blog")),
+            posts.iter().collect(),
+        );
+        assert_eq!(labels(&groups), [(String::new(), 2)]);
+        assert!(group_pages(None, Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::core::config::SiteConfig;
+use crate::core::content::page::Page;
+use std::str::FromStr;
+
+use super::{NavItem, link};
+
+/// A hand-curated site navigation entry, configured with
+/// [`SiteConfig::with_menu`]. Unlike the automatic `nav`, the menu lists
+/// exactly these links, in [`weight`](Self::weight) order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MenuItem {
+    /// Link text.
+    pub name: String,
+    /// Link target. Root-relative URLs (`/blog/`) are canonical and get the
+    /// base path; anything else (`https://…`, `#top`) is used verbatim.
+    pub url: String,
+    /// Sort key, ascending. Items with equal weight (the default is `0`)
+    /// keep their declaration order.
+    pub weight: i64,
+}
+
+impl MenuItem {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            weight: 0,
+        }
+    }
+
+    /// Set the sort weight; lighter items come first.
+    pub fn with_weight(mut self, weight: i64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Parses `name=url`, the CLI's `--menu` format.
+impl FromStr for MenuItem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, url) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected `name=url`, got `{s}`"))?;
+        let (name, url) = (name.trim(), url.trim());
+        if name.is_empty() || url.is_empty() {
+            return Err(format!("expected non-empty name and url, got `{s}`"));
+        }
+        Ok(MenuItem::new(name, url))
+    }
+}
+
+/// The configured menu as nav items for `page`: ordered by weight then
+/// declaration order, hrefs resolved against the base path (and shaped by
+/// [`SiteConfig::trailing_slash`] when it isn't `auto`), and the item
+/// whose URL is the page's own marked current.
+pub(crate) fn menu_for(page: &Page, config: &SiteConfig) -> Vec<NavItem> {
+    let mut items: Vec<&MenuItem> = config.menu.iter().collect();
+    // Stable sort: equal weights keep declaration order.
+    items.sort_by_key(|item| item.weight);
+    items
+        .into_iter()
+        .map(|item| {
+            let href = if item.url.starts_with('/') && !item.url.starts_with("//") {
+                link(&item.url, config)
+            } else {
+                item.url.clone()
+            };
+            NavItem {
+                title: item.name.clone(),
+                href,
+                is_current: item.url == page.url,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::{make_page, test_config, test_config_with_base};
+
+    #[test]
+    fn orders_by_weight_then_declaration() {
+        let config = test_config().with_menu([
+            MenuItem::new("Last", "/last/").with_weight(10),
+            MenuItem::new("A", "/a/"),
+            MenuItem::new("First", "/first/").with_weight(-1),
+            MenuItem::new("B", "/b/"),
+        ]);
+        let page = make_page("index", "Home");
+        let titles: Vec<_> = menu_for(&page, &config)
+            .into_iter()
+            .map(|i| i.title)
+            .collect();
+        assert_eq!(titles, vec!["First", "A", "B", "Last"]);
+    }
+
+    #[test]
+    fn resolves_canonical_urls_only() {
+        let config = test_config_with_base("/docs").with_menu([
+            MenuItem::new("Blog", "/blog/"),
+            MenuItem::new("GitHub", "https://github.com/thombruce/sherwood"),
+        ]);
+        let page = make_page("index", "Home");
+        let hrefs: Vec<_> = menu_for(&page, &config)
+            .into_iter()
+            .map(|i| i.href)
+            .collect();
+        assert_eq!(
+            hrefs,
+            vec!["/docs/blog/", "https://github.com/thombruce/sherwood"]
+        );
+    }
+
+    #[test]
+    fn marks_current_page() {
+        let config = test_config().with_menu([
+            MenuItem::new("Home", "/"),
+            MenuItem::new("About", "/about/"),
+        ]);
+        let page = make_page("about", "About");
+        let current: Vec<_> = menu_for(&page, &config)
+            .into_iter()
+            .map(|i| i.is_current)
+            .collect();
+        assert_eq!(current, vec![false, true]);
+    }
+
+    #[test]
+    fn parses_cli_format() {
+        let item: MenuItem = "Blog=/blog/".parse().unwrap();
+        assert_eq!(item, MenuItem::new("Blog", "/blog/"));
+        assert!("Blog".parse::<MenuItem>().is_err());
+        assert!("=/blog/".parse::<MenuItem>().is_err());
+    }
+}
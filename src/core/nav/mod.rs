@@ -1,17 +1,46 @@
 use crate::core::config::SiteConfig;
 use crate::core::content::page::Page;
+use crate::core::content::url_strategy::is_not_found;
+use crate::core::footer::footer_html;
+use crate::core::head::head_html;
 use std::path::Path;
 
 mod breadcrumb;
+mod group;
+mod menu;
+mod related;
+mod series;
+mod sort;
+mod translations;
 mod url;
 
 #[cfg(test)]
 pub(crate) mod test_support;
 
 pub use breadcrumb::Breadcrumb;
-pub(crate) use url::{href_for, resolve, section_of};
+pub use group::{PageGroup, UNDATED_GROUP};
+pub use menu::MenuItem;
+pub use related::DEFAULT_RELATED_LIMIT;
+pub use series::Series;
+pub(crate) use series::series_landing_pages;
+pub use sort::ListSort;
+pub use translations::Translation;
+pub use url::TrailingSlash;
+pub(crate) use url::{
+    absolute_href, absolute_url, href_for, is_external, link, normalize_segments, resolve,
+    section_of, strip_base_path,
+};
 
 use breadcrumb::breadcrumbs_for;
+use group::group_pages;
+use menu::menu_for;
+use related::related_for;
+use series::series_for;
+use sort::SectionSort;
+use translations::translations_for;
+
+/// Frontmatter field listing a page's extra stylesheets.
+const STYLESHEETS: &str = "stylesheets";
 
 #[derive(Debug, Clone)]
 pub struct NavItem {
@@ -23,6 +52,11 @@ pub struct NavItem {
 #[derive(Debug, Clone)]
 pub struct PageContext<'a> {
     pub nav: Vec<NavItem>,
+    /// The hand-curated menu ([`SiteConfig::menu`]) as nav items, ordered by
+    /// weight and already resolved against the base path. Empty unless a
+    /// menu is configured — templates typically prefer it over `nav` when
+    /// present.
+    pub menu: Vec<NavItem>,
     pub breadcrumbs: Vec<Breadcrumb>,
     pub prev: Option<NavItem>,
     pub next: Option<NavItem>,
@@ -33,6 +67,50 @@ pub struct PageContext<'a> {
     ///
     /// [`resolve`]: PageContext::resolve
     pub base_path: String,
+    /// [`SiteConfig::trailing_slash`]: whether page hrefs end with `/`.
+    /// Generated hrefs already follow it, and so does [`resolve`].
+    ///
+    /// [`resolve`]: PageContext::resolve
+    pub trailing_slash: TrailingSlash,
+    /// Href of the site stylesheet ([`SiteConfig::stylesheet`]), already
+    /// resolved against the base path — e.g. `/sherwood/style.css`.
+    pub stylesheet: String,
+    /// Href of the print stylesheet ([`SiteConfig::print_stylesheet`]),
+    /// resolved like [`stylesheet`](Self::stylesheet) — link it with
+    /// `media="print"`. `None` when no print stylesheet is configured.
+    pub print_stylesheet: Option<String>,
+    /// Extra stylesheets for this page only, from its `stylesheets`
+    /// frontmatter list (or a single string), linked after
+    /// [`stylesheet`](Self::stylesheet). Names are relative to the output
+    /// root like [`SiteConfig::stylesheet`] and resolved against the base
+    /// path; full URLs pass through. Empty for most pages.
+    pub stylesheets: Vec<String>,
+    /// The page's language for `<html lang>`: its own
+    /// [`Page::lang`](crate::Page::lang) when it has one, else the site's
+    /// [`SiteConfig::language`].
+    pub lang: String,
+    /// Language versions of this page (itself included), linked by a shared
+    /// `translation_key` frontmatter field and sorted by language — emit
+    /// them as `<link rel="alternate" hreflang>` tags. Empty for untranslated
+    /// pages.
+    pub translations: Vec<Translation>,
+    /// The multi-part series this page belongs to (its `series` frontmatter
+    /// field), with its position and prev/next-in-series links. `None` for
+    /// pages outside a series.
+    pub series: Option<Series>,
+    /// Other pages sharing the most `tags` frontmatter values with this one,
+    /// newest first among equals, up to [`SiteConfig::related_limit`] — for
+    /// a "Related posts" list. Empty for untagged pages.
+    pub related: Vec<NavItem>,
+    /// The configured head tags ([`SiteConfig::head`]) as ready-to-emit HTML,
+    /// one tag per line; empty when none are set. Output it unescaped just
+    /// before `</head>`.
+    pub head: String,
+    /// The configured footer ([`SiteConfig::footer`]) as ready-to-emit HTML:
+    /// a `<p class="copyright">` line running to the build year and a
+    /// `<p class="license">` line, each only when set. Empty when neither
+    /// is. Output it unescaped.
+    pub footer: String,
     /// All pages in the site, in build order (root index first, then by
     /// output path). Templates can iterate, filter, and sort this to build
     /// indexes, archives, tag listings, etc.
@@ -44,22 +122,56 @@ impl<'a> PageContext<'a> {
     /// indexes — e.g. a `/blog/index.html` page can call
     /// `ctx.pages_under("/blog/")` to list every post under `blog/`.
     /// The current page is included; filter it out yourself if undesired.
+    /// The not-found page (`404.md`) never is.
+    ///
+    /// Pages come in build order, unless the section index at `url_prefix`
+    /// sets `sort_by` (`date` or `title`) and optionally `sort_order` (`asc`
+    /// or `desc`; dates default to newest first) in its frontmatter.
     ///
     /// Matches against canonical, un-prefixed `page.url`, so pass canonical
     /// prefixes (`"/blog/"`) regardless of any base path.
     pub fn pages_under(&self, url_prefix: &str) -> Vec<&'a Page> {
-        self.pages
+        let mut pages: Vec<&'a Page> = self
+            .pages
             .iter()
-            .filter(|p| p.url.starts_with(url_prefix))
-            .collect()
+            .filter(|p| p.url.starts_with(url_prefix) && !is_not_found(p))
+            .collect();
+        if let Some(sort) = section_sort(url_prefix, self.pages) {
+            sort.list(&mut pages);
+        }
+        pages
+    }
+
+    /// How [`pages_under`](Self::pages_under) orders the section at
+    /// `url_prefix`: its index's `sort_by` / `sort_order`, or `None` when the
+    /// listing is in build order. Lets a list page caption itself
+    /// ([`ListSort::description`], "Sorted by date, newest first") or mark
+    /// the active sort control.
+    pub fn sort_under(&self, url_prefix: &str) -> Option<ListSort> {
+        section_sort(url_prefix, self.pages).map(|sort| sort.describe())
+    }
+
+    /// [`pages_under`](Self::pages_under) without the section index itself,
+    /// split into groups for an archive page. The index picks the grouping with
+    /// `group_by: year` (labels like `2024`) or `group_by: month`
+    /// (`March 2024`); pages without a date share an [`UNDATED_GROUP`].
+    /// Groups follow the listing order, so pair it with `sort_by: date`.
+    /// Without `group_by` every page lands in a single unlabeled group.
+    pub fn pages_grouped_under(&self, url_prefix: &str) -> Vec<PageGroup<'a>> {
+        let index = self.pages.iter().find(|p| p.url == url_prefix);
+        let mut pages = self.pages_under(url_prefix);
+        pages.retain(|p| p.url != url_prefix);
+        group_pages(index, pages)
     }
 
     /// Resolve a canonical (root-relative) URL against the site's base path —
     /// `ctx.resolve("/blog/")` is `/sherwood/blog/` under a `/sherwood` base,
-    /// or `/blog/` at the root. Use it for hrefs built from `page.url` or
+    /// or `/blog/` at the root, and `/blog` with
+    /// [`TrailingSlash::Never`]. Use it for hrefs built from `page.url` or
     /// `pages_under`, and for static assets (`ctx.resolve("/style.css")`).
     pub fn resolve(&self, canonical: &str) -> String {
-        resolve(canonical, &self.base_path)
+        self.trailing_slash
+            .apply(&resolve(canonical, &self.base_path))
     }
 }
 
@@ -73,41 +185,85 @@ pub fn compute_context<'a>(
     let nav = all_pages
         .iter()
         .filter(|p| include_in_nav(p, config))
-        .map(|p| nav_item_for(p, p.output_path == page.output_path, base))
+        .map(|p| nav_item_for(p, p.output_path == page.output_path, config))
         .collect();
 
     // Prev/next chain within the page's section (same URL parent, in build
-    // order), so a blog post's neighbours are other posts, not whatever page
-    // sorts adjacent site-wide.
+    // order or the section index's `sort_by`), so a blog post's neighbours
+    // are other posts, not whatever page sorts adjacent site-wide.
     let section = section_of(&page.url);
-    let siblings: Vec<&Page> = all_pages
+    let mut siblings: Vec<&Page> = all_pages
         .iter()
-        .filter(|p| section_of(&p.url) == section)
+        .filter(|p| section_of(&p.url) == section && !is_not_found(p))
         .collect();
+    if let Some(sort) = section_sort(section, all_pages) {
+        sort.read(&mut siblings);
+    }
     let idx = siblings
         .iter()
         .position(|p| p.output_path == page.output_path);
 
     let prev = idx
         .filter(|&i| i > 0)
-        .map(|i| nav_item_for(siblings[i - 1], false, base));
+        .map(|i| nav_item_for(siblings[i - 1], false, config));
 
     let next = idx
         .filter(|&i| i + 1 < siblings.len())
-        .map(|i| nav_item_for(siblings[i + 1], false, base));
+        .map(|i| nav_item_for(siblings[i + 1], false, config));
 
     let breadcrumbs = breadcrumbs_for(page, all_pages, config);
 
     PageContext {
         nav,
+        menu: menu_for(page, config),
         breadcrumbs,
         prev,
         next,
         base_path: config.base_path.clone(),
+        trailing_slash: config.trailing_slash,
+        stylesheet: resolve(&format!("/{}", config.stylesheet), base),
+        print_stylesheet: config
+            .print_stylesheet
+            .as_ref()
+            .map(|name| resolve(&format!("/{name}"), base)),
+        stylesheets: stylesheets_for(page, base),
+        lang: page.lang.clone().unwrap_or_else(|| config.language.clone()),
+        translations: translations_for(page, all_pages, config),
+        series: series_for(page, all_pages, config),
+        related: related_for(page, all_pages, config),
+        head: head_html(config),
+        footer: footer_html(config),
         pages: all_pages,
     }
 }
 
+/// A page's `stylesheets` frontmatter as hrefs: names resolved like the site
+/// stylesheet, full (`https://`, `//`) URLs as written.
+fn stylesheets_for(page: &Page, base: &str) -> Vec<String> {
+    let names = match page.frontmatter.get(STYLESHEETS) {
+        Some(gray_matter::Pod::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                gray_matter::Pod::String(s) => Some(s.trim()),
+                _ => None,
+            })
+            .collect(),
+        Some(gray_matter::Pod::String(s)) => vec![s.trim()],
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            if name.contains("://") || name.starts_with("//") {
+                name.to_string()
+            } else {
+                resolve(&format!("/{}", name.trim_start_matches('/')), base)
+            }
+        })
+        .collect()
+}
+
 /// Nav inclusion rules. By default the top-level nav lists:
 ///
 /// - any top-level page (e.g. `/about/`, `/`)
@@ -117,17 +273,20 @@ pub fn compute_context<'a>(
 /// Anything else (deep leaf pages like `/blog/first-post/`) is excluded.
 ///
 /// Frontmatter `nav: true` force-includes a page that wouldn't otherwise
-/// qualify; `nav: false` force-excludes one that would.
+/// qualify; `nav: false` force-excludes one that would. The not-found page
+/// is never listed.
 fn include_in_nav(page: &Page, config: &SiteConfig) -> bool {
+    if is_not_found(page) {
+        return false;
+    }
     if let Some(gray_matter::Pod::Boolean(b)) = page.frontmatter.get("nav") {
         return *b;
     }
     if page.is_section_index {
         return true;
     }
-    let relative = page
-        .source_path
-        .strip_prefix(&config.content_dir)
+    let relative = config
+        .content_relative(&page.source_path)
         .unwrap_or(&page.source_path);
     let normal_components: Vec<_> = relative
         .components()
@@ -143,10 +302,18 @@ pub(crate) fn is_root_index(page: &Page, config: &SiteConfig) -> bool {
         .unwrap_or(false)
 }
 
-fn nav_item_for(p: &Page, is_current: bool, base: &str) -> NavItem {
+/// The sort configured by the index page of the section at `url`.
+fn section_sort(url: &str, all_pages: &[Page]) -> Option<SectionSort> {
+    all_pages
+        .iter()
+        .find(|p| p.url == url)
+        .and_then(SectionSort::of)
+}
+
+fn nav_item_for(p: &Page, is_current: bool, config: &SiteConfig) -> NavItem {
     NavItem {
         title: p.frontmatter.title.clone(),
-        href: resolve(&p.url, base),
+        href: link(&p.url, config),
         is_current,
     }
 }
@@ -236,6 +403,99 @@ mod tests {
         assert!(ctx.next.is_none());
     }
 
+    fn sorted_blog(sort_by: &str, sort_order: &str) -> Vec<Page> {
+        let post = |rel: &str, title: &str, date: &str| {
+            let mut page = make_page(rel, title);
+            page.date = Some(crate::core::date::PageDate {
+                iso: Some(date.to_string()),
+                display: date.to_string(),
+            });
+            page
+        };
+        let sort = |key: &str| gray_matter::Pod::String(key.to_string());
+        vec![
+            make_page_with_data(
+                "blog/index",
+                "Blog",
+                pod_hash(&[("sort_by", sort(sort_by)), ("sort_order", sort(sort_order))]),
+            ),
+            post("blog/a", "Zebra", "2024-03-01"),
+            post("blog/b", "Apple", "2024-01-01"),
+            post("blog/c", "Mango", "2024-02-01"),
+        ]
+    }
+
+    fn neighbours(page: &Page, pages: &[Page]) -> (Option<String>, Option<String>) {
+        let ctx = compute_context(page, pages, &test_config());
+        (ctx.prev.map(|n| n.title), ctx.next.map(|n| n.title))
+    }
+
+    #[test]
+    fn prev_next_follow_section_date_sort_chronologically() {
+        // Listed newest first or oldest first, "previous" is the older post.
+        for order in ["desc", "asc"] {
+            let pages = sorted_blog("date", order);
+            assert_eq!(
+                neighbours(&pages[3], &pages),
+                (Some("Apple".to_string()), Some("Zebra".to_string())),
+                "{order}"
+            );
+            assert_eq!(neighbours(&pages[1], &pages).1, None, "{order}");
+        }
+    }
+
+    #[test]
+    fn prev_next_follow_section_title_sort() {
+        let pages = sorted_blog("title", "asc");
+        assert_eq!(
+            neighbours(&pages[3], &pages),
+            (Some("Apple".to_string()), Some("Zebra".to_string()))
+        );
+        assert_eq!(neighbours(&pages[2], &pages).0, None);
+    }
+
+    #[test]
+    fn pages_under_lists_in_section_sort_order() {
+        let pages = sorted_blog("date", "desc");
+        let ctx = compute_context(&pages[0], &pages, &test_config());
+        let titles: Vec<&str> = ctx
+            .pages_under("/blog/")
+            .iter()
+            .map(|p| p.frontmatter.title.as_str())
+            .collect();
+        // The undated index itself sorts last.
+        assert_eq!(titles, ["Zebra", "Mango", "Apple", "Blog"]);
+    }
+
+    #[test]
+    fn sort_under_describes_the_section_sort() {
+        let pages = sorted_blog("title", "desc");
+        let ctx = compute_context(&pages[0], &pages, &test_config());
+        let sort = ctx.sort_under("/blog/").unwrap();
+        assert!(sort.by_title() && sort.descending);
+        assert_eq!(sort.description(), "Sorted by title, Z to A");
+        assert_eq!(ctx.sort_under("/"), None);
+    }
+
+    #[test]
+    fn pages_grouped_under_follows_section_group_by() {
+        let mut pages = sorted_blog("date", "desc");
+        pages[0].frontmatter.data = pod_hash(&[
+            ("sort_by", gray_matter::Pod::String("date".to_string())),
+            ("group_by", gray_matter::Pod::String("month".to_string())),
+        ]);
+        let ctx = compute_context(&pages[0], &pages, &test_config());
+        let grouped = ctx.pages_grouped_under("/blog/");
+        let groups: Vec<(&str, usize)> = grouped
+            .iter()
+            .map(|g| (g.label.as_str(), g.pages.len()))
+            .collect();
+        assert_eq!(
+            groups,
+            [("March 2024", 1), ("February 2024", 1), ("January 2024", 1)]
+        );
+    }
+
     #[test]
     fn only_page_has_no_prev_next() {
         let config = test_config();
@@ -275,6 +535,24 @@ mod tests {
         assert_eq!(blog, vec!["/blog/", "/blog/first/", "/blog/second/"]);
     }
 
+    #[test]
+    fn not_found_page_stays_out_of_listings() {
+        let pages = vec![
+            make_page("index", "Home"),
+            make_page("about", "About"),
+            make_page("404", "Not Found"),
+        ];
+        let ctx = compute_context(&pages[1], &pages, &test_config());
+        let titles: Vec<_> = ctx.nav.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, ["Home", "About"]);
+        assert_eq!(ctx.pages_under("/").len(), 2);
+        assert!(ctx.next.is_none());
+
+        let ctx = compute_context(&pages[2], &pages, &test_config());
+        assert_eq!(pages[2].url, "/404.html");
+        assert!(ctx.prev.is_none() && ctx.next.is_none());
+    }
+
     #[test]
     fn pages_under_empty_for_unknown_prefix() {
         let config = test_config();
@@ -366,6 +644,31 @@ mod tests {
         assert_eq!(ctx.resolve("/style.css"), "/sherwood/style.css");
     }
 
+    #[test]
+    fn trailing_slash_never_shapes_every_generated_href() {
+        let config = test_config_with_base("/docs").with_trailing_slash(TrailingSlash::Never);
+        let pages = vec![
+            make_page("index", "Home"),
+            make_page("blog/index", "Blog"),
+            make_page("blog/first", "First"),
+            make_page("blog/second", "Second"),
+        ];
+        let ctx = compute_context(&pages[2], &pages, &config);
+        let nav: Vec<_> = ctx.nav.iter().map(|n| n.href.as_str()).collect();
+        assert_eq!(nav, ["/docs", "/docs/blog"]);
+        let crumbs: Vec<_> = ctx
+            .breadcrumbs
+            .iter()
+            .filter_map(|c| c.href.as_deref())
+            .collect();
+        assert_eq!(crumbs, ["/docs", "/docs/blog"]);
+        assert_eq!(ctx.next.as_ref().unwrap().href, "/docs/blog/second");
+        assert_eq!(ctx.resolve(&pages[3].url), "/docs/blog/second");
+        assert_eq!(ctx.resolve("/style.css"), "/docs/style.css");
+        // Canonical URLs keep their slash, so listings still match.
+        assert_eq!(ctx.pages_under("/blog/").len(), 3);
+    }
+
     #[test]
     fn pages_under_matches_canonical_under_base_path() {
         let config = test_config_with_base("/sherwood");
@@ -383,6 +686,84 @@ mod tests {
         assert_eq!(urls, vec!["/blog/", "/blog/first/"]);
     }
 
+    #[test]
+    fn menu_empty_unless_configured() {
+        let pages = vec![make_page("index", "Home")];
+        let ctx = compute_context(&pages[0], &pages, &test_config());
+        assert!(ctx.menu.is_empty());
+
+        let config = test_config().with_menu([MenuItem::new("Home", "/")]);
+        let ctx = compute_context(&pages[0], &pages, &config);
+        assert_eq!(ctx.menu.len(), 1);
+        assert!(ctx.menu[0].is_current);
+    }
+
+    #[test]
+    fn stylesheet_href_resolved_under_base_path() {
+        let config = test_config_with_base("/docs").with_stylesheet("styles.css");
+        let pages = vec![make_page("index", "Home")];
+        let ctx = compute_context(&pages[0], &pages, &config);
+        assert_eq!(ctx.stylesheet, "/docs/styles.css");
+        assert_eq!(ctx.print_stylesheet, None);
+        let config = config.with_print_stylesheet("css/print.css");
+        let ctx = compute_context(&pages[0], &pages, &config);
+        assert_eq!(ctx.print_stylesheet.as_deref(), Some("/docs/css/print.css"));
+    }
+
+    #[test]
+    fn extra_stylesheets_resolved_per_page() {
+        use gray_matter::Pod;
+        let config = test_config_with_base("/docs");
+        let pages = vec![
+            make_page("index", "Home"),
+            make_page_with_data(
+                "guide",
+                "Guide",
+                pod_hash(&[(
+                    "stylesheets",
+                    Pod::Array(vec![
+                        Pod::String("css/docs.css".to_string()),
+                        Pod::String("/code.css".to_string()),
+                        Pod::String("https://cdn.example.com/k.css".to_string()),
+                    ]),
+                )]),
+            ),
+            make_page_with_data(
+                "blog",
+                "Blog",
+                pod_hash(&[("stylesheets", Pod::String("blog.css".to_string()))]),
+            ),
+        ];
+        assert!(
+            compute_context(&pages[0], &pages, &config)
+                .stylesheets
+                .is_empty()
+        );
+        assert_eq!(
+            compute_context(&pages[1], &pages, &config).stylesheets,
+            [
+                "/docs/css/docs.css",
+                "/docs/code.css",
+                "https://cdn.example.com/k.css"
+            ]
+        );
+        assert_eq!(
+            compute_context(&pages[2], &pages, &config).stylesheets,
+            ["/docs/blog.css"]
+        );
+    }
+
+    #[test]
+    fn lang_prefers_the_page_over_the_site() {
+        let config = test_config();
+        let mut pages = vec![make_page("index", "Home"), make_page("about", "Über")];
+        pages[1].lang = Some("de".to_string());
+        assert_eq!(compute_context(&pages[0], &pages, &config).lang, "en");
+        assert_eq!(compute_context(&pages[1], &pages, &config).lang, "de");
+        let config = config.with_language("fr");
+        assert_eq!(compute_context(&pages[0], &pages, &config).lang, "fr");
+    }
+
     #[test]
     fn prev_next_hrefs_resolved_under_base_path() {
         let config = test_config_with_base("/docs");
@@ -0,0 +1,118 @@
+use crate::core::config::SiteConfig;
+use crate::core::content::page::Page;
+use crate::core::content::url_strategy::is_not_found;
+
+use super::{NavItem, nav_item_for};
+
+/// Frontmatter field listing a page's tags.
+const TAGS: &str = "tags";
+
+/// How many related pages [`PageContext::related`](super::PageContext::related)
+/// lists unless [`SiteConfig::with_related_limit`] says otherwise.
+pub const DEFAULT_RELATED_LIMIT: usize = 5;
+
+/// Up to [`SiteConfig::related_limit`] other pages sharing tags with `page`:
+/// most shared tags first, then newest (undated last), then build order.
+/// Empty when `page` has no tags.
+pub(crate) fn related_for(page: &Page, all_pages: &[Page], config: &SiteConfig) -> Vec<NavItem> {
+    let tags = tags_of(page);
+    if tags.is_empty() || config.related_limit == 0 {
+        return Vec::new();
+    }
+    let mut scored: Vec<(usize, Option<&str>, &Page)> = all_pages
+        .iter()
+        .filter(|p| p.output_path != page.output_path && !is_not_found(p))
+        .filter_map(|p| {
+            let shared = tags_of(p).iter().filter(|t| tags.contains(t)).count();
+            let date = p.date.as_ref().and_then(|d| d.iso.as_deref());
+            (shared > 0).then_some((shared, date, p))
+        })
+        .collect();
+    // Stable sort: equal scores and dates keep build order.
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+    scored
+        .into_iter()
+        .take(config.related_limit)
+        .map(|(_, _, p)| nav_item_for(p, false, config))
+        .collect()
+}
+
+/// A page's `tags` frontmatter — a list, or a single string — trimmed,
+/// lowercased and without duplicates, so `Rust` and `rust ` match.
+fn tags_of(page: &Page) -> Vec<String> {
+    let raw: Vec<String> = match page.frontmatter.get(TAGS) {
+        Some(gray_matter::Pod::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                gray_matter::Pod::String(s) => Some(s.clone()),
+                _ => None,
+            })
+            .collect(),
+        Some(gray_matter::Pod::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::PageDate;
+    use crate::core::nav::test_support::{make_page_with_data, pod_hash, test_config};
+    use gray_matter::Pod;
+
+    fn post(rel: &str, tags: &[&str], date: Option<&str>) -> Page {
+        let tags = tags.iter().map(|t| Pod::String(t.to_string())).collect();
+        let mut page = make_page_with_data(rel, rel, pod_hash(&[(TAGS, Pod::Array(tags))]));
+        page.date = date.map(|d| PageDate {
+            iso: Some(d.to_string()),
+            display: d.to_string(),
+        });
+        page
+    }
+
+    fn titles(items: &[NavItem]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_by_shared_tags_then_recency() {
+        let pages = vec![
+            post("blog/current", &["rust", "ssg", "web"], Some("2024-05-01")),
+            post("blog/one-old", &["Rust"], Some("2023-01-01")),
+            post("blog/two", &["rust", "web"], Some("2022-01-01")),
+            post("blog/one-new", &["ssg "], Some("2024-01-01")),
+            post("blog/undated", &["web"], None),
+            post("blog/unrelated", &["cooking"], Some("2024-04-01")),
+        ];
+        let related = related_for(&pages[0], &pages, &test_config());
+        assert_eq!(
+            titles(&related),
+            ["blog/two", "blog/one-new", "blog/one-old", "blog/undated"]
+        );
+        assert_eq!(related[0].href, "/blog/two/");
+    }
+
+    #[test]
+    fn respects_limit_and_untagged_pages() {
+        let pages = vec![
+            post("a", &["x"], None),
+            post("b", &["x"], None),
+            post("c", &["x"], None),
+            post("d", &[], None),
+        ];
+        let mut config = test_config();
+        config.related_limit = 1;
+        assert_eq!(titles(&related_for(&pages[0], &pages, &config)), ["b"]);
+        assert!(related_for(&pages[3], &pages, &config).is_empty());
+        config.related_limit = 0;
+        assert!(related_for(&pages[0], &pages, &config).is_empty());
+    }
+}
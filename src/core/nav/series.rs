@@ -0,0 +1,200 @@
+use crate::core::config::SiteConfig;
+use crate::core::content::frontmatter::FrontMatter;
+use crate::core::content::page::Page;
+use crate::core::content::parser::escape_html;
+use crate::core::content::slug::slugify;
+
+use super::{NavItem, href_for, link, nav_item_for};
+
+/// Frontmatter field that groups pages into a multi-part series.
+pub(crate) const SERIES: &str = "series";
+
+/// The series a page belongs to, for "Part 2 of 5" indicators and
+/// prev/next-in-series links.
+#[derive(Debug, Clone)]
+pub struct Series {
+    /// The series name, as written in the frontmatter.
+    pub name: String,
+    /// Href of the series landing page (`/series/<slug>/`), resolved against
+    /// the base path.
+    pub href: String,
+    /// This page's position, counting from 1.
+    pub part: usize,
+    /// Every part in reading order; the current page has `is_current` set.
+    /// `parts.len()` is the part count.
+    pub parts: Vec<NavItem>,
+    pub prev: Option<NavItem>,
+    pub next: Option<NavItem>,
+}
+
+/// The series `page` is part of, per its `series` frontmatter field.
+pub(crate) fn series_for(page: &Page, all_pages: &[Page], config: &SiteConfig) -> Option<Series> {
+    let name = page.frontmatter.get_string(SERIES)?;
+    let parts = series_parts(&name, all_pages);
+    let idx = parts
+        .iter()
+        .position(|p| p.output_path == page.output_path)?;
+    Some(Series {
+        href: link(&series_url(&name), config),
+        part: idx + 1,
+        parts: parts
+            .iter()
+            .map(|p| nav_item_for(p, p.output_path == page.output_path, config))
+            .collect(),
+        prev: idx
+            .checked_sub(1)
+            .map(|i| nav_item_for(parts[i], false, config)),
+        next: parts.get(idx + 1).map(|p| nav_item_for(p, false, config)),
+        name,
+    })
+}
+
+/// A generated landing page for each series, at `/series/<slug>/`, whose
+/// content is an ordered list of the parts. Generated pages have no source
+/// file: `source_path` names the content-relative location the page
+/// would have (`series/<slug>`), and callers skip any whose output an
+/// authored page already claims.
+pub(crate) fn series_landing_pages(pages: &[Page], config: &SiteConfig) -> Vec<Page> {
+    let mut names: Vec<String> = pages
+        .iter()
+        .filter_map(|p| p.frontmatter.get_string(SERIES))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut landings = Vec::new();
+    for name in names {
+        let slug = slugify(&name);
+        if slug.is_empty() {
+            continue;
+        }
+        let parts = series_parts(&name, pages);
+        let mut content_html = String::from("<ol class=\"series-parts\">\n");
+        for part in &parts {
+            content_html.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                link(&part.url, config),
+                escape_html(&part.frontmatter.title)
+            ));
+        }
+        content_html.push_str("</ol>\n");
+        let output_path = config
+            .output_dir
+            .join("series")
+            .join(&slug)
+            .join("index.html");
+        landings.push(Page {
+            frontmatter: FrontMatter {
+                title: name,
+                data: gray_matter::Pod::Null,
+            },
+            content_html,
+            excerpt_html: None,
+            toc_html: None,
+            raw_content: None,
+            source_path: config.content_dir.join("series").join(&slug),
+            url: href_for(&output_path, config),
+            output_path,
+            is_section_index: false,
+            last_modified: None,
+            date: None,
+            lang: parts.first().and_then(|p| p.lang.clone()),
+            warnings: Vec::new(),
+        });
+    }
+    landings
+}
+
+/// Pages in series `name`, in reading order: ascending frontmatter
+/// `weight` (weighted parts first), then `date` (oldest first, undated
+/// last), then build order.
+fn series_parts<'a>(name: &str, pages: &'a [Page]) -> Vec<&'a Page> {
+    let mut parts: Vec<&Page> = pages
+        .iter()
+        .filter(|p| p.frontmatter.get_string(SERIES).as_deref() == Some(name))
+        .collect();
+    parts.sort_by_key(|p| {
+        let weight = match p.frontmatter.get("weight") {
+            Some(gray_matter::Pod::Integer(n)) => Some(*n),
+            _ => None,
+        };
+        let date = p.date.as_ref().and_then(|d| d.iso.clone());
+        ((weight.is_none(), weight), (date.is_none(), date))
+    });
+    parts
+}
+
+fn series_url(name: &str) -> String {
+    format!("/series/{}/", slugify(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::PageDate;
+    use crate::core::nav::test_support::{
+        make_page_with_data, pod_hash, test_config, test_config_with_base,
+    };
+    use gray_matter::Pod;
+
+    fn part(rel: &str, weight: Option<i64>, date: Option<&str>) -> Page {
+        let mut data = vec![(SERIES, Pod::String("Rust 101".to_string()))];
+        if let Some(w) = weight {
+            data.push(("weight", Pod::Integer(w)));
+        }
+        let mut page = make_page_with_data(rel, rel, pod_hash(&data));
+        page.date = date.map(|d| PageDate {
+            iso: Some(d.to_string()),
+            display: d.to_string(),
+        });
+        page
+    }
+
+    #[test]
+    fn parts_ordered_by_weight_then_date() {
+        let pages = vec![
+            part("c", None, Some("2024-03-01")),
+            part("b", None, Some("2024-01-01")),
+            part("z", Some(1), None),
+            part("d", None, None),
+        ];
+        let titles: Vec<&str> = series_parts("Rust 101", &pages)
+            .iter()
+            .map(|p| p.frontmatter.title.as_str())
+            .collect();
+        assert_eq!(titles, ["z", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn series_for_gives_position_and_neighbours() {
+        let config = test_config_with_base("/docs");
+        let pages = vec![
+            part("one", Some(1), None),
+            part("two", Some(2), None),
+            part("three", Some(3), None),
+            make_page_with_data("other", "Other", Pod::Null),
+        ];
+        let series = series_for(&pages[1], &pages, &config).unwrap();
+        assert_eq!(series.name, "Rust 101");
+        assert_eq!(series.href, "/docs/series/rust-101/");
+        assert_eq!(series.part, 2);
+        assert_eq!(series.parts.len(), 3);
+        assert!(series.parts[1].is_current);
+        assert_eq!(series.prev.unwrap().href, "/docs/one/");
+        assert_eq!(series.next.unwrap().href, "/docs/three/");
+        assert!(series_for(&pages[3], &pages, &config).is_none());
+    }
+
+    #[test]
+    fn landing_page_lists_parts() {
+        let config = test_config();
+        let pages = vec![part("two", Some(2), None), part("one", Some(1), None)];
+        let landings = series_landing_pages(&pages, &config);
+        assert_eq!(landings.len(), 1);
+        let landing = &landings[0];
+        assert_eq!(landing.url, "/series/rust-101/");
+        assert_eq!(landing.frontmatter.title, "Rust 101");
+        let content = &landing.content_html;
+        assert!(content.find("/one/").unwrap() < content.find("/two/").unwrap());
+    }
+}
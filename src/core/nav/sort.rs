@@ -0,0 +1,226 @@
+use crate::core::content::page::Page;
+use std::cmp::Ordering;
+
+/// Section index frontmatter field naming the key its pages sort by:
+/// `date` or `title`. Absent (or anything else) keeps build order.
+pub(crate) const SORT_BY: &str = "sort_by";
+/// Section index frontmatter field for the listing direction: `asc` or
+/// `desc`. Defaults to `desc` for dates (newest first), `asc` for titles.
+pub(crate) const SORT_ORDER: &str = "sort_order";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Date,
+    Title,
+}
+
+/// How a section index asks for its pages to be listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SectionSort {
+    key: SortKey,
+    descending: bool,
+}
+
+/// A section listing's sort, for templates that tell readers how it's
+/// ordered or render sort controls. From
+/// [`PageContext::sort_under`](crate::PageContext::sort_under).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ListSort {
+    /// The key pages are sorted by: `"date"` or `"title"`.
+    pub key: &'static str,
+    /// Newest (or Z) first.
+    pub descending: bool,
+}
+
+impl ListSort {
+    /// Whether the listing is ordered by date.
+    pub fn by_date(&self) -> bool {
+        self.key == "date"
+    }
+
+    /// Whether the listing is ordered by title.
+    pub fn by_title(&self) -> bool {
+        self.key == "title"
+    }
+
+    /// A caption for the listing, e.g. `Sorted by date, newest first`.
+    pub fn description(&self) -> String {
+        let direction = match (self.by_date(), self.descending) {
+            (true, true) => "newest first",
+            (true, false) => "oldest first",
+            (false, false) => "A to Z",
+            (false, true) => "Z to A",
+        };
+        format!("Sorted by {}, {direction}", self.key)
+    }
+}
+
+impl SectionSort {
+    /// The sort `index`'s frontmatter configures, if any.
+    pub(crate) fn of(index: &Page) -> Option<Self> {
+        let key = match index.frontmatter.get_string(SORT_BY)?.trim() {
+            "date" => SortKey::Date,
+            "title" => SortKey::Title,
+            _ => return None,
+        };
+        let descending = match index
+            .frontmatter
+            .get_string(SORT_ORDER)
+            .as_deref()
+            .map(str::trim)
+        {
+            Some("asc") => false,
+            Some("desc") => true,
+            _ => key == SortKey::Date,
+        };
+        Some(Self { key, descending })
+    }
+
+    /// The sort as templates see it.
+    pub(crate) fn describe(&self) -> ListSort {
+        ListSort {
+            key: match self.key {
+                SortKey::Date => "date",
+                SortKey::Title => "title",
+            },
+            descending: self.descending,
+        }
+    }
+
+    /// Sort `pages` in listing order. Pages without the key (undated pages)
+    /// go last either way; ties keep build order.
+    pub(crate) fn list(&self, pages: &mut [&Page]) {
+        pages.sort_by(|a, b| {
+            let ordering = self.compare(a, b);
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.undated_last(pages);
+    }
+
+    /// Sort `pages` in reading order for prev/next: always ascending, so
+    /// "previous" is the older post (or the earlier title) even when the
+    /// section lists newest first.
+    pub(crate) fn read(&self, pages: &mut [&Page]) {
+        pages.sort_by(|a, b| self.compare(a, b));
+        self.undated_last(pages);
+    }
+
+    fn compare(&self, a: &Page, b: &Page) -> Ordering {
+        match self.key {
+            SortKey::Date => date_of(a).cmp(&date_of(b)),
+            SortKey::Title => a
+                .frontmatter
+                .title
+                .to_lowercase()
+                .cmp(&b.frontmatter.title.to_lowercase()),
+        }
+    }
+
+    fn undated_last(&self, pages: &mut [&Page]) {
+        if self.key == SortKey::Date {
+            // Stable: dated pages keep their sorted order ahead of the rest.
+            pages.sort_by_key(|p| date_of(p).is_none());
+        }
+    }
+}
+
+fn date_of(page: &Page) -> Option<&str> {
+    page.date.as_ref().and_then(|d| d.iso.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::date::PageDate;
+    use crate::core::nav::test_support::{make_page, make_page_with_data, pod_hash};
+    use gray_matter::Pod;
+
+    fn index(sort_by: &str, sort_order: Option<&str>) -> Page {
+        let mut data = vec![(SORT_BY, Pod::String(sort_by.to_string()))];
+        if let Some(order) = sort_order {
+            data.push((SORT_ORDER, Pod::String(order.to_string())));
+        }
+        make_page_with_data("blog/index", "Blog", pod_hash(&data))
+    }
+
+    fn post(rel: &str, title: &str, date: Option<&str>) -> Page {
+        let mut page = make_page(rel, title);
+        page.date = date.map(|d| PageDate {
+            iso: Some(d.to_string()),
+            display: d.to_string(),
+        });
+        page
+    }
+
+    fn titles(pages: &[&Page]) -> Vec<String> {
+        pages.iter().map(|p| p.frontmatter.title.clone()).collect()
+    }
+
+    #[test]
+    fn unknown_or_missing_keys_configure_nothing() {
+        assert_eq!(SectionSort::of(&make_page("blog/index", "Blog")), None);
+        assert_eq!(SectionSort::of(&index("weight", None)), None);
+    }
+
+    #[test]
+    fn dates_list_newest_first_by_default_but_read_oldest_first() {
+        let posts = [
+            post("blog/b", "B", Some("2024-02-01")),
+            post("blog/u", "Undated", None),
+            post("blog/a", "A", Some("2024-01-01")),
+            post("blog/c", "C", Some("2024-03-01")),
+        ];
+        let mut pages: Vec<&Page> = posts.iter().collect();
+        let sort = SectionSort::of(&index("date", None)).unwrap();
+        sort.list(&mut pages);
+        assert_eq!(titles(&pages), ["C", "B", "A", "Undated"]);
+        sort.read(&mut pages);
+        assert_eq!(titles(&pages), ["A", "B", "C", "Undated"]);
+
+        let sort = SectionSort::of(&index("date", Some("asc"))).unwrap();
+        sort.list(&mut pages);
+        assert_eq!(titles(&pages), ["A", "B", "C", "Undated"]);
+    }
+
+    #[test]
+    fn describes_the_sort() {
+        let describe = |by, order| SectionSort::of(&index(by, order)).unwrap().describe();
+        let sort = describe("date", None);
+        assert!(sort.by_date() && sort.descending);
+        assert_eq!(sort.description(), "Sorted by date, newest first");
+        assert_eq!(
+            describe("date", Some("asc")).description(),
+            "Sorted by date, oldest first"
+        );
+        let sort = describe("title", None);
+        assert!(sort.by_title() && !sort.descending);
+        assert_eq!(sort.description(), "Sorted by title, A to Z");
+        assert_eq!(
+            describe("title", Some("desc")).description(),
+            "Sorted by title, Z to A"
+        );
+    }
+
+    #[test]
+    fn titles_sort_case_insensitively() {
+        let posts = [
+            post("blog/1", "banana", None),
+            post("blog/2", "Apple", None),
+            post("blog/3", "cherry", None),
+        ];
+        let mut pages: Vec<&Page> = posts.iter().collect();
+        SectionSort::of(&index("title", None))
+            .unwrap()
+            .list(&mut pages);
+        assert_eq!(titles(&pages), ["Apple", "banana", "cherry"]);
+        SectionSort::of(&index("title", Some("desc")))
+            .unwrap()
+            .list(&mut pages);
+        assert_eq!(titles(&pages), ["cherry", "banana", "Apple"]);
+    }
+}
@@ -2,8 +2,8 @@
 
 use crate::core::config::SiteConfig;
 use crate::core::content::frontmatter::FrontMatter;
-use crate::core::content::page::{Page, output_path_for};
-use crate::core::nav::href_for;
+use crate::core::content::page::Page;
+use crate::core::content::url_strategy::page_location;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -23,7 +23,7 @@ pub(crate) fn test_config_with_base(base: &str) -> SiteConfig {
 /// Examples: `"about"`, `"blog/post"`, `"index"`, `"blog/index"`. The
 /// helper computes source path, output path, URL, and the
 /// `is_section_index` flag from this single input — mirroring what
-/// `load_page` does.
+/// `load_page_with_defaults` does.
 pub(crate) fn make_page(rel: &str, title: &str) -> Page {
     make_page_with_data(rel, title, gray_matter::Pod::Null)
 }
@@ -31,20 +31,26 @@ pub(crate) fn make_page(rel: &str, title: &str) -> Page {
 pub(crate) fn make_page_with_data(rel: &str, title: &str, data: gray_matter::Pod) -> Page {
     let config = test_config();
     let source = config.content_dir.join(format!("{}.md", rel));
-    let output = output_path_for(&source, &config);
-    let url = href_for(&output, &config);
+    let frontmatter = FrontMatter {
+        title: title.to_string(),
+        data,
+    };
+    let (output, url) = page_location(&source, &frontmatter, &config);
     let is_section_index = Path::new(rel).file_name().and_then(|n| n.to_str()) == Some("index");
     Page {
-        frontmatter: FrontMatter {
-            title: title.to_string(),
-            data,
-        },
+        frontmatter,
         content_html: String::new(),
         excerpt_html: None,
+        toc_html: None,
+        raw_content: None,
         source_path: source,
         output_path: output,
         url,
         is_section_index,
+        last_modified: None,
+        date: None,
+        lang: None,
+        warnings: Vec::new(),
     }
 }
 
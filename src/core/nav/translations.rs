@@ -0,0 +1,114 @@
+use crate::core::config::SiteConfig;
+use crate::core::content::page::Page;
+
+use super::absolute_href;
+
+/// Frontmatter field that links language versions of the same page.
+pub(crate) const TRANSLATION_KEY: &str = "translation_key";
+
+/// One language version of a page, for
+/// `<link rel="alternate" hreflang="…" href="…">` tags and language
+/// switchers.
+#[derive(Debug, Clone)]
+pub struct Translation {
+    /// Language code, e.g. `en` or `pt-BR` ([`Page::lang`]).
+    pub lang: String,
+    /// Page title in that language.
+    pub title: String,
+    /// Absolute URL when [`SiteConfig::site_url`] is set (search engines
+    /// require it for `hreflang`), otherwise root-relative; either way
+    /// resolved against the base path.
+    pub href: String,
+    /// `true` for the page being rendered.
+    pub is_current: bool,
+}
+
+/// Every language version of `page` — pages sharing its `translation_key`
+/// and carrying a language — sorted by language code. The page itself is
+/// included, as `hreflang` expects. Empty when the page has no key or
+/// language, or no other version exists.
+pub(crate) fn translations_for(
+    page: &Page,
+    all_pages: &[Page],
+    config: &SiteConfig,
+) -> Vec<Translation> {
+    let (Some(key), Some(_)) = (page.frontmatter.get_string(TRANSLATION_KEY), &page.lang) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<Translation> = all_pages
+        .iter()
+        .filter(|p| p.frontmatter.get_string(TRANSLATION_KEY).as_deref() == Some(key.as_str()))
+        .filter_map(|p| {
+            let lang = p.lang.clone()?;
+            Some(Translation {
+                lang,
+                title: p.frontmatter.title.clone(),
+                href: absolute_href(config, &p.url),
+                is_current: p.output_path == page.output_path,
+            })
+        })
+        .collect();
+    if versions.len() < 2 {
+        return Vec::new();
+    }
+    versions.sort_by(|a, b| a.lang.cmp(&b.lang));
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::{make_page, make_page_with_data, pod_hash, test_config};
+    use gray_matter::Pod;
+
+    fn translated(rel: &str, lang: &str, key: &str) -> Page {
+        let mut page = make_page_with_data(
+            rel,
+            rel,
+            pod_hash(&[(TRANSLATION_KEY, Pod::String(key.to_string()))]),
+        );
+        page.lang = Some(lang.to_string());
+        page
+    }
+
+    #[test]
+    fn links_pages_sharing_a_key() {
+        let pages = vec![
+            translated("fr/about", "fr", "about"),
+            translated("en/about", "en", "about"),
+            translated("en/contact", "en", "contact"),
+            make_page("index", "Home"),
+        ];
+        let config = test_config().with_site_url("https://example.com");
+        let versions = translations_for(&pages[0], &pages, &config);
+        let summary: Vec<_> = versions
+            .iter()
+            .map(|t| (t.lang.as_str(), t.href.as_str(), t.is_current))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("en", "https://example.com/en/about/", false),
+                ("fr", "https://example.com/fr/about/", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn untranslated_page_has_no_alternates() {
+        let pages = vec![
+            translated("en/contact", "en", "contact"),
+            make_page("index", "Home"),
+        ];
+        assert!(translations_for(&pages[0], &pages, &test_config()).is_empty());
+        assert!(translations_for(&pages[1], &pages, &test_config()).is_empty());
+    }
+
+    #[test]
+    fn pages_without_a_language_are_skipped() {
+        let mut unlabelled = translated("about", "en", "about");
+        unlabelled.lang = None;
+        let pages = vec![translated("fr/about", "fr", "about"), unlabelled];
+        assert!(translations_for(&pages[0], &pages, &test_config()).is_empty());
+    }
+}
@@ -1,5 +1,71 @@
 use crate::core::config::SiteConfig;
 use std::path::Path;
+use std::str::FromStr;
+
+/// Whether generated links to pages end with `/`
+/// ([`SiteConfig::trailing_slash`]). Hosts differ: some serve `/about/` and
+/// redirect `/about`, others the reverse, so links should match the host to
+/// save a redirect. Canonical URLs (`page.url`) keep their slash either way;
+/// this only shapes the hrefs Sherwood emits — nav, menu, breadcrumbs,
+/// prev/next, series and related links, translations, feed entries, alias
+/// redirects and [`PageContext::resolve`](crate::PageContext::resolve). Links to files (`/404.html`, `/style.css`) and links
+/// written in content are left alone.
+///
+/// ```
+/// use sherwood::{SiteConfig, TrailingSlash};
+/// let config = SiteConfig::new().with_trailing_slash(TrailingSlash::Never);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TrailingSlash {
+    /// Page links as generated, `/about/` (the default). Configured menu
+    /// URLs are used as written.
+    #[default]
+    Auto,
+    /// Every page link ends with `/`, configured menu URLs included.
+    Always,
+    /// No page link ends with `/` (`/about`), except the site root.
+    Never,
+}
+
+impl TrailingSlash {
+    /// `url` with its path's trailing slash added or removed. Only
+    /// root-relative URLs whose last segment isn't a file name change; a
+    /// query or fragment is kept.
+    pub(crate) fn apply(self, url: &str) -> String {
+        if self == TrailingSlash::Auto || !url.starts_with('/') || url.starts_with("//") {
+            return url.to_string();
+        }
+        let split = url.find(['?', '#']).unwrap_or(url.len());
+        let (path, rest) = url.split_at(split);
+        let last = path.rsplit('/').next().unwrap_or_default();
+        if last.contains('.') {
+            return url.to_string();
+        }
+        match self {
+            TrailingSlash::Always if !path.ends_with('/') => format!("{path}/{rest}"),
+            TrailingSlash::Never if path.len() > 1 && path.ends_with('/') => {
+                format!("{}{rest}", &path[..path.len() - 1])
+            }
+            _ => url.to_string(),
+        }
+    }
+}
+
+impl FromStr for TrailingSlash {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(TrailingSlash::Auto),
+            "always" => Ok(TrailingSlash::Always),
+            "never" => Ok(TrailingSlash::Never),
+            other => Err(format!(
+                "unknown trailing slash `{other}` (expected auto, always or never)"
+            )),
+        }
+    }
+}
 
 pub(crate) fn href_for(output_path: &Path, config: &SiteConfig) -> String {
     let relative = output_path
@@ -45,6 +111,68 @@ pub(crate) fn resolve(canonical: &str, base: &str) -> String {
     }
 }
 
+/// Href for a generated link to a canonical URL: resolved against the base
+/// path, then shaped by [`SiteConfig::trailing_slash`]. Under a `/docs`
+/// base with `never`, `/` links to `/docs` and `/guide/` to `/docs/guide`.
+pub(crate) fn link(canonical: &str, config: &SiteConfig) -> String {
+    config
+        .trailing_slash
+        .apply(&resolve(canonical, &config.base_path))
+}
+
+/// Absolute form of [`link`], for feeds and `hreflang` alternates.
+pub(crate) fn absolute_href(config: &SiteConfig, canonical: &str) -> String {
+    format!("{}{}", config.site_url, link(canonical, config))
+}
+
+/// Absolute URL for a canonical path: the configured site URL, then the base
+/// path, then the path. Without a site URL this degrades to a root-relative
+/// URL.
+pub(crate) fn absolute_url(config: &SiteConfig, canonical: &str) -> String {
+    format!(
+        "{}{}",
+        config.site_url,
+        resolve(canonical, &config.base_path)
+    )
+}
+
+/// Whether a link leaves the site: protocol-relative (`//cdn.example`) or
+/// with a scheme (`https:`, `mailto:`). A colon after the first `/`
+/// (`notes/a:b/`) is part of the path.
+pub(crate) fn is_external(link: &str) -> bool {
+    link.starts_with("//")
+        || link
+            .find(':')
+            .is_some_and(|colon| !link[..colon].contains('/'))
+}
+
+/// A root-relative path with a leading base path removed, so links written
+/// with or without it (`/docs/guide/`, `/guide/` under `/docs`) name the
+/// same page. `/docs` itself becomes empty; `/docsite/` is left alone.
+pub(crate) fn strip_base_path<'a>(path: &'a str, base: &str) -> &'a str {
+    match path.strip_prefix(base) {
+        Some(rest) if !base.is_empty() && (rest.is_empty() || rest.starts_with('/')) => rest,
+        _ => path,
+    }
+}
+
+/// The segments of a `/`-separated path with empty and `.` segments dropped
+/// and each `..` removing the one before it, so the result can't climb above
+/// whatever root it's joined to.
+pub(crate) fn normalize_segments(path: &str) -> Vec<&str> {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+    segments
+}
+
 // Build an absolute URL from a relative output path. We walk components and
 // join with '/' rather than using `Path::display()` because on Windows
 // `display()` would emit '\' separators, producing invalid URLs like
@@ -122,4 +250,69 @@ mod tests {
         assert_eq!(resolve("/", "/sherwood"), "/sherwood/");
         assert_eq!(resolve("/style.css", "/sherwood"), "/sherwood/style.css");
     }
+
+    #[test]
+    fn trailing_slash_shapes_page_links_only() {
+        let never = TrailingSlash::Never;
+        assert_eq!(never.apply("/guide/"), "/guide");
+        assert_eq!(never.apply("/guide/?a=1#top"), "/guide?a=1#top");
+        assert_eq!(never.apply("/"), "/");
+        assert_eq!(never.apply("/404.html"), "/404.html");
+        let always = TrailingSlash::Always;
+        assert_eq!(always.apply("/guide#top"), "/guide/#top");
+        assert_eq!(always.apply("/style.css"), "/style.css");
+        assert_eq!(
+            always.apply("https://example.com/a"),
+            "https://example.com/a"
+        );
+        assert_eq!(TrailingSlash::Auto.apply("/guide"), "/guide");
+        assert_eq!("Never".parse::<TrailingSlash>(), Ok(TrailingSlash::Never));
+        assert!("sometimes".parse::<TrailingSlash>().is_err());
+    }
+
+    #[test]
+    fn link_applies_base_path_then_trailing_slash() {
+        let config = test_config()
+            .with_base_path("/docs")
+            .with_trailing_slash(TrailingSlash::Never);
+        assert_eq!(link("/guide/", &config), "/docs/guide");
+        assert_eq!(link("/", &config), "/docs");
+        assert_eq!(link("/", &test_config()), "/");
+        let config = config.with_site_url("https://example.com");
+        assert_eq!(absolute_href(&config, "/a/"), "https://example.com/docs/a");
+    }
+
+    #[test]
+    fn normalize_segments_resolves_dots_within_the_root() {
+        assert_eq!(normalize_segments("/blog/./post/"), ["blog", "post"]);
+        assert_eq!(normalize_segments("a/b/../c"), ["a", "c"]);
+        assert_eq!(normalize_segments("/../../etc/passwd"), ["etc", "passwd"]);
+        assert!(normalize_segments("/").is_empty());
+    }
+
+    #[test]
+    fn external_links_need_a_scheme_or_host() {
+        assert!(is_external("https://example.com/"));
+        assert!(is_external("mailto:me@example.com"));
+        assert!(is_external("//cdn.example.com/x.js"));
+        assert!(!is_external("/notes/a:b/"));
+        assert!(!is_external("../guide/"));
+    }
+
+    #[test]
+    fn strip_base_path_only_removes_a_whole_segment() {
+        assert_eq!(strip_base_path("/docs/guide/", "/docs"), "/guide/");
+        assert_eq!(strip_base_path("/docs", "/docs"), "");
+        assert_eq!(strip_base_path("/docsite/", "/docs"), "/docsite/");
+        assert_eq!(strip_base_path("/guide/", ""), "/guide/");
+    }
+
+    #[test]
+    fn absolute_url_includes_base_path() {
+        let config = test_config()
+            .with_site_url("https://example.com/")
+            .with_base_path("/docs");
+        assert_eq!(absolute_url(&config, "/a/"), "https://example.com/docs/a/");
+        assert_eq!(absolute_url(&config, "/"), "https://example.com/docs/");
+    }
 }
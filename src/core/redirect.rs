@@ -0,0 +1,401 @@
+//! Redirect rules configured once, for moved content: each rule sends an old
+//! path somewhere else. The build writes a stub page at every `from` path (a
+//! meta refresh plus a canonical link) for static hosts, and `serve` answers
+//! the same paths with real HTTP redirects, so both agree on where each old
+//! URL lands.
+//!
+//! Pages can also list their own old paths in `aliases` (or Jekyll's
+//! `redirect_from`) frontmatter; each becomes a 301 rule to the page.
+
+use crate::core::config::SiteConfig;
+use crate::core::content::page::Page;
+use crate::core::content::parser::escape_html;
+use crate::core::nav::{normalize_segments, resolve};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Status codes a [`Redirect`] may use.
+const STATUSES: [u16; 4] = [301, 302, 307, 308];
+
+/// Frontmatter fields listing a page's old paths: Hugo's name and Jekyll's.
+const ALIAS_KEYS: [&str; 2] = ["aliases", "redirect_from"];
+
+/// One redirect rule, configured with
+/// [`SiteConfig::with_redirects`](crate::SiteConfig::with_redirects).
+///
+/// ```
+/// use sherwood::Redirect;
+/// let moved = Redirect::new("/old-post/", "/blog/new-post/");
+/// let temporary: Redirect = "/sale/ https://shop.example.com/ 302".parse().unwrap();
+/// assert_eq!((moved.status, temporary.status), (301, 302));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Redirect {
+    /// Root-relative path to redirect from, without the base path. `/old`,
+    /// `/old/` and `/old/index.html` are the same page.
+    pub from: String,
+    /// Where to send visitors: a root-relative path (resolved against the
+    /// base path) or an absolute URL.
+    pub to: String,
+    /// 301 (permanent, the default), 302, 307 or 308. Stub pages can't carry
+    /// a status, so it only matters to `serve` and to hosts that read it.
+    pub status: u16,
+}
+
+impl Redirect {
+    /// A permanent (301) redirect.
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            status: 301,
+        }
+    }
+
+    /// Set the HTTP status.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// The `Location` to send, with a root-relative `to` resolved against
+    /// `base_path`.
+    pub(crate) fn target(&self, base_path: &str) -> String {
+        if self.to.starts_with('/') && !self.to.starts_with("//") {
+            resolve(&self.to, base_path)
+        } else {
+            self.to.clone()
+        }
+    }
+}
+
+/// Parses `FROM TO [STATUS]` (whitespace-separated, as in a Netlify
+/// `_redirects` line), the CLI's `--redirect` format.
+impl FromStr for Redirect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let redirect = match parts.as_slice() {
+            [from, to] => Redirect::new(*from, *to),
+            [from, to, status] => Redirect::new(*from, *to).with_status(
+                status
+                    .parse()
+                    .map_err(|_| format!("invalid status `{status}` in `{s}`"))?,
+            ),
+            _ => return Err(format!("expected `FROM TO [STATUS]`, got `{s}`")),
+        };
+        Ok(redirect)
+    }
+}
+
+/// A redirect rule that can't be honoured.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RedirectError {
+    #[error("redirect from `{from}`: {message}")]
+    Invalid { from: String, message: String },
+    #[error("redirect from `{from}` is listed more than once")]
+    Duplicate { from: String },
+    #[error("redirect loop: {}", chain.join(" -> "))]
+    Loop { chain: Vec<String> },
+    #[error("redirect from `{from}` would overwrite {}", existing.display())]
+    Conflict { from: String, existing: PathBuf },
+}
+
+/// Check every rule is well-formed, no path is redirected twice, and no
+/// chain of rules leads back to where it started.
+pub(crate) fn validate(redirects: &[Redirect]) -> Result<(), RedirectError> {
+    let mut targets: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+    for r in redirects {
+        let invalid = |message: &str| RedirectError::Invalid {
+            from: r.from.clone(),
+            message: message.to_string(),
+        };
+        if !r.from.starts_with('/') || r.from.starts_with("//") {
+            return Err(invalid("must be a root-relative path like `/old/`"));
+        }
+        if r.to.trim().is_empty() {
+            return Err(invalid("has no target"));
+        }
+        if !STATUSES.contains(&r.status) {
+            return Err(invalid(&format!(
+                "status {} is not one of 301, 302, 307, 308",
+                r.status
+            )));
+        }
+        let target = (r.to.starts_with('/') && !r.to.starts_with("//")).then(|| page_key(&r.to));
+        if targets.insert(page_key(&r.from), target).is_some() {
+            return Err(RedirectError::Duplicate {
+                from: r.from.clone(),
+            });
+        }
+    }
+    for r in redirects {
+        let mut chain = vec![r.from.clone()];
+        let mut seen = vec![page_key(&r.from)];
+        let mut at = page_key(&r.from);
+        while let Some(Some(next)) = targets.get(&at) {
+            let rule = redirects.iter().find(|x| page_key(&x.from) == *next);
+            chain.push(rule.map_or_else(|| next.display().to_string(), |x| x.from.clone()));
+            if seen.contains(next) {
+                return Err(RedirectError::Loop { chain });
+            }
+            seen.push(next.clone());
+            at = next.clone();
+        }
+    }
+    Ok(())
+}
+
+/// The configured rules plus a 301 from each page alias to its page. An
+/// alias whose path a page, a copied file, a configured rule or an earlier
+/// alias already claims is dropped, with a warning on the page declaring it.
+pub(crate) fn site_redirects(
+    config: &SiteConfig,
+    pages: &mut [Page],
+    assets: &[(PathBuf, PathBuf)],
+) -> Vec<Redirect> {
+    let mut rules = config.redirects.clone();
+    let mut taken: HashSet<PathBuf> = pages
+        .iter()
+        .map(|p| p.output_path.clone())
+        .chain(assets.iter().map(|(_, dest)| dest.clone()))
+        .chain(rules.iter().map(|r| stub_path(&r.from, config)))
+        .collect();
+    for page in pages.iter_mut() {
+        for alias in aliases_of(page) {
+            let from = if alias.starts_with('/') {
+                alias
+            } else {
+                format!("/{alias}")
+            };
+            if taken.insert(stub_path(&from, config)) {
+                rules.push(Redirect::new(from, config.trailing_slash.apply(&page.url)));
+            } else {
+                page.warnings.push(format!(
+                    "alias `{from}` is already used by another page, file or redirect; skipping it"
+                ));
+            }
+        }
+    }
+    rules
+}
+
+/// A page's `aliases` / `redirect_from` frontmatter: a list, or a single
+/// string.
+fn aliases_of(page: &Page) -> Vec<String> {
+    let mut aliases = Vec::new();
+    for key in ALIAS_KEYS {
+        match page.frontmatter.get(key) {
+            Some(gray_matter::Pod::Array(items)) => {
+                aliases.extend(items.iter().filter_map(|item| match item {
+                    gray_matter::Pod::String(s) => Some(s.trim().to_string()),
+                    _ => None,
+                }))
+            }
+            Some(gray_matter::Pod::String(s)) => aliases.push(s.trim().to_string()),
+            _ => {}
+        }
+    }
+    aliases.retain(|a| !a.is_empty());
+    aliases
+}
+
+/// The rule redirecting `path` (a request path with the base path already
+/// stripped), if any. Only `serve` answers requests, so this is CLI-only.
+#[cfg(any(test, feature = "cli"))]
+pub(crate) fn find<'a>(redirects: &'a [Redirect], path: &str) -> Option<&'a Redirect> {
+    let key = page_key(path);
+    redirects.iter().find(|r| page_key(&r.from) == key)
+}
+
+/// Where the stub for `from` is written under the output directory.
+pub(crate) fn stub_path(from: &str, config: &SiteConfig) -> PathBuf {
+    config.output_dir.join(page_key(from))
+}
+
+/// Write a stub page for every rule, returning the files written and their
+/// sizes. With [`SiteConfig::dry_run`] nothing is written.
+pub(crate) fn write_stubs(
+    redirects: &[Redirect],
+    config: &SiteConfig,
+) -> Result<Vec<(PathBuf, u64)>, std::io::Error> {
+    let mut written = Vec::new();
+    for r in redirects {
+        let dest = stub_path(&r.from, config);
+        let html = stub_html(&r.target(&config.base_path), &config.language);
+        if !config.dry_run {
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, &html)?;
+        }
+        written.push((dest, html.len() as u64));
+    }
+    Ok(written)
+}
+
+/// A page that sends browsers (meta refresh), search engines (canonical
+/// link) and everyone else (a plain link) to `target`.
+fn stub_html(target: &str, lang: &str) -> String {
+    let target = escape_html(target);
+    let lang = escape_html(lang);
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"UTF-8\">\n\
+         <title>Redirecting…</title>\n<link rel=\"canonical\" href=\"{target}\">\n\
+         <meta http-equiv=\"refresh\" content=\"0; url={target}\">\n\
+         <meta name=\"robots\" content=\"noindex\">\n</head>\n<body>\n\
+         <p>This page has moved to <a href=\"{target}\">{target}</a>.</p>\n</body>\n</html>\n"
+    )
+}
+
+/// The output-relative file a path is served from: `/old`, `/old/` and
+/// `/old/index.html` are all `old/index.html`; a path whose last segment
+/// has an extension (`/feed.xml`) is that file. `.` and `..` segments are
+/// resolved so a rule can't reach outside the output directory.
+fn page_key(path: &str) -> PathBuf {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let segments = normalize_segments(path);
+    let is_file = !path.ends_with('/')
+        && segments
+            .last()
+            .is_some_and(|last| Path::new(last).extension().is_some());
+    let mut key: PathBuf = segments.iter().collect();
+    if !is_file {
+        key.push("index.html");
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::nav::test_support::{
+        make_page_with_data, pod_hash, test_config, test_config_with_base,
+    };
+    use gray_matter::Pod;
+
+    #[test]
+    fn paths_with_and_without_slash_share_a_stub() {
+        assert_eq!(page_key("/old"), PathBuf::from("old/index.html"));
+        assert_eq!(page_key("/old/"), PathBuf::from("old/index.html"));
+        assert_eq!(
+            page_key("/old/index.html?x=1"),
+            PathBuf::from("old/index.html")
+        );
+        assert_eq!(page_key("/feed.xml"), PathBuf::from("feed.xml"));
+        assert_eq!(page_key("/../../etc/"), PathBuf::from("etc/index.html"));
+        let rules = [Redirect::new("/old/", "/new/")];
+        assert!(find(&rules, "/old").is_some());
+        assert!(find(&rules, "/older/").is_none());
+    }
+
+    #[test]
+    fn parses_cli_format() {
+        assert_eq!(
+            "/a/ /b/".parse::<Redirect>(),
+            Ok(Redirect::new("/a/", "/b/"))
+        );
+        assert_eq!(
+            "/a/  https://x.com/  302".parse::<Redirect>(),
+            Ok(Redirect::new("/a/", "https://x.com/").with_status(302))
+        );
+        assert!("/a/".parse::<Redirect>().is_err());
+        assert!("/a/ /b/ moved".parse::<Redirect>().is_err());
+    }
+
+    #[test]
+    fn rejects_loops_duplicates_and_bad_rules() {
+        let ok = [
+            Redirect::new("/a/", "/b/"),
+            Redirect::new("/b", "/c/"),
+            Redirect::new("/x/", "https://example.com/x/"),
+        ];
+        assert!(validate(&ok).is_ok());
+
+        let looped = [
+            Redirect::new("/a/", "/b/"),
+            Redirect::new("/b/", "/c/"),
+            Redirect::new("/c", "/a/index.html"),
+        ];
+        assert_eq!(
+            validate(&looped).unwrap_err().to_string(),
+            "redirect loop: /a/ -> /b/ -> /c -> /a/"
+        );
+        assert!(matches!(
+            validate(&[Redirect::new("/a/", "/a")]),
+            Err(RedirectError::Loop { .. })
+        ));
+        assert!(matches!(
+            validate(&[Redirect::new("/a/", "/b/"), Redirect::new("/a", "/c/")]),
+            Err(RedirectError::Duplicate { .. })
+        ));
+        for bad in [
+            Redirect::new("a/", "/b/"),
+            Redirect::new("/a/", " "),
+            Redirect::new("/a/", "/b/").with_status(404),
+        ] {
+            assert!(
+                matches!(validate(&[bad]), Err(RedirectError::Invalid { .. })),
+                "expected invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn page_aliases_become_rules_unless_taken() {
+        let config = test_config().with_redirects([Redirect::new("/gone/", "/")]);
+        let aliases =
+            |items: &[&str]| Pod::Array(items.iter().map(|s| Pod::String(s.to_string())).collect());
+        let mut pages = vec![
+            make_page_with_data(
+                "post",
+                "Post",
+                pod_hash(&[
+                    ("aliases", aliases(&["/old-post/", "older", "/gone"])),
+                    ("redirect_from", Pod::String("/about/".to_string())),
+                ]),
+            ),
+            make_page_with_data("about", "About", Pod::Null),
+            make_page_with_data(
+                "other",
+                "Other",
+                pod_hash(&[("aliases", aliases(&["/old-post"]))]),
+            ),
+        ];
+        let rules = site_redirects(&config, &mut pages, &[]);
+        assert_eq!(
+            rules,
+            [
+                Redirect::new("/gone/", "/"),
+                Redirect::new("/old-post/", "/post/"),
+                Redirect::new("/older", "/post/"),
+            ]
+        );
+        assert_eq!(pages[0].warnings.len(), 2, "{:?}", pages[0].warnings);
+        assert!(pages[0].warnings[1].contains("`/about/`"));
+        assert_eq!(pages[2].warnings.len(), 1);
+    }
+
+    #[test]
+    fn stub_points_at_resolved_target() {
+        let config = test_config_with_base("/docs");
+        assert_eq!(
+            stub_path("/old/", &config),
+            PathBuf::from("_site/old/index.html")
+        );
+        let html = stub_html(
+            &Redirect::new("/old/", "/new/?a=1&b=2").target(&config.base_path),
+            &config.language,
+        );
+        assert!(
+            html.contains("<link rel=\"canonical\" href=\"/docs/new/?a=1&amp;b=2\">"),
+            "{html}"
+        );
+        assert!(html.contains("content=\"0; url=/docs/new/?a=1&amp;b=2\""));
+    }
+}
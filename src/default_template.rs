@@ -1,30 +1,86 @@
-use crate::{Breadcrumb, BuildError, NavItem, Page, PageContext};
+use crate::core::content::parser::escape_html;
+use crate::{Breadcrumb, BuildError, NavItem, Page, PageContext, Series, Translation};
 use sailfish::TemplateSimple;
 
 pub const DEFAULT_STYLE: &str = include_str!("../templates/style.css");
 
+/// Source of the bundled page template: the Sailfish markup [`render_page`]
+/// is compiled from. It isn't read at run time, so a copy is a starting
+/// point for a renderer of your own rather than a way to restyle this one.
+pub const DEFAULT_TEMPLATE: &str = include_str!("../templates/page.stpl");
+
+/// The bundled stylesheets, as (file name, contents), for tooling that
+/// lists or copies them.
+pub const EMBEDDED_STYLES: &[(&str, &str)] = &[("style.css", DEFAULT_STYLE)];
+
+/// The bundled templates, as (file name, source).
+pub const EMBEDDED_TEMPLATES: &[(&str, &str)] = &[("page.stpl", DEFAULT_TEMPLATE)];
+
 #[derive(TemplateSimple)]
 #[template(path = "page.stpl")]
 struct PageTemplate<'a> {
     title: &'a str,
     content: &'a str,
+    toc: Option<&'a str>,
     nav: &'a [NavItem],
     breadcrumbs: &'a [Breadcrumb],
     prev: Option<&'a NavItem>,
     next: Option<&'a NavItem>,
-    base_path: &'a str,
+    series: Option<&'a Series>,
+    related: &'a [NavItem],
+    stylesheet: &'a str,
+    print_stylesheet: Option<&'a str>,
+    stylesheets: &'a [String],
+    lang: &'a str,
+    translations: &'a [Translation],
+    head: &'a str,
+    footer: &'a str,
+    last_modified: Option<&'a str>,
+    body_attrs: String,
+    date: Option<String>,
 }
 
 pub fn render_page(page: &Page, ctx: &PageContext) -> Result<String, BuildError> {
     PageTemplate {
         title: &page.frontmatter.title,
         content: &page.content_html,
-        nav: &ctx.nav,
+        toc: page.toc_html.as_deref(),
+        // A configured menu replaces the automatic nav.
+        nav: if ctx.menu.is_empty() {
+            &ctx.nav
+        } else {
+            &ctx.menu
+        },
         breadcrumbs: &ctx.breadcrumbs,
         prev: ctx.prev.as_ref(),
         next: ctx.next.as_ref(),
-        base_path: &ctx.base_path,
+        series: ctx.series.as_ref(),
+        related: &ctx.related,
+        stylesheet: &ctx.stylesheet,
+        print_stylesheet: ctx.print_stylesheet.as_deref(),
+        stylesheets: &ctx.stylesheets,
+        lang: &ctx.lang,
+        translations: &ctx.translations,
+        head: &ctx.head,
+        footer: &ctx.footer,
+        last_modified: page.last_modified.as_deref(),
+        body_attrs: body_attrs(page),
+        date: page.date.as_ref().map(|d| d.to_html()),
     }
     .render_once()
     .map_err(|e| BuildError::Render(e.to_string()))
 }
+
+/// `<body>` attributes from the page's `body_class` / `body_id` frontmatter
+/// (` class="landing" id="home"`), escaped; empty when neither is set.
+fn body_attrs(page: &Page) -> String {
+    let mut attrs = String::new();
+    for (attr, key) in [("class", "body_class"), ("id", "body_id")] {
+        if let Some(value) = page.frontmatter.get_string(key)
+            && !value.trim().is_empty()
+        {
+            attrs.push_str(&format!(" {attr}=\"{}\"", escape_html(value.trim())));
+        }
+    }
+    attrs
+}
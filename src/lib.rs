@@ -22,6 +22,10 @@
 //! `content/blog/post.md` → `_site/blog/post/index.html`, served as
 //! `/blog/post/`.
 //!
+//! The two halves are public too: [`load_content`] parses everything into a
+//! [`SiteContent`] without writing a file, and [`build_content`] renders one —
+//! so a pipeline can index, filter or rewrite pages in between.
+//!
 //! ```no_run
 //! use sherwood::{BuildError, ParserRegistry, SiteConfig, build_site};
 //!
@@ -41,16 +45,20 @@
 //!
 //! # Cargo features
 //!
-//! Both are enabled by default; the `sherwood` binary needs both.
+//! All are enabled by default; the `sherwood` binary needs `cli` and
+//! `default-template`.
 //!
 //! - `cli` — the clap `build`/`serve` CLI (`run_cli`, `try_run_cli`, `Asset`)
 //!   with a file-watching, live-reloading dev server.
 //! - `default-template` — the bundled Sailfish template and stylesheet
-//!   (`render_page`, `DEFAULT_STYLE`).
+//!   (`render_page`, `DEFAULT_STYLE`, and the `EMBEDDED_STYLES` /
+//!   `EMBEDDED_TEMPLATES` listings).
+//! - `compress` — `compress_output`, which writes gzip / Brotli siblings of
+//!   output files (flate2, brotli).
 //!
 //! With `default-features = false` the headless core remains: [`build_site`],
-//! the parser API, and the nav types — no clap, tokio, axum, or sailfish in
-//! your dependency tree.
+//! the parser API, and the nav types — no clap, tokio, axum, sailfish, or
+//! compression crates in your dependency tree.
 
 mod core;
 
@@ -60,18 +68,50 @@ mod cli;
 #[cfg(feature = "default-template")]
 mod default_template;
 
-pub use core::build::{BuildError, build_site};
-pub use core::config::SiteConfig;
+pub use core::a11y::{A11yIssue, A11yIssueKind, check_accessibility};
+pub use core::build::{
+    BuildError, BuildReport, SiteContent, build_content, build_feeds, build_site,
+    build_site_with_report, load_content,
+};
+#[cfg(feature = "compress")]
+pub use core::compress::compress_output;
+pub use core::compress::{
+    CompressConfig, CompressFormat, DEFAULT_COMPRESS_EXTENSIONS, DEFAULT_COMPRESS_MIN_SIZE,
+};
+pub use core::config::{
+    CONFIG_FILE, ConfigError, DEFAULT_LANGUAGE, DEFAULT_STYLESHEET, SiteConfig,
+};
 pub use core::content::frontmatter::{FrontMatter, FrontmatterError, split_frontmatter};
+pub use core::content::ignore::DRAFTS_DIR;
+pub use core::content::images::ImageFingerprints;
 pub use core::content::page::{Page, PageError};
+#[cfg(feature = "asciidoc")]
+pub use core::content::parser::{ASCIIDOC_EXTENSIONS, AsciidocParser};
 pub use core::content::parser::{
-    ContentParser, MarkdownParser, Parsed, ParserError, ParserRegistry, markdown_to_html,
+    AnchorPosition, ContentParser, DEFAULT_EXCERPT_SEPARATOR, HTML_EXTENSIONS, HeadingAnchors,
+    HtmlParser, MARKDOWN_EXTENSIONS, MarkdownFeatures, MarkdownParser, Parsed, ParserError,
+    ParserRegistry, Shortcode, ShortcodeCall, ShortcodeRegistry, markdown_to_html,
+};
+pub use core::content::section::{SECTION_TITLE, SectionError};
+pub use core::content::transform::HtmlTransform;
+pub use core::content::url_strategy::UrlStrategy;
+pub use core::content::validation::ValidationRule;
+pub use core::date::{DEFAULT_DATE_FORMAT, PageDate, RELATIVE_DATE_FORMAT, UtcOffset};
+pub use core::export::build_single_file;
+pub use core::feed::{FeedConfig, FeedFormat};
+pub use core::footer::FooterConfig;
+pub use core::head::HeadTag;
+pub use core::nav::{
+    Breadcrumb, DEFAULT_RELATED_LIMIT, ListSort, MenuItem, NavItem, PageContext, PageGroup, Series,
+    TrailingSlash, Translation, UNDATED_GROUP,
 };
-pub use core::nav::{Breadcrumb, NavItem, PageContext};
+pub use core::redirect::{Redirect, RedirectError};
 pub use gray_matter::Pod;
 
 #[cfg(feature = "cli")]
 pub use cli::{Asset, CliError, run_cli, try_run_cli, try_run_cli_from};
 
 #[cfg(feature = "default-template")]
-pub use default_template::{DEFAULT_STYLE, render_page};
+pub use default_template::{
+    DEFAULT_STYLE, DEFAULT_TEMPLATE, EMBEDDED_STYLES, EMBEDDED_TEMPLATES, render_page,
+};
@@ -31,7 +31,7 @@ fn build_full_site_against_fixture() {
     );
     write(
         &content.join("about.md"),
-        "---\ntitle: About\ndescription: About this site.\n---\n\nAbout body.\n",
+        "---\ntitle: About\ndescription: About this site.\nbody_class: wide \"quoted\"\nbody_id: about\n---\n\nAbout body.\n",
     );
     write(
         &content.join("blog/index.md"),
@@ -70,8 +70,12 @@ fn build_full_site_against_fixture() {
     // Home renders body + bundled nav linking the section index
     let home = fs::read_to_string(output.join("index.html")).unwrap();
     assert!(home.contains("<title>Home</title>"));
-    assert!(home.contains("<h1>Welcome</h1>"));
+    assert!(home.contains("<h1 id=\"welcome\">Welcome</h1>"));
     assert!(home.contains("<link rel=\"stylesheet\" href=\"/style.css\">"));
+    assert!(
+        !home.contains("media=\"print\""),
+        "no print stylesheet configured"
+    );
     assert!(home.contains("href=\"/blog/\""), "nav should link /blog/");
     // Blog post is a leaf — nav scoping should hide it
     assert!(
@@ -83,6 +87,11 @@ fn build_full_site_against_fixture() {
     let about = fs::read_to_string(output.join("about/index.html")).unwrap();
     assert!(about.contains("<title>About</title>"));
     assert!(about.contains("<p>About body.</p>"));
+    assert!(
+        about.contains("<body class=\"wide &quot;quoted&quot;\" id=\"about\">"),
+        "body attrs from frontmatter, escaped:\n{about}"
+    );
+    assert!(home.contains("<body>"), "no body attrs without frontmatter");
     assert!(
         about.contains("href=\"/about/\" aria-current=\"page\""),
         "current page should be marked"
@@ -91,6 +100,10 @@ fn build_full_site_against_fixture() {
     // Blog post: breadcrumb includes Home + Blog + leaf title
     let post = fs::read_to_string(output.join("blog/first/index.html")).unwrap();
     assert!(post.contains("<title>First Post</title>"));
+    assert!(
+        post.contains("<time datetime=\"2026-05-30\">May 30, 2026</time>"),
+        "post date should be a <time> element:\n{post}"
+    );
     assert!(post.contains("href=\"/blog/\""));
 }
 
@@ -184,3 +197,41 @@ fn build_reports_frontmatter_error_with_snippet() {
         "line-numbered indent missing:\n{stderr}"
     );
 }
+
+#[test]
+fn failed_build_reports_a_json_error_event() {
+    let bin = env!("CARGO_BIN_EXE_sherwood");
+    let tmp = TempDir::new().unwrap();
+    let content = tmp.path().join("content");
+    let output = tmp.path().join("out");
+
+    write(&content.join("index.md"), "---\ntitle: Home\n---\n");
+    write(&content.join("bad.md"), "---\nfoo: bar\n---\n\nBody.\n");
+
+    let result = Command::new(bin)
+        .args([
+            "build",
+            "--log-format",
+            "json",
+            "--content-dir",
+            content.to_str().unwrap(),
+            "--output-dir",
+            output.to_str().unwrap(),
+        ])
+        .output()
+        .expect("failed to launch sherwood binary");
+
+    assert!(!result.status.success(), "build should fail");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let last = stdout.lines().last().unwrap_or_default();
+    assert!(
+        last.starts_with('{') && last.contains("\"level\":\"error\""),
+        "stdout:\n{stdout}"
+    );
+    assert!(
+        last.contains("missing required field `title`"),
+        "stdout:\n{stdout}"
+    );
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.is_empty(), "stderr:\n{stderr}");
+}
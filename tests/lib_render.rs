@@ -8,7 +8,10 @@ use std::cell::{Cell, RefCell};
 use std::fs;
 use std::path::Path;
 
-use sherwood::{BuildError, Page, PageContext, ParserRegistry, SiteConfig, build_site};
+use sherwood::{
+    BuildError, Page, PageContext, ParserRegistry, SiteConfig, build_content, build_site,
+    load_content,
+};
 use tempfile::TempDir;
 
 fn write(path: &Path, body: &str) {
@@ -73,7 +76,7 @@ fn custom_renderer_output_is_written_at_pretty_urls() {
     // Pretty-URL layout: every page becomes <dir>/index.html.
     let home = fs::read_to_string(out.join("index.html")).unwrap();
     assert!(home.contains("data-title=\"Home\""));
-    assert!(home.contains("<h1>Welcome</h1>"));
+    assert!(home.contains("<h1 id=\"welcome\">Welcome</h1>"));
 
     let about = fs::read_to_string(out.join("about/index.html")).unwrap();
     assert!(about.contains("data-title=\"About\""));
@@ -84,6 +87,41 @@ fn custom_renderer_output_is_written_at_pretty_urls() {
     assert!(out.join("blog/second/index.html").exists());
 }
 
+#[test]
+fn content_can_be_transformed_between_loading_and_building() {
+    let (_tmp, config) = fixture();
+    let out = config.output_dir.clone();
+
+    let mut content = load_content(&config, &ParserRegistry::default()).unwrap();
+    // Nothing is written until the content is built.
+    assert!(!out.exists());
+    let urls: Vec<&str> = content.pages.iter().map(|p| p.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        ["/", "/about/", "/blog/first/", "/blog/", "/blog/second/"]
+    );
+
+    content.pages.retain(|p| p.url != "/about/");
+    for page in &mut content.pages {
+        page.content_html = page.content_html.to_uppercase();
+    }
+    let report = build_content(
+        &config,
+        content,
+        |page: &Page, ctx: &PageContext| {
+            assert!(ctx.pages.iter().all(|p| p.url != "/about/"));
+            Ok(page.content_html.clone())
+        },
+        |_| {},
+    )
+    .unwrap();
+
+    assert_eq!(report.pages, 4);
+    assert!(!out.join("about/index.html").exists());
+    let second = fs::read_to_string(out.join("blog/second/index.html")).unwrap();
+    assert!(second.contains("SECOND BODY."), "{second}");
+}
+
 #[test]
 fn renderer_receives_nav_breadcrumbs_and_prev_next() {
     let (_tmp, config) = fixture();
@@ -284,6 +322,7 @@ impl ContentParser for ShoutParser {
             },
             content_html: format!("<p>{body}</p>"),
             excerpt_html: None,
+            warnings: Vec::new(),
         })
     }
 }